@@ -3,7 +3,9 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command as TokioCommand;
 use std::fs;
-use crate::commands::binary_utils::get_ffmpeg_path;
+use crate::commands::binary_utils::{app_temp_dir, get_ffmpeg_path};
+use crate::commands::openai::app_openai_config;
+use crate::commands::operations::{cancellable_output, TrackedOutputAsync};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FilterResult {
@@ -25,8 +27,55 @@ const FILTERS: &[(&str, &str)] = &[
     ("pixelate", "scale=iw/8:ih/8:flags=neighbor,scale=iw*8:ih*8:flags=neighbor"),
     ("emboss", "convolution=0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0"),
     ("oil_paint", "gblur=sigma=1.5,eq=saturation=1.5"),
+    ("noir", "hue=s=0,curves=all='0/0 0.2/0.05 0.5/0.5 0.8/0.95 1/1',vignette=PI/4,noise=alls=8:allf=t"),
+    // Strong cross-channel terms mimic the dye bleed between the three Technicolor strips
+    ("technicolor", "colorchannelmixer=rr=1.2:rg=-0.1:rb=-0.1:gr=-0.15:gg=1.15:gb=0.1:br=0.05:bg=-0.15:bb=1.1,eq=saturation=1.25:contrast=1.05"),
 ];
 
+// Film emulation grades; approximations built from curves, colorbalance and eq
+const COLOR_GRADE_PRESETS: &[(&str, &str)] = &[
+    ("kodachrome", "curves=r='0/0.05 0.5/0.6 1/1':g='0/0 0.5/0.55 1/0.95':b='0/0.1 0.5/0.5 1/0.85'"),
+    ("fujifilm_superia", "curves=r='0/0 0.5/0.48 1/0.95':g='0/0.02 0.5/0.55 1/1':b='0/0.05 0.5/0.52 1/0.92',eq=saturation=1.1"),
+    ("cinematic_teal_orange", "colorbalance=rs=-0.15:gs=0.05:bs=0.2:rh=0.2:gh=0.05:bh=-0.15,eq=contrast=1.1:saturation=1.15"),
+    ("instagram_clarendon", "eq=contrast=1.2:saturation=1.35,colorbalance=bs=0.1:rh=0.05"),
+    ("lomography", "curves=r='0/0 0.25/0.2 0.75/0.85 1/1':g='0/0 0.25/0.2 0.75/0.85 1/1':b='0/0.1 1/0.9',eq=saturation=1.4,vignette=PI/4"),
+];
+
+// Exposure correction algorithms; FFmpeg has no native CLAHE, so "clahe" is an approximation
+const EXPOSURE_ALGORITHMS: &[(&str, &str)] = &[
+    ("autolevels", "histeq=strength=0.2:intensity=0.2:antibanding=strong"),
+    ("histogram_equalization", "histeq=strength=0.9"),
+    ("clahe", "hflip,histeq,hflip"),
+    ("stretch", "normalize=blackpt=black:whitept=white"),
+];
+
+// FFmpeg audio filter definitions
+const AUDIO_FILTERS: &[(&str, &str)] = &[
+    ("bass_boost", "equalizer=f=100:t=h:w=200:g=5"),
+    ("treble_boost", "equalizer=f=8000:t=h:w=3000:g=5"),
+    ("telephone", "highpass=f=300,lowpass=f=3000"),
+    ("reverb", "aecho=0.8:0.88:60:0.4"),
+    ("chorus", "chorus=0.5:0.9:50|60:0.4|0.32:0.25|0.4:2|1.3"),
+    ("pitch_up", "asetrate=48000*1.1,aresample=48000"),
+    ("pitch_down", "asetrate=48000*0.9,aresample=48000"),
+];
+
+/// Join FILTERS entries into a single `-vf` chain
+fn build_filter_chain(filters: &[String]) -> Result<String, String> {
+    let mut filter_chain = String::new();
+    for (i, filter_id) in filters.iter().enumerate() {
+        if let Some((_, ffmpeg_filter)) = FILTERS.iter().find(|(id, _)| id == filter_id) {
+            if i > 0 {
+                filter_chain.push(',');
+            }
+            filter_chain.push_str(ffmpeg_filter);
+        } else {
+            return Err(format!("Unknown filter: {}", filter_id));
+        }
+    }
+    Ok(filter_chain)
+}
+
 #[command]
 pub async fn apply_filters(
     app: AppHandle,
@@ -52,7 +101,7 @@ pub async fn apply_filters(
     let output_filename = format!("{}_filtered_{}.{}", stem, timestamp, extension);
     
     // Create temp directory for processed files
-    let temp_dir = std::env::temp_dir().join("clipforge_processed");
+    let temp_dir = app_temp_dir(&app);
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
     
@@ -61,18 +110,7 @@ pub async fn apply_filters(
     
     let output_path = temp_dir.join(&output_filename);
 
-    // Build FFmpeg filter chain
-    let mut filter_chain = String::new();
-    for (i, filter_id) in filters.iter().enumerate() {
-        if let Some((_, ffmpeg_filter)) = FILTERS.iter().find(|(id, _)| id == filter_id) {
-            if i > 0 {
-                filter_chain.push(',');
-            }
-            filter_chain.push_str(ffmpeg_filter);
-        } else {
-            return Err(format!("Unknown filter: {}", filter_id));
-        }
-    }
+    let filter_chain = build_filter_chain(&filters)?;
 
     println!("FFmpeg filter chain: {}", filter_chain);
 
@@ -88,10 +126,7 @@ pub async fn apply_filters(
         .arg(&output_path);
 
     // Execute FFmpeg
-    let output = ffmpeg_cmd
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+    let output = cancellable_output(&app, "filters", input_path, &output_path, &mut ffmpeg_cmd).await?;
 
     if !output.status.success() {
         let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -108,6 +143,136 @@ pub async fn apply_filters(
     })
 }
 
+/// Render a quick, low-quality preview of `filters`; a duration of 0 produces a single JPEG frame
+#[command]
+pub async fn preview_filters(
+    app: AppHandle,
+    input_path: String,
+    filters: Vec<String>,
+    preview_duration: Option<f64>,
+) -> Result<FilterResult, String> {
+    let preview_duration = preview_duration.unwrap_or(3.0);
+    if !preview_duration.is_finite() || preview_duration < 0.0 {
+        return Err("Preview duration must be zero or positive".to_string());
+    }
+
+    let input_path_obj = Path::new(&input_path);
+    if !input_path_obj.exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+    let stem = input_path_obj.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid input path")?;
+
+    let thumbnail_mode = preview_duration == 0.0;
+    let mut filter_chain = build_filter_chain(&filters)?;
+    if thumbnail_mode {
+        // Pick the most representative of the first few frames rather than the very first one
+        if !filter_chain.is_empty() {
+            filter_chain.push(',');
+        }
+        filter_chain.push_str("thumbnail=25");
+    }
+
+    let temp_dir = app_temp_dir(&app);
+    std::fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let extension = if thumbnail_mode { "jpg" } else { "mp4" };
+    let output_path = temp_dir.join(format!("{}_preview_{}.{}", stem, uuid::Uuid::new_v4(), extension));
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd.arg("-i").arg(&input_path);
+    if !filter_chain.is_empty() {
+        ffmpeg_cmd.arg("-vf").arg(&filter_chain);
+    }
+    if thumbnail_mode {
+        ffmpeg_cmd.arg("-vframes").arg("1").arg("-q:v").arg("3");
+    } else {
+        // Speed matters more than quality here, and previews play muted
+        ffmpeg_cmd
+            .arg("-t")
+            .arg(preview_duration.to_string())
+            .arg("-c:v")
+            .arg("libx264")
+            .arg("-preset")
+            .arg("ultrafast")
+            .arg("-crf")
+            .arg("35")
+            .arg("-an");
+    }
+    ffmpeg_cmd.arg("-y").arg(&output_path);
+
+    let output = cancellable_output(&app, "filter_preview", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path: output_path.to_string_lossy().to_string(),
+        success: true,
+        message: if thumbnail_mode {
+            format!("Rendered preview frame with {} filters", filters.len())
+        } else {
+            format!("Rendered {:.1}s preview with {} filters", preview_duration, filters.len())
+        },
+    })
+}
+
+#[command]
+pub async fn apply_audio_filters(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    filters: Vec<String>,
+) -> Result<FilterResult, String> {
+    println!("Applying audio filters: {:?} to {}", filters, input_path);
+
+    if filters.is_empty() {
+        return Err("No audio filters selected".to_string());
+    }
+
+    // Build FFmpeg audio filter chain
+    let mut filter_chain = Vec::with_capacity(filters.len());
+    for filter_id in &filters {
+        match AUDIO_FILTERS.iter().find(|(id, _)| id == filter_id) {
+            Some((_, ffmpeg_filter)) => filter_chain.push(*ffmpeg_filter),
+            None => return Err(format!("Unknown audio filter: {}", filter_id)),
+        }
+    }
+    // The pitch filters assume 48 kHz, so resample first
+    let filter_chain = format!("aresample=48000,{}", filter_chain.join(","));
+
+    println!("FFmpeg audio filter chain: {}", filter_chain);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-af")
+        .arg(&filter_chain)
+        .arg("-c:v")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "audio_filters", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path,
+        success: true,
+        message: format!("Applied {} audio filters successfully", filters.len()),
+    })
+}
+
 #[command]
 pub async fn upscale_media(
     app: AppHandle,
@@ -135,7 +300,7 @@ pub async fn upscale_media(
     let output_filename = format!("{}_upscaled_{}x_{}.{}", stem, scale_factor, timestamp, extension);
     
     // Create temp directory for processed files
-    let temp_dir = std::env::temp_dir().join("clipforge_processed");
+    let temp_dir = app_temp_dir(&app);
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
     
@@ -155,7 +320,7 @@ pub async fn upscale_media(
         if file_type == "video" {
             return upscale_video_with_openai(&app, input_path, scale_factor, "dalle", &output_path).await;
         } else {
-            return upscale_with_openai(input_path, scale_factor, file_type, "dalle", &output_path).await;
+            return upscale_with_openai(&app, input_path, scale_factor, file_type, "dalle", &output_path).await;
         }
     }
     
@@ -194,7 +359,7 @@ pub async fn upscale_media(
 
     // Execute FFmpeg
     let output = ffmpeg_cmd
-        .output()
+        .tracked_output(&app, "upscale", input_path, &output_path)
         .await
         .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
 
@@ -241,6 +406,346 @@ pub async fn copy_file_to_location(source_path: &str, destination_path: &str) ->
     Ok(destination_path.to_string())
 }
 
+/// Convert a color temperature to per-channel gains using Tanner Helland's approximation.
+/// Neutral daylight (around 6500 K) maps to roughly (1.0, 1.0, 1.0).
+pub fn kelvin_to_rgb_multipliers(k: f64) -> (f64, f64, f64) {
+    let temp = k / 100.0;
+
+    let red = if temp <= 66.0 {
+        255.0
+    } else {
+        329.698727446 * (temp - 60.0).powf(-0.1332047592)
+    };
+
+    let green = if temp <= 66.0 {
+        99.4708025861 * temp.ln() - 161.1195681661
+    } else {
+        288.1221695283 * (temp - 60.0).powf(-0.0755148492)
+    };
+
+    let blue = if temp >= 66.0 {
+        255.0
+    } else if temp <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (temp - 10.0).ln() - 305.0447927307
+    };
+
+    (
+        red.clamp(0.0, 255.0) / 255.0,
+        green.clamp(0.0, 255.0) / 255.0,
+        blue.clamp(0.0, 255.0) / 255.0,
+    )
+}
+
+#[command]
+pub async fn apply_color_temperature(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    temperature_kelvin: f64,
+) -> Result<FilterResult, String> {
+    if !(2000.0..=10000.0).contains(&temperature_kelvin) {
+        return Err("Color temperature must be between 2000 K and 10000 K".to_string());
+    }
+
+    let (red_gain, green_gain, blue_gain) = kelvin_to_rgb_multipliers(temperature_kelvin);
+    let filter = format!(
+        "colorchannelmixer=rr={:.4}:gg={:.4}:bb={:.4}",
+        red_gain, green_gain, blue_gain
+    );
+    println!("Applying color temperature {}K: {}", temperature_kelvin, filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path)
+        .tracked_output(&app, "color_temperature", &input_path, &output_path)
+        .await
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path,
+        success: true,
+        message: format!("Applied {}K color temperature", temperature_kelvin),
+    })
+}
+
+/// Remove lens vignetting, or add it for effect with `mode: "vignette_add"`
+#[command]
+pub async fn correct_vignette(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    strength: f64,
+    midpoint: f64,
+    mode: Option<String>,
+) -> Result<FilterResult, String> {
+    if !(0.0..=1.0).contains(&strength) {
+        return Err("Strength must be between 0.0 and 1.0".to_string());
+    }
+    if !(0.0..1.0).contains(&midpoint) {
+        return Err("Midpoint must be at least 0.0 and below 1.0".to_string());
+    }
+    let add = match mode.as_deref().unwrap_or("correct") {
+        "correct" => false,
+        "vignette_add" => true,
+        other => return Err(format!("Unknown vignette mode: {}. Use correct or vignette_add", other)),
+    };
+
+    // vignette has no option for where the falloff starts, so a midpoint needs a geq radial gain
+    let filter = if midpoint == 0.0 {
+        format!(
+            "vignette=angle={:.4}:mode={}:eval=init:x0=w/2:y0=h/2:aspect=1",
+            strength * std::f64::consts::FRAC_PI_2,
+            if add { "forward" } else { "backward" }
+        )
+    } else {
+        // Quadratic ramp from 0 at the midpoint to `strength` in the corners
+        let ramp = format!(
+            "{:.4}*pow(max(0,(hypot(X-W/2,Y-H/2)/hypot(W/2,H/2)-{:.4})/{:.4}),2)",
+            strength,
+            midpoint,
+            1.0 - midpoint
+        );
+        format!(
+            "format=yuv420p,geq=lum='clip(lum(X,Y)*(1{}{}),0,255)':cb='cb(X,Y)':cr='cr(X,Y)'",
+            if add { "-" } else { "+" },
+            ramp
+        )
+    };
+    println!("Vignette {} filter: {}", if add { "add" } else { "correction" }, filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "vignette", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path,
+        success: true,
+        message: format!(
+            "{} vignetting at {:.0}% strength",
+            if add { "Added" } else { "Corrected" },
+            strength * 100.0
+        ),
+    })
+}
+
+/// Filter graph for a color grade preset mixed over the original by `intensity`
+fn color_grade_filter(preset: &str, intensity: f64) -> Result<String, String> {
+    let (_, grade) = COLOR_GRADE_PRESETS
+        .iter()
+        .find(|(id, _)| *id == preset)
+        .ok_or_else(|| format!("Unknown color grade preset: {}", preset))?;
+
+    Ok(format!(
+        "split[original][tograde];[tograde]{}[graded];[graded][original]blend=all_expr='A*{:.3}+B*(1-{:.3})'",
+        grade, intensity, intensity
+    ))
+}
+
+#[command]
+pub async fn get_color_grade_presets() -> Result<Vec<String>, String> {
+    Ok(COLOR_GRADE_PRESETS.iter().map(|(id, _)| id.to_string()).collect())
+}
+
+#[command]
+pub async fn apply_color_grade(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    preset: String,
+    intensity: f64,
+) -> Result<FilterResult, String> {
+    if !(0.0..=1.0).contains(&intensity) {
+        return Err("Intensity must be between 0.0 and 1.0".to_string());
+    }
+
+    let filter = color_grade_filter(&preset, intensity)?;
+    println!("Applying color grade {}: {}", preset, filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "color_grade", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path,
+        success: true,
+        message: format!("Applied {} grade at {:.0}% intensity", preset, intensity * 100.0),
+    })
+}
+
+#[command]
+pub async fn get_auto_correction_algorithms() -> Result<Vec<String>, String> {
+    Ok(EXPOSURE_ALGORITHMS.iter().map(|(id, _)| id.to_string()).collect())
+}
+
+/// Fix under- or over-exposed footage with histogram equalization or level stretching
+#[command]
+pub async fn auto_correct_exposure(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    algorithm: String,
+) -> Result<FilterResult, String> {
+    let (_, filter) = EXPOSURE_ALGORITHMS
+        .iter()
+        .find(|(id, _)| *id == algorithm)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = EXPOSURE_ALGORITHMS.iter().map(|(id, _)| *id).collect();
+            format!("Unknown algorithm: {}. Valid options: {}", algorithm, valid.join(", "))
+        })?;
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(filter)
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "exposure", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path,
+        success: true,
+        message: format!("Corrected exposure with {}", algorithm),
+    })
+}
+
+// Output formats supported by convert_image_format
+const IMAGE_OUTPUT_FORMATS: &[&str] = &["png", "jpg", "webp", "tiff", "bmp", "avif"];
+
+#[command]
+pub async fn convert_image_format(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    quality: Option<u32>,
+) -> Result<String, String> {
+    if !Path::new(&input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    let output_extension = Path::new(&output_path)
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_lowercase())
+        .ok_or("Output path has no file extension")?;
+    if !IMAGE_OUTPUT_FORMATS.contains(&output_extension.as_str()) {
+        return Err(format!(
+            "Unsupported output format: {}. Supported: {}",
+            output_extension,
+            IMAGE_OUTPUT_FORMATS.join(", ")
+        ));
+    }
+
+    let is_heic = Path::new(&input_path)
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| matches!(s.to_lowercase().as_str(), "heic" | "heif"))
+        .unwrap_or(false);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = TokioCommand::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-frames:v")
+        .arg("1");
+
+    if output_extension == "avif" {
+        ffmpeg_cmd
+            .arg("-c:v")
+            .arg("libaom-av1")
+            .arg("-crf")
+            .arg("23")
+            .arg("-still-picture")
+            .arg("1");
+    } else if let Some(quality) = quality {
+        ffmpeg_cmd.arg("-q:v").arg(quality.to_string());
+    }
+
+    ffmpeg_cmd.arg("-y").arg(&output_path);
+
+    let output = ffmpeg_cmd
+        .tracked_output(&app, "convert_image", &input_path, &output_path)
+        .await
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+
+        // Outside macOS FFmpeg usually lacks a HEIC decoder, so try the image crate instead
+        if is_heic && !cfg!(target_os = "macos") {
+            return image::open(&input_path)
+                .and_then(|img| img.save(&output_path))
+                .map(|_| output_path.clone())
+                .map_err(|e| {
+                    format!(
+                        "HEIC conversion requires FFmpeg built with VideoToolbox ({}). FFmpeg error: {}",
+                        e, error_msg
+                    )
+                });
+        }
+
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    println!("Converted {} to {}", input_path, output_path);
+    Ok(output_path)
+}
+
 // Helper function to clean up old temp files
 fn cleanup_old_temp_files(temp_dir: &std::path::Path) -> Result<(), std::io::Error> {
     use std::fs;
@@ -358,8 +863,212 @@ fn get_models_directory() -> Result<std::path::PathBuf, String> {
     Ok(models_dir)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilmLook {
+    pub id: String,
+    pub name: String,
+    pub style: String,
+}
+
+/// Maps one RGB sample (0.0 - 1.0 per channel) to its graded value
+type FilmGrade = fn((f64, f64, f64)) -> (f64, f64, f64);
+
+// Film looks, each generated as a .cube LUT from its grade (id, name, style, grade)
+const FILM_LOOKS: &[(&str, &str, &str, FilmGrade)] = &[
+    ("cinematic", "Cinematic", "Teal shadows and warm highlights", cinematic_grade),
+    ("warm_print", "Warm Print", "Print film style with rich contrast and lifted blacks", warm_print_grade),
+    ("soft_green", "Soft Green", "Soft greens and gentle highlight roll-off", soft_green_grade),
+    ("bleach_bypass", "Bleach Bypass", "Desaturated, high-contrast silver retention", bleach_bypass_grade),
+];
+
+// Lattice points per axis of the generated LUTs
+const FILM_LOOK_LUT_SIZE: usize = 17;
+
+fn rec709_luma((r, g, b): (f64, f64, f64)) -> f64 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Smoothstep S-curve mixed over the identity by `amount`
+fn s_curve(x: f64, amount: f64) -> f64 {
+    let x = x.clamp(0.0, 1.0);
+    x + (x * x * (3.0 - 2.0 * x) - x) * amount
+}
+
+/// Shadows pushed toward teal and highlights toward orange, with mild contrast
+fn cinematic_grade(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    let luma = rec709_luma(rgb);
+    let shadow = 1.0 - luma;
+    (
+        s_curve(rgb.0 + 0.06 * luma - 0.03 * shadow, 0.3),
+        s_curve(rgb.1 + 0.02 * shadow, 0.3),
+        s_curve(rgb.2 + 0.07 * shadow - 0.05 * luma, 0.3),
+    )
+}
+
+/// Print-style contrast over warmed channels, with blacks lifted to 0.03
+fn warm_print_grade((r, g, b): (f64, f64, f64)) -> (f64, f64, f64) {
+    let print = |x: f64| 0.03 + s_curve(x, 0.4) * 0.97;
+    (print(r * 1.05), print(g), print(b * 0.92))
+}
+
+/// Slightly desaturated, greener midtones, and highlights above 0.8 compressed
+fn soft_green_grade(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    let luma = rec709_luma(rgb);
+    let soften = |x: f64| x + (luma - x) * 0.1;
+    let roll_off = |x: f64| if x > 0.8 { 0.8 + (x - 0.8) * 0.6 } else { x };
+    let midtones = 4.0 * luma * (1.0 - luma);
+    (roll_off(soften(rgb.0)), roll_off(soften(rgb.1) + 0.04 * midtones), roll_off(soften(rgb.2)))
+}
+
+/// Luma mixed 60% over the color, then a strong S-curve, as skipping the bleach leaves silver over the dyes
+fn bleach_bypass_grade(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    let luma = rec709_luma(rgb);
+    let bypass = |x: f64| s_curve(x + (luma - x) * 0.6, 0.6);
+    (bypass(rgb.0), bypass(rgb.1), bypass(rgb.2))
+}
+
+/// A film look's grade as .cube text, red varying fastest
+fn film_look_cube(name: &str, grade: FilmGrade) -> String {
+    let mut cube = format!(
+        "TITLE \"ClipForge {}\"\nLUT_3D_SIZE {}\nDOMAIN_MIN 0.0 0.0 0.0\nDOMAIN_MAX 1.0 1.0 1.0\n",
+        name, FILM_LOOK_LUT_SIZE
+    );
+    let level = |index: usize| index as f64 / (FILM_LOOK_LUT_SIZE - 1) as f64;
+    for b in 0..FILM_LOOK_LUT_SIZE {
+        for g in 0..FILM_LOOK_LUT_SIZE {
+            for r in 0..FILM_LOOK_LUT_SIZE {
+                let (r, g, b) = grade((level(r), level(g), level(b)));
+                cube.push_str(&format!(
+                    "{:.6} {:.6} {:.6}\n",
+                    r.clamp(0.0, 1.0),
+                    g.clamp(0.0, 1.0),
+                    b.clamp(0.0, 1.0)
+                ));
+            }
+        }
+    }
+    cube
+}
+
+#[command]
+pub async fn get_available_film_looks() -> Result<Vec<FilmLook>, String> {
+    Ok(FILM_LOOKS
+        .iter()
+        .map(|(id, name, style, _)| FilmLook {
+            id: id.to_string(),
+            name: name.to_string(),
+            style: style.to_string(),
+        })
+        .collect())
+}
+
+/// Path of a film look's LUT in `<data_dir>/clipforge/luts`, generating it into the cache on first use
+fn film_look_lut_path(look_id: &str) -> Result<std::path::PathBuf, String> {
+    let (_, name, _, grade) = FILM_LOOKS
+        .iter()
+        .find(|(id, _, _, _)| *id == look_id)
+        .ok_or_else(|| format!("Unknown film look: {}", look_id))?;
+
+    let luts_dir = dirs::data_dir()
+        .ok_or("Failed to get data directory")?
+        .join("clipforge")
+        .join("luts");
+    fs::create_dir_all(&luts_dir)
+        .map_err(|e| format!("Failed to create LUT directory: {}", e))?;
+
+    let lut_path = luts_dir.join(format!("{}.cube", look_id));
+    if lut_path.exists() {
+        return Ok(lut_path);
+    }
+
+    // Write to a temp file and rename, so an interrupted write never leaves a truncated LUT in the cache
+    let temp_path = luts_dir.join(format!(".{}_{}.part", look_id, uuid::Uuid::new_v4()));
+    fs::write(&temp_path, film_look_cube(name, *grade))
+        .map_err(|e| format!("Failed to write LUT: {}", e))?;
+    if let Err(e) = fs::rename(&temp_path, &lut_path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to cache LUT: {}", e));
+    }
+
+    Ok(lut_path)
+}
+
+/// Escape a path for a quoted `option='...'` filter value, through both the graph and option unescaping levels
+pub(crate) fn escape_filter_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('\'', "'\\\\\\''")
+        .replace(':', "\\:")
+}
+
+/// Grade with a .cube LUT, mixed over the original by `strength` (0.0 - 1.0)
+async fn apply_lut(
+    app: &AppHandle,
+    input_path: &str,
+    output_path: &str,
+    lut_path: &Path,
+    strength: f64,
+) -> Result<FilterResult, String> {
+    let lut = format!("lut3d=file='{}'", escape_filter_path(lut_path));
+    let filter = if strength >= 1.0 {
+        lut
+    } else {
+        format!(
+            "split[original][graded];[graded]{}[lut];[lut][original]blend=all_mode=normal:all_opacity={:.3}",
+            lut, strength
+        )
+    };
+
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(output_path)
+        .tracked_output(app, "film_look", input_path, output_path)
+        .await
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(FilterResult {
+        output_path: output_path.to_string(),
+        success: true,
+        message: format!("Applied LUT at {:.0}% strength", strength * 100.0),
+    })
+}
+
+#[command]
+pub async fn apply_film_look(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    look_id: String,
+    strength: f64,
+) -> Result<FilterResult, String> {
+    if !(0.0..=1.0).contains(&strength) {
+        return Err("Strength must be between 0.0 and 1.0".to_string());
+    }
+    if !Path::new(&input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    let lut_path = film_look_lut_path(&look_id)?;
+    let mut result = apply_lut(&app, &input_path, &output_path, &lut_path, strength).await?;
+    result.message = format!("Applied {} film look at {:.0}% strength", look_id, strength * 100.0);
+    Ok(result)
+}
+
 // OpenAI-based upscaling function
 async fn upscale_with_openai(
+    app: &AppHandle,
     input_path: &str,
     scale_factor: i32,
     file_type: &str,
@@ -373,8 +1082,11 @@ async fn upscale_with_openai(
     }
     
     // Get OpenAI API key
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .map_err(|_| "OpenAI API key not found. Please set OPENAI_API_KEY environment variable.")?;
+    let config = app_openai_config(app)?;
+    let api_key = config.resolve_key("");
+    if api_key.is_empty() {
+        return Err("OpenAI API key not found. Configure one or set the OPENAI_API_KEY environment variable.".to_string());
+    }
     
     // Create the prompt for DALL-E 3 upscaling
     let prompt = format!(
@@ -400,7 +1112,7 @@ async fn upscale_with_openai(
     });
     
     let response = client
-        .post("https://api.openai.com/v1/images/generations")
+        .post(config.endpoint("images/generations"))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&request_body)
@@ -460,11 +1172,14 @@ async fn upscale_video_with_openai(
     let ffmpeg_path = get_ffmpeg_path(app)?;
     
     // Get OpenAI API key
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .map_err(|_| "OpenAI API key not found. Please set OPENAI_API_KEY environment variable.")?;
+    let config = app_openai_config(app)?;
+    let api_key = config.resolve_key("");
+    if api_key.is_empty() {
+        return Err("OpenAI API key not found. Configure one or set the OPENAI_API_KEY environment variable.".to_string());
+    }
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_upscale");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_upscale");
     let frames_dir = temp_dir.join("frames");
     let upscaled_frames_dir = temp_dir.join("upscaled_frames");
     
@@ -486,7 +1201,7 @@ async fn upscale_video_with_openai(
         .arg("2") // High quality
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -560,7 +1275,7 @@ async fn upscale_video_with_openai(
         form = form.part("image", frame_part);
         
         let response = client
-            .post("https://api.openai.com/v1/images/edits")
+            .post(config.endpoint("images/edits"))
             .header("Authorization", format!("Bearer {}", api_key))
             .multipart(form)
             .send()
@@ -615,7 +1330,7 @@ async fn upscale_video_with_openai(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -678,7 +1393,7 @@ async fn unblur_media(
         .as_secs();
     let output_filename = format!("{}_unblurred_{}.{}", stem, timestamp, extension);
     
-    let temp_dir = std::env::temp_dir().join("clipforge_processed");
+    let temp_dir = app_temp_dir(&app);
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
     
@@ -693,7 +1408,7 @@ async fn unblur_media(
         if file_type == "video" {
             return unblur_video_with_openai(&app, input_path, &output_path).await;
         } else {
-            return unblur_with_openai(input_path, &output_path).await;
+            return unblur_with_openai(&app, input_path, &output_path).await;
         }
     }
 
@@ -731,7 +1446,7 @@ async fn unblur_media(
     }
 
     let output = ffmpeg_cmd
-        .output()
+        .tracked_output(&app, "unblur", input_path, &output_path)
         .await
         .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
 
@@ -754,14 +1469,18 @@ async fn unblur_media(
 
 // OpenAI DALL-E unblurring for images
 async fn unblur_with_openai(
+    app: &AppHandle,
     input_path: &str,
     output_path: &std::path::Path,
 ) -> Result<FilterResult, String> {
     println!("Using OpenAI DALL-E for AI unblurring");
     
     // Get OpenAI API key
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .map_err(|_| "OPENAI_API_KEY environment variable not set")?;
+    let config = app_openai_config(app)?;
+    let api_key = config.resolve_key("");
+    if api_key.is_empty() {
+        return Err("OpenAI API key not found. Configure one or set the OPENAI_API_KEY environment variable.".to_string());
+    }
     
     // Create OpenAI DALL-E 3 request
     let client = reqwest::Client::new();
@@ -774,7 +1493,7 @@ async fn unblur_with_openai(
     });
     
     let response = client
-        .post("https://api.openai.com/v1/images/generations")
+        .post(config.endpoint("images/generations"))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&request_body)
@@ -831,7 +1550,7 @@ async fn unblur_video_with_openai(
     println!("Using OpenAI DALL-E for video unblurring");
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_openai");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_openai");
     let frames_dir = temp_dir.join("frames");
     let unblurred_frames_dir = temp_dir.join("unblurred_frames");
     
@@ -853,7 +1572,7 @@ async fn unblur_video_with_openai(
         .arg("2")
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -886,12 +1605,12 @@ async fn unblur_video_with_openai(
         let unblurred_frame_path = unblurred_frames_dir.join(format!("unblurred_frame_{:04}.png", i + 1));
         
         // Use OpenAI DALL-E for each frame
-        match unblur_with_openai(&frame_path.to_string_lossy(), &unblurred_frame_path).await {
+        match unblur_with_openai(app, &frame_path.to_string_lossy(), &unblurred_frame_path).await {
             Ok(_) => {
                 unblurred_count += 1;
             },
             Err(e) => {
-                println!("Warning: Failed to process frame {}: {}", i + 1, e);
+                log::warn!("Failed to process frame {}: {}", i + 1, e);
                 // Continue with other frames
             }
         }
@@ -916,7 +1635,7 @@ async fn unblur_video_with_openai(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -951,7 +1670,7 @@ async fn unblur_video_with_ai(
     println!("Using local AI for {} video unblurring", method);
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_unblur");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_unblur");
     let frames_dir = temp_dir.join("frames");
     let unblurred_frames_dir = temp_dir.join("unblurred_frames");
     
@@ -973,7 +1692,7 @@ async fn unblur_video_with_ai(
         .arg("2")
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -1014,7 +1733,7 @@ async fn unblur_video_with_ai(
             .arg("unsharp=5:5:1.0:5:5:0.0")
             .arg("-y")
             .arg(&unblurred_frame_path)
-            .output()
+            .tracked_output(app, "unblur", input_path, output_path)
             .await
             .map_err(|e| format!("Failed to unblur frame {}: {}", i + 1, e))?;
         
@@ -1045,7 +1764,7 @@ async fn unblur_video_with_ai(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -1081,7 +1800,7 @@ async fn upscale_video_with_ai(
     println!("Using local AI for {} video upscaling by {}x", method, scale_factor);
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_upscale_ai");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_upscale_ai");
     let frames_dir = temp_dir.join("frames");
     let upscaled_frames_dir = temp_dir.join("upscaled_frames");
     
@@ -1103,7 +1822,7 @@ async fn upscale_video_with_ai(
         .arg("2")
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -1149,7 +1868,7 @@ async fn upscale_video_with_ai(
             .arg(&filter)
             .arg("-y")
             .arg(&upscaled_frame_path)
-            .output()
+            .tracked_output(app, "upscale", input_path, output_path)
             .await;
         
         match frame_output {
@@ -1158,11 +1877,11 @@ async fn upscale_video_with_ai(
                     upscaled_count += 1;
                 } else {
                     let error = String::from_utf8_lossy(&output.stderr);
-                    println!("Warning: Failed to process frame {}: {}", i + 1, error);
+                    log::warn!("Failed to process frame {}: {}", i + 1, error);
                 }
             },
             Err(e) => {
-                println!("Warning: Failed to process frame {}: {}", i + 1, e);
+                log::warn!("Failed to process frame {}: {}", i + 1, e);
             }
         }
     }
@@ -1186,7 +1905,7 @@ async fn upscale_video_with_ai(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -1232,7 +1951,7 @@ async fn upscale_with_enhanced(
         .arg(&filter)
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
 
@@ -1264,7 +1983,7 @@ async fn upscale_video_with_enhanced(
     println!("Using enhanced traditional processing for {}x video upscaling", scale_factor);
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_enhanced");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_enhanced");
     let frames_dir = temp_dir.join("frames");
     let upscaled_frames_dir = temp_dir.join("upscaled_frames");
     
@@ -1286,7 +2005,7 @@ async fn upscale_video_with_enhanced(
         .arg("2")
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -1332,7 +2051,7 @@ async fn upscale_video_with_enhanced(
             .arg(&filter)
             .arg("-y")
             .arg(&upscaled_frame_path)
-            .output()
+            .tracked_output(app, "upscale", input_path, output_path)
             .await
             .map_err(|e| format!("Failed to process frame {}: {}", i + 1, e))?;
         
@@ -1363,7 +2082,7 @@ async fn upscale_video_with_enhanced(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "upscale", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -1405,7 +2124,7 @@ async fn unblur_with_enhanced(
         .arg(filter)
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
 
@@ -1436,7 +2155,7 @@ async fn unblur_video_with_enhanced(
     println!("Using enhanced traditional processing for video unblurring");
     
     // Create temporary directories for frames
-    let temp_dir = std::env::temp_dir().join("clipforge_video_enhanced_unblur");
+    let temp_dir = app_temp_dir(app).join("clipforge_video_enhanced_unblur");
     let frames_dir = temp_dir.join("frames");
     let unblurred_frames_dir = temp_dir.join("unblurred_frames");
     
@@ -1458,7 +2177,7 @@ async fn unblur_video_with_enhanced(
         .arg("2")
         .arg(&frame_pattern)
         .arg("-y")
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to extract frames: {}", e))?;
     
@@ -1501,7 +2220,7 @@ async fn unblur_video_with_enhanced(
             .arg(filter)
             .arg("-y")
             .arg(&unblurred_frame_path)
-            .output()
+            .tracked_output(app, "unblur", input_path, output_path)
             .await
             .map_err(|e| format!("Failed to process frame {}: {}", i + 1, e))?;
         
@@ -1532,7 +2251,7 @@ async fn unblur_video_with_enhanced(
         .arg("yuv420p")
         .arg("-y")
         .arg(output_path)
-        .output()
+        .tracked_output(app, "unblur", input_path, output_path)
         .await
         .map_err(|e| format!("Failed to reassemble video: {}", e))?;
     
@@ -1556,6 +2275,7 @@ async fn unblur_video_with_enhanced(
 
 #[command]
 pub async fn generate_image_with_dalle(
+    app: AppHandle,
     api_key: &str,
     prompt: &str,
     size: &str,
@@ -1576,11 +2296,11 @@ pub async fn generate_image_with_dalle(
     println!("API key provided, length: {}", api_key.len());
     
     // Create output directory
-    let output_dir = "/tmp/clipforge_processed";
-    println!("Creating output directory: {}", output_dir);
-    std::fs::create_dir_all(output_dir)
+    let output_dir = app_temp_dir(&app);
+    println!("Creating output directory: {}", output_dir.display());
+    std::fs::create_dir_all(&output_dir)
         .map_err(|e| {
-            println!("Error creating output directory: {}", e);
+            log::error!("Error creating output directory: {}", e);
             format!("Failed to create output directory: {}", e)
         })?;
     
@@ -1590,7 +2310,7 @@ pub async fn generate_image_with_dalle(
         .unwrap()
         .as_secs();
     let output_filename = format!("dalle_generated_{}.png", timestamp);
-    let output_path = std::path::Path::new(output_dir).join(&output_filename);
+    let output_path = output_dir.join(&output_filename);
     
     println!("Output path: {}", output_path.to_string_lossy());
     println!("Generating DALL-E image with prompt: {}", prompt);
@@ -1608,15 +2328,16 @@ pub async fn generate_image_with_dalle(
     println!("Request body: {}", serde_json::to_string_pretty(&request_body).unwrap_or_default());
     println!("Making API request to OpenAI...");
     
+    let config = app_openai_config(&app)?;
     let response = client
-        .post("https://api.openai.com/v1/images/generations")
-        .header("Authorization", format!("Bearer {}", api_key))
+        .post(config.endpoint("images/generations"))
+        .header("Authorization", format!("Bearer {}", config.resolve_key(api_key)))
         .header("Content-Type", "application/json")
         .json(&request_body)
         .send()
         .await
         .map_err(|e| {
-            println!("API request failed: {}", e);
+            log::error!("API request failed: {}", e);
             format!("Failed to call OpenAI API: {}", e)
         })?;
     
@@ -1660,3 +2381,147 @@ pub async fn generate_image_with_dalle(
         message: "Image generated successfully using DALL-E".to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{assert_filter_chain, filter_names, render_filter, unescape_filter_option};
+
+    #[test]
+    fn filter_path_escaping_survives_both_unescape_levels() {
+        for path in ["C:\\Users\\Me\\Film Looks\\warm.cube", "/tmp/it's here/a:b.trf", "/plain/path.cube"] {
+            let value = unescape_filter_option(&format!("'{}'", escape_filter_path(Path::new(path))));
+            assert_eq!(value, path);
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.02,
+            "expected {} to be close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn daylight_is_neutral() {
+        let (r, g, b) = kelvin_to_rgb_multipliers(6500.0);
+        assert_close(r, 1.0);
+        assert_close(g, 1.0);
+        assert_close(b, 1.0);
+    }
+
+    #[test]
+    fn tungsten_is_warm() {
+        let (r, g, b) = kelvin_to_rgb_multipliers(3000.0);
+        assert_close(r, 1.0);
+        assert_close(g, 0.695);
+        assert_close(b, 0.431);
+    }
+
+    #[test]
+    fn overcast_sky_is_cool() {
+        let (r, g, b) = kelvin_to_rgb_multipliers(10000.0);
+        assert_close(r, 0.791);
+        assert_close(g, 0.855);
+        assert_close(b, 1.0);
+        assert!(r < b);
+    }
+
+    #[test]
+    fn very_low_temperatures_have_no_blue() {
+        let (_, _, b) = kelvin_to_rgb_multipliers(1500.0);
+        assert_eq!(b, 0.0);
+    }
+
+    const FILTER_TEST_SOURCE: &str = "testsrc2=size=320x240:rate=25:duration=5";
+
+    fn filter_chain(id: &str) -> &'static str {
+        FILTERS.iter().find(|(filter_id, _)| *filter_id == id).map(|(_, chain)| *chain).unwrap()
+    }
+
+    #[test]
+    fn noir_desaturates_before_contrast_vignette_and_grain() {
+        let chain = filter_chain("noir");
+        assert_eq!(filter_names(chain), ["hue", "curves", "vignette", "noise"]);
+        assert!(chain.starts_with("hue=s=0,"));
+        assert!(chain.contains("curves=all='0/0 0.2/0.05 0.5/0.5 0.8/0.95 1/1'"));
+        assert!(chain.ends_with("noise=alls=8:allf=t"));
+    }
+
+    #[test]
+    fn technicolor_mixes_channels_then_boosts_saturation() {
+        let chain = filter_chain("technicolor");
+        assert_eq!(filter_names(chain), ["colorchannelmixer", "eq"]);
+        assert!(chain.contains("rr=1.2:rg=-0.1:rb=-0.1:gr=-0.15:gg=1.15:gb=0.1:br=0.05:bg=-0.15:bb=1.1"));
+        assert!(chain.ends_with("eq=saturation=1.25:contrast=1.05"));
+    }
+
+    #[test]
+    #[ignore = "requires ffmpeg"]
+    fn noir_preset_renders() {
+        let output_path = render_filter(FILTER_TEST_SOURCE, filter_chain("noir"), "noir.mp4");
+        assert!(std::fs::metadata(&output_path).unwrap().len() > 0);
+    }
+
+    #[test]
+    #[ignore = "requires ffmpeg"]
+    fn technicolor_preset_renders() {
+        let output_path = render_filter(FILTER_TEST_SOURCE, filter_chain("technicolor"), "technicolor.mp4");
+        assert!(std::fs::metadata(&output_path).unwrap().len() > 0);
+    }
+
+    #[test]
+    fn color_grade_filter_blends_graded_over_original() {
+        let filter = color_grade_filter("kodachrome", 0.75).unwrap();
+        assert!(filter.starts_with("split[original][tograde];[tograde]curves="));
+        assert!(filter.ends_with("[graded][original]blend=all_expr='A*0.750+B*(1-0.750)'"));
+    }
+
+    #[test]
+    fn color_grade_presets_wrap_their_grade_in_the_blend() {
+        for (id, grade) in COLOR_GRADE_PRESETS {
+            let filter = color_grade_filter(id, 0.5).unwrap();
+            assert_eq!(
+                filter,
+                format!(
+                    "split[original][tograde];[tograde]{}[graded];[graded][original]blend=all_expr='A*0.500+B*(1-0.500)'",
+                    grade
+                )
+            );
+            assert_filter_chain(grade, &["curves", "eq", "colorbalance", "vignette"]);
+        }
+    }
+
+    #[test]
+    fn unknown_color_grade_is_rejected() {
+        assert!(color_grade_filter("sepia_dream", 1.0).is_err());
+    }
+
+    #[test]
+    #[ignore = "requires ffmpeg"]
+    fn color_grade_presets_compile() {
+        for (id, _) in COLOR_GRADE_PRESETS {
+            let filter = color_grade_filter(id, 0.5).unwrap();
+            render_filter("testsrc2=size=320x240:rate=25:duration=1", &filter, &format!("grade_{}.mp4", id));
+        }
+    }
+
+    #[test]
+    fn film_look_cubes_fill_the_lattice() {
+        for (id, name, _, grade) in FILM_LOOKS {
+            let cube = film_look_cube(name, *grade);
+            let entries: Vec<Vec<f64>> = cube
+                .lines()
+                .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+                .map(|line| line.split(' ').map(|value| value.parse().unwrap()).collect())
+                .collect();
+            assert_eq!(entries.len(), FILM_LOOK_LUT_SIZE.pow(3), "{}", id);
+            assert!(entries.iter().flatten().all(|value| (0.0..=1.0).contains(value)), "{}", id);
+            // Black stays dark and white stays bright
+            assert!(entries[0].iter().all(|value| *value < 0.1), "{}: {:?}", id, entries[0]);
+            assert!(entries[entries.len() - 1].iter().all(|value| *value > 0.8), "{}: {:?}", id, entries[entries.len() - 1]);
+        }
+    }
+}
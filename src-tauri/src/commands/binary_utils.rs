@@ -1,5 +1,63 @@
-use tauri::{AppHandle, Manager};
-use std::path::PathBuf;
+use tauri::{command, AppHandle, Manager};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// App settings persisted to `<data_dir>/clipforge/settings.json`, held as managed state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClipForgeConfig {
+    /// Base directory for processed output, set with `set_temp_directory`
+    #[serde(default)]
+    pub temp_directory: Option<PathBuf>,
+}
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(dirs::data_dir()
+        .ok_or("Failed to get data directory")?
+        .join("clipforge")
+        .join("settings.json"))
+}
+
+impl ClipForgeConfig {
+    /// Read saved settings, falling back to defaults when the file is missing or unreadable
+    pub fn load() -> Self {
+        settings_path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write settings: {}", e))
+    }
+}
+
+/// Directory for processed output: `clipforge_processed` under the user-configured base, or the system temp dir
+pub fn get_clipforge_temp_dir(config: &ClipForgeConfig) -> PathBuf {
+    config
+        .temp_directory
+        .clone()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("clipforge_processed")
+}
+
+/// `get_clipforge_temp_dir` for the app's managed config
+pub fn app_temp_dir(app: &AppHandle) -> PathBuf {
+    let config = app
+        .try_state::<Mutex<ClipForgeConfig>>()
+        .and_then(|state| state.lock().ok().map(|config| config.clone()))
+        .unwrap_or_default();
+    get_clipforge_temp_dir(&config)
+}
 
 /// Get the path to a bundled binary, falling back to system binary in development
 pub fn get_binary_path(app: &AppHandle, binary_name: &str) -> Result<PathBuf, String> {
@@ -37,3 +95,392 @@ pub fn get_ffprobe_path(app: &AppHandle) -> Result<PathBuf, String> {
     get_binary_path(app, "ffprobe")
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatInfo {
+    pub short_name: String,
+    pub long_name: String,
+    pub extensions: Vec<String>,
+}
+
+// Probing every demuxer takes a few hundred FFmpeg runs, so the list is built once per session
+static INPUT_FORMAT_CACHE: OnceLock<Vec<FormatInfo>> = OnceLock::new();
+
+// Concurrent `ffmpeg -h demuxer=` runs while building the format list
+const DEMUXER_QUERY_BATCH: usize = 16;
+
+// Demuxers that list no extensions because FFmpeg detects them by probing, with the extensions they read
+const PROBE_ONLY_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("mpegts", &["ts", "m2ts", "mts"]),
+    ("mpeg", &["mpg", "mpeg", "vob"]),
+];
+
+impl FormatInfo {
+    /// Whether this demuxer reads files with `extension`, including probe-only demuxers such as mpegts
+    pub fn claims_extension(&self, extension: &str) -> bool {
+        self.extensions.iter().any(|ext| ext == extension)
+            || self.short_name.split(',').any(|name| {
+                PROBE_ONLY_EXTENSIONS
+                    .iter()
+                    .any(|(demuxer, extensions)| *demuxer == name && extensions.contains(&extension))
+            })
+    }
+}
+
+/// Parse `ffmpeg -demuxers` rows like " D  mov,mp4,m4a,3gp,3g2,mj2 QuickTime / MOV"
+fn parse_demuxer_table(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .skip_while(|line| line.trim() != "--")
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().peekable();
+            let flags = fields.next()?;
+            if !flags.contains('D') {
+                return None;
+            }
+            // Newer builds add a separate "d" column for device demuxers
+            fields.next_if(|field| *field == "d");
+            let short_name = fields.next()?.to_string();
+            Some((short_name, fields.collect::<Vec<_>>().join(" ")))
+        })
+        .collect()
+}
+
+/// Read the "Common extensions" line from `ffmpeg -h demuxer=<name>`
+fn parse_demuxer_extensions(help: &str) -> Vec<String> {
+    help.lines()
+        .find_map(|line| line.trim().strip_prefix("Common extensions:"))
+        .map(|list| {
+            list.trim()
+                .trim_end_matches('.')
+                .split(',')
+                .map(|ext| ext.trim().to_lowercase())
+                .filter(|ext| !ext.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Demuxers in the bundled FFmpeg, cached after the first fully successful query
+pub(crate) async fn supported_input_formats(app: &AppHandle) -> Result<&'static [FormatInfo], String> {
+    if let Some(formats) = INPUT_FORMAT_CACHE.get() {
+        return Ok(formats);
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = tokio::process::Command::new(&ffmpeg_path)
+        .args(["-hide_banner", "-demuxers"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let demuxers = parse_demuxer_table(&String::from_utf8_lossy(&output.stdout));
+    if demuxers.is_empty() {
+        return Err("ffmpeg reported no demuxers".to_string());
+    }
+
+    let mut formats = Vec::with_capacity(demuxers.len());
+    for batch in demuxers.chunks(DEMUXER_QUERY_BATCH) {
+        let mut queries = tokio::task::JoinSet::new();
+        for (index, (short_name, _)) in batch.iter().enumerate() {
+            // Aliased rows such as "mov,mp4,m4a" are queried by their first name
+            let demuxer = short_name.split(',').next().unwrap_or(short_name).to_string();
+            let ffmpeg_path = ffmpeg_path.clone();
+            queries.spawn(async move {
+                let help = tokio::process::Command::new(ffmpeg_path)
+                    .args(["-hide_banner", "-h", &format!("demuxer={}", demuxer)])
+                    .output()
+                    .await
+                    .map(|output| parse_demuxer_extensions(&String::from_utf8_lossy(&output.stdout)))
+                    .map_err(|e| format!("Failed to query demuxer {}: {}", demuxer, e));
+                (index, help)
+            });
+        }
+
+        // A failed query would cache a format with no extensions, so give up and retry on the next call
+        let mut extensions = vec![Vec::new(); batch.len()];
+        while let Some(result) = queries.join_next().await {
+            let (index, found) = result.map_err(|e| format!("Demuxer query task failed: {}", e))?;
+            extensions[index] = found?;
+        }
+
+        for ((short_name, long_name), extensions) in batch.iter().zip(extensions) {
+            formats.push(FormatInfo {
+                short_name: short_name.clone(),
+                long_name: long_name.clone(),
+                extensions,
+            });
+        }
+    }
+
+    Ok(INPUT_FORMAT_CACHE.get_or_init(|| formats))
+}
+
+/// List the input formats the bundled FFmpeg can demux
+#[command]
+pub async fn get_supported_input_formats(app: AppHandle) -> Result<Vec<FormatInfo>, String> {
+    Ok(supported_input_formats(&app).await?.to_vec())
+}
+
+/// Whether the file's extension belongs to a demuxer in the bundled FFmpeg; files without one are left to ffprobe
+#[command]
+pub async fn is_format_supported(app: AppHandle, file_path: String) -> Result<bool, String> {
+    let Some(extension) = Path::new(&file_path).extension().map(|ext| ext.to_string_lossy().to_lowercase()) else {
+        return Ok(true);
+    };
+
+    let formats = supported_input_formats(&app).await?;
+    Ok(formats.iter().any(|format| format.claims_extension(&extension)))
+}
+
+/// Build an output file name like `<stem>_<operation>_<timestamp>.<ext>` for a source file
+pub fn suggest_output_filename(source_path: &str, operation: &str, extension: &str) -> PathBuf {
+    let stem = Path::new(source_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("output");
+    let operation: String = operation
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let extension = extension.trim().trim_start_matches('.');
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    PathBuf::from(format!("{}_{}_{}.{}", stem, operation, timestamp, extension))
+}
+
+/// Suggested output path next to the source file
+pub fn output_path_beside(source_path: &str, operation: &str, extension: &str) -> PathBuf {
+    let file_name = suggest_output_filename(source_path, operation, extension);
+    match Path::new(source_path).parent() {
+        Some(dir) => dir.join(file_name),
+        None => file_name,
+    }
+}
+
+/// Suggest where a processed file should be written, beside the source or in the temp dir
+#[command]
+pub async fn get_default_output_path(
+    app: AppHandle,
+    source_path: String,
+    operation: String,
+    extension: String,
+    use_same_dir: bool,
+) -> Result<String, String> {
+    if operation.trim().is_empty() {
+        return Err("Operation tag cannot be empty".to_string());
+    }
+    if extension.trim().trim_start_matches('.').is_empty() {
+        return Err("Extension cannot be empty".to_string());
+    }
+
+    let output_path = if use_same_dir {
+        output_path_beside(&source_path, &operation, &extension)
+    } else {
+        let temp_dir = app_temp_dir(&app);
+        std::fs::create_dir_all(&temp_dir)
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+        temp_dir.join(suggest_output_filename(&source_path, &operation, &extension))
+    };
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+    pub vram_mb: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HardwareCapabilities {
+    pub cpu_cores: usize,
+    pub available_ram_mb: u64,
+    pub gpus: Vec<GpuInfo>,
+    pub hw_encoders: Vec<String>,
+}
+
+// Encoder name suffixes that mean the encode runs on a GPU or media engine
+const HW_ENCODER_SUFFIXES: &[&str] = &["_nvenc", "_qsv", "_videotoolbox", "_amf", "_vaapi", "_mf", "_v4l2m2m"];
+
+/// Parse a size such as "8 GB" or "1536 MB" into megabytes
+#[cfg(target_os = "macos")]
+fn parse_vram_mb(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let amount: u64 = parts.next()?.parse().ok()?;
+    match parts.next()?.to_uppercase().as_str() {
+        "GB" => Some(amount * 1024),
+        "MB" => Some(amount),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn detect_gpus() -> Vec<GpuInfo> {
+    let Ok(output) = std::process::Command::new("system_profiler").arg("SPDisplaysDataType").output() else {
+        return Vec::new();
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut gpus: Vec<GpuInfo> = Vec::new();
+    for line in stdout.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("Chipset Model:") {
+            gpus.push(GpuInfo { name: name.trim().to_string(), vram_mb: None });
+        } else if line.starts_with("VRAM") {
+            // "VRAM (Total): 8 GB" or "VRAM (Dynamic, Max): 1536 MB"
+            if let (Some(gpu), Some((_, value))) = (gpus.last_mut(), line.split_once(':')) {
+                gpu.vram_mb = parse_vram_mb(value.trim());
+            }
+        }
+    }
+    gpus
+}
+
+#[cfg(target_os = "windows")]
+fn detect_gpus() -> Vec<GpuInfo> {
+    let Ok(output) = std::process::Command::new("wmic")
+        .args(["path", "win32_videocontroller", "get", "name,adapterram", "/format:csv"])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    // CSV columns: Node,AdapterRAM,Name
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip_while(|line| !line.starts_with("Node"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut columns = line.trim().splitn(3, ',');
+            let _node = columns.next()?;
+            let adapter_ram = columns.next()?;
+            let name = columns.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(GpuInfo {
+                name: name.to_string(),
+                vram_mb: adapter_ram.trim().parse::<u64>().ok().map(|bytes| bytes / (1024 * 1024)),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn detect_gpus() -> Vec<GpuInfo> {
+    let Ok(output) = std::process::Command::new("lspci").output() else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.contains("VGA compatible controller") || line.contains("3D controller"))
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .map(|name| GpuInfo { name: name.trim().to_string(), vram_mb: None })
+        .collect()
+}
+
+/// Hardware encoders this ffmpeg build was compiled with
+fn detect_hw_encoders(app: &AppHandle) -> Vec<String> {
+    let Ok(ffmpeg_path) = get_ffmpeg_path(app) else {
+        return Vec::new();
+    };
+    let Ok(output) = std::process::Command::new(ffmpeg_path).args(["-hide_banner", "-encoders"]).output() else {
+        return Vec::new();
+    };
+
+    // Rows look like " V....D h264_videotoolbox    VideoToolbox H.264 Encoder"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter(|name| HW_ENCODER_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Logical CPU cores and currently available RAM in megabytes
+pub(crate) fn cpu_cores_and_available_ram_mb() -> (usize, u64) {
+    let cpu_cores = std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(1);
+
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    (cpu_cores, system.available_memory() / (1024 * 1024))
+}
+
+/// Per-job cost of a batch of FFmpeg runs, for sizing how many run at once
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct JobCost {
+    pub cores: usize,
+    pub ram_mb: u64,
+    /// Ceiling regardless of hardware, for jobs bound by something else such as disk I/O
+    pub max_jobs: usize,
+}
+
+/// Jobs that fit in both the cores and the available RAM, at least one
+pub(crate) fn concurrency_limit(cpu_cores: usize, available_ram_mb: u64, cost: JobCost) -> usize {
+    let by_cores = cpu_cores / cost.cores.max(1);
+    let by_ram = (available_ram_mb / cost.ram_mb.max(1)) as usize;
+    by_cores.min(by_ram).clamp(1, cost.max_jobs.max(1))
+}
+
+/// `concurrency_limit` for this machine
+pub(crate) fn machine_concurrency_limit(cost: JobCost) -> usize {
+    let (cpu_cores, available_ram_mb) = cpu_cores_and_available_ram_mb();
+    concurrency_limit(cpu_cores, available_ram_mb, cost)
+}
+
+#[command]
+pub async fn get_hardware_capabilities(app: AppHandle) -> Result<HardwareCapabilities, String> {
+    let (cpu_cores, available_ram_mb) = cpu_cores_and_available_ram_mb();
+
+    Ok(HardwareCapabilities {
+        cpu_cores,
+        available_ram_mb,
+        gpus: detect_gpus(),
+        hw_encoders: detect_hw_encoders(&app),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(short_name: &str, extensions: &[&str]) -> FormatInfo {
+        FormatInfo {
+            short_name: short_name.to_string(),
+            long_name: String::new(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn concurrency_limit_follows_cores_and_ram() {
+        let cost = JobCost { cores: 4, ram_mb: 1024, max_jobs: 8 };
+        assert_eq!(concurrency_limit(16, 32 * 1024, cost), 4);
+        // Short on RAM
+        assert_eq!(concurrency_limit(16, 2 * 1024, cost), 2);
+        // Fewer cores than one job wants still runs one
+        assert_eq!(concurrency_limit(2, 512, cost), 1);
+        assert_eq!(concurrency_limit(64, 256 * 1024, cost), 8);
+    }
+
+    #[test]
+    fn probe_only_demuxers_claim_their_extensions() {
+        assert!(format("mpegts", &[]).claims_extension("ts"));
+        assert!(format("mpeg", &[]).claims_extension("vob"));
+        assert!(format("matroska,webm", &["mkv", "mk3d", "mka", "mks", "webm"]).claims_extension("mkv"));
+        assert!(!format("mpegtsraw", &[]).claims_extension("ts"));
+        assert!(!format("mpegts", &[]).claims_extension("mp4"));
+    }
+}
@@ -5,7 +5,8 @@ use tokio::process::Command;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use image::{ImageBuffer, Rgb, RgbImage, DynamicImage};
-use crate::commands::binary_utils::get_ffmpeg_path;
+use crate::commands::binary_utils::{app_temp_dir, get_ffmpeg_path};
+use crate::commands::openai::app_openai_config;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -44,8 +45,8 @@ pub struct SpriteSize {
 
 /// Create a temporary directory for character extraction
 #[command]
-pub async fn create_temp_directory(name: &str) -> Result<String, String> {
-    let temp_dir = std::env::temp_dir().join(format!("clipforge_{}_{}", name, Uuid::new_v4()));
+pub async fn create_temp_directory(app: AppHandle, name: &str) -> Result<String, String> {
+    let temp_dir = app_temp_dir(&app).join(format!("clipforge_{}_{}", name, Uuid::new_v4()));
     
     fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
@@ -123,14 +124,19 @@ pub async fn extract_video_frames(
 /// Detect character in a single frame using OpenAI Vision API
 #[command]
 pub async fn detect_character_in_frame(
+    app: AppHandle,
     frame_path: &str,
     frame_index: usize,
     output_dir: &str,
     reference_image_path: Option<String>,
+    api_key: Option<String>,
 ) -> Result<serde_json::Value, String> {
-    // Get OpenAI API key
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .map_err(|_| "OPENAI_API_KEY environment variable not set")?;
+    // Use the caller's key, falling back to the configured one
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(api_key.as_deref().unwrap_or_default());
+    if api_key.is_empty() {
+        return Err("OpenAI API key not configured".to_string());
+    }
 
     // Read frame image
     let frame_bytes = fs::read(frame_path)
@@ -237,7 +243,7 @@ If no clear Mario character is visible, return 'null'."
     // Call OpenAI Vision API to detect character
     let client = reqwest::Client::new();
     let response = client
-        .post("https://api.openai.com/v1/chat/completions")
+        .post(config.endpoint("chat/completions"))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -309,6 +315,13 @@ fn parse_bounding_box(content: &str) -> Option<BoundingBox> {
         return None;
     }
 
+    // Models often answer with a JSON object, possibly wrapped in a code fence
+    if let (Some(start), Some(end)) = (content.find('{'), content.rfind('}')) {
+        if let Ok(bbox) = serde_json::from_str::<BoundingBox>(&content[start..=end]) {
+            return (bbox.width > 0 && bbox.height > 0 && bbox.x >= 0 && bbox.y >= 0).then_some(bbox);
+        }
+    }
+
     // Try to parse different formats
     let mut x = None;
     let mut y = None;
@@ -449,6 +462,143 @@ async fn crop_character_from_frame(
     Ok(output_path_str)
 }
 
+/// Parse the background JSON GPT-4o returns for a sprite, tolerating markdown code fences
+fn parse_sprite_background(content: &str) -> Option<(String, String)> {
+    let start = content.find('{')?;
+    let end = content.rfind('}')?;
+    if end < start {
+        return None;
+    }
+
+    let parsed: serde_json::Value = serde_json::from_str(&content[start..=end]).ok()?;
+    let bg_type = parsed["bg_type"].as_str()?.trim().to_lowercase();
+    let bg_color_hex = parsed["bg_color_hex"].as_str().unwrap_or("").trim().to_string();
+    Some((bg_type, bg_color_hex))
+}
+
+/// Normalise "#RRGGBB" or "RRGGBB" into FFmpeg's "0xRRGGBB" colour syntax
+fn ffmpeg_hex_color(hex: &str) -> Option<String> {
+    let digits = hex.trim_start_matches('#').trim_start_matches("0x");
+    if digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("0x{}", digits))
+    } else {
+        None
+    }
+}
+
+/// Detect and crop a character, then key out a solid background into a transparent PNG
+#[command]
+pub async fn extract_sprite_transparent(
+    app: AppHandle,
+    api_key: String,
+    frame_path: String,
+    frame_index: usize,
+    output_dir: String,
+) -> Result<serde_json::Value, String> {
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let detection =
+        detect_character_in_frame(app.clone(), &frame_path, frame_index, &output_dir, None, Some(api_key.clone())).await?;
+    if !detection["success"].as_bool().unwrap_or(false) {
+        return Ok(detection);
+    }
+
+    let sprite: CharacterSprite = serde_json::from_value(detection["characterSprite"].clone())
+        .map_err(|e| format!("Failed to read detected sprite: {}", e))?;
+
+    let sprite_bytes = fs::read(&sprite.image_path)
+        .map_err(|e| format!("Failed to read cropped sprite: {}", e))?;
+    let base64_sprite = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &sprite_bytes);
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(config.endpoint("chat/completions"))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": "Describe the background color of this sprite (sky, ground, solid color, complex). Return JSON: {bg_type, bg_color_hex}. Use bg_type \"solid\" when the background is a single flat color."
+                        },
+                        {
+                            "type": "image_url",
+                            "image_url": {
+                                "url": format!("data:image/png;base64,{}", base64_sprite),
+                                "detail": "high"
+                            }
+                        }
+                    ]
+                }
+            ],
+            "max_tokens": 100,
+            "temperature": 0.1
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to call OpenAI API: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("OpenAI API error: {}", error_text));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+    let content = response_json["choices"][0]["message"]["content"]
+        .as_str()
+        .unwrap_or("");
+
+    println!("Background analysis for frame {}: {}", frame_index, content);
+
+    let (bg_type, bg_color_hex) = parse_sprite_background(content)
+        .unwrap_or_else(|| ("complex".to_string(), String::new()));
+
+    // Only a flat colour can be keyed cleanly; anything else is kept as-is in RGBA
+    let key_color = if bg_type == "solid" { ffmpeg_hex_color(&bg_color_hex) } else { None };
+    let filter = match &key_color {
+        Some(color) => format!("colorkey=color={}:similarity=0.1,format=rgba", color),
+        None => "format=rgba".to_string(),
+    };
+
+    let output_path = Path::new(&output_dir).join(format!("sprite_transparent_{:04}.png", frame_index));
+    let output_path_str = output_path.to_string_lossy().to_string();
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&sprite.image_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-pix_fmt")
+        .arg("rgba")
+        .arg("-y")
+        .arg(&output_path_str)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to remove sprite background: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg colorkey error: {}", error_msg));
+    }
+
+    Ok(serde_json::json!({
+        "success": true,
+        "imagePath": output_path_str,
+        "backgroundRemoved": key_color.is_some(),
+        "bgType": bg_type,
+        "bgColorHex": bg_color_hex,
+        "characterSprite": sprite
+    }))
+}
+
 /// Compare two images for similarity
 #[command]
 pub async fn compare_images(
@@ -579,7 +729,7 @@ pub async fn build_character_sprite_sheet(
     if !output.status.success() {
         let error_msg = String::from_utf8_lossy(&output.stderr);
         let stdout_msg = String::from_utf8_lossy(&output.stdout);
-        println!("FFmpeg STDERR: {}", error_msg);
+        log::error!("FFmpeg STDERR: {}", error_msg);
         println!("FFmpeg STDOUT: {}", stdout_msg);
         println!("Filter complex used: {}", filter_complex);
         
@@ -650,13 +800,13 @@ pub async fn copy_sprite_sheet_to_location(
     // Check if source files exist
     if !Path::new(sprite_sheet_path).exists() {
         let error = format!("Source sprite sheet does not exist: {}", sprite_sheet_path);
-        println!("ERROR: {}", error);
+        log::error!("{}", error);
         return Err(error);
     }
     
     if !Path::new(metadata_path).exists() {
         let error = format!("Source metadata does not exist: {}", metadata_path);
-        println!("ERROR: {}", error);
+        log::error!("{}", error);
         return Err(error);
     }
     
@@ -672,7 +822,7 @@ pub async fn copy_sprite_sheet_to_location(
         meta_path
     } else {
         let error = "Invalid target path - no file stem found".to_string();
-        println!("ERROR: {}", error);
+        log::error!("{}", error);
         return Err(error);
     };
     
@@ -684,7 +834,7 @@ pub async fn copy_sprite_sheet_to_location(
         },
         Err(e) => {
             let error = format!("Failed to copy sprite sheet: {}", e);
-            println!("ERROR: {}", error);
+            log::error!("{}", error);
             return Err(error);
         }
     }
@@ -697,7 +847,7 @@ pub async fn copy_sprite_sheet_to_location(
         },
         Err(e) => {
             let error = format!("Failed to copy metadata: {}", e);
-            println!("ERROR: {}", error);
+            log::error!("{}", error);
             return Err(error);
         }
     }
@@ -721,13 +871,13 @@ pub async fn copy_sprite_sheet_to_desktop(
     // Check if source files exist
     if !std::path::Path::new(sprite_sheet_path).exists() {
         let error = format!("Source sprite sheet does not exist: {}", sprite_sheet_path);
-        println!("ERROR: {}", error);
+        log::error!("{}", error);
         return Err(error);
     }
     
     if !std::path::Path::new(metadata_path).exists() {
         let error = format!("Source metadata does not exist: {}", metadata_path);
-        println!("ERROR: {}", error);
+        log::error!("{}", error);
         return Err(error);
     }
     
@@ -740,7 +890,7 @@ pub async fn copy_sprite_sheet_to_desktop(
         },
         None => {
             let error = "Could not find home directory".to_string();
-            println!("ERROR: {}", error);
+            log::error!("{}", error);
             return Err(error);
         }
     };
@@ -768,7 +918,7 @@ pub async fn copy_sprite_sheet_to_desktop(
         },
         Err(e) => {
             let error = format!("Failed to copy sprite sheet: {}", e);
-            println!("ERROR: {}", error);
+            log::error!("{}", error);
             return Err(error);
         }
     }
@@ -781,7 +931,7 @@ pub async fn copy_sprite_sheet_to_desktop(
         },
         Err(e) => {
             let error = format!("Failed to copy metadata: {}", e);
-            println!("ERROR: {}", error);
+            log::error!("{}", error);
             return Err(error);
         }
     }
@@ -923,3 +1073,96 @@ fn is_sky_color(r: u8, g: u8, b: u8) -> bool {
     // Check if color is sky blue (common in retro game backgrounds)
     r < 100 && g > 150 && b > 200
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_bbox(bbox: Option<BoundingBox>, expected: (i32, i32, i32, i32)) {
+        let bbox = bbox.expect("expected a bounding box");
+        assert_eq!((bbox.x, bbox.y, bbox.width, bbox.height), expected);
+    }
+
+    #[test]
+    fn parses_single_line_bounding_box() {
+        assert_bbox(parse_bounding_box("x: 100, y: 50, width: 32, height: 48"), (100, 50, 32, 48));
+    }
+
+    #[test]
+    fn parses_multi_line_bounding_box() {
+        assert_bbox(parse_bounding_box("x=12\ny=34\nwidth=56\nheight=78"), (12, 34, 56, 78));
+    }
+
+    #[test]
+    fn parses_json_bounding_box() {
+        assert_bbox(
+            parse_bounding_box(r#"{"x": 10, "y": 20, "width": 30, "height": 40}"#),
+            (10, 20, 30, 40),
+        );
+        assert_bbox(
+            parse_bounding_box("```json\n{\"x\": 1, \"y\": 2, \"width\": 3, \"height\": 4}\n```"),
+            (1, 2, 3, 4),
+        );
+    }
+
+    #[test]
+    fn null_response_has_no_bounding_box() {
+        assert!(parse_bounding_box("null").is_none());
+        assert!(parse_bounding_box("  NULL\n").is_none());
+    }
+
+    #[test]
+    fn rejects_empty_or_negative_dimensions() {
+        assert!(parse_bounding_box("x: 10, y: 10, width: 0, height: 20").is_none());
+        assert!(parse_bounding_box("x: 10, y: 10, width: 20, height: -5").is_none());
+        assert!(parse_bounding_box(r#"{"x": 10, "y": 10, "width": -1, "height": 20}"#).is_none());
+        assert!(parse_bounding_box("no character here").is_none());
+    }
+
+    #[test]
+    fn extracts_number_after_colon_or_equals() {
+        assert_eq!(extract_number_after_pattern("Width: 64", "width:"), Some(64));
+        assert_eq!(extract_number_after_pattern("height=128,", "height="), Some(128));
+        assert_eq!(extract_number_after_pattern("x: -3", "x:"), Some(-3));
+        assert_eq!(extract_number_after_pattern("width: wide", "width:"), None);
+        assert_eq!(extract_number_after_pattern("height: 10", "width:"), None);
+    }
+
+    #[test]
+    fn extracts_number_after_colon() {
+        assert_eq!(extract_number_after_colon("frames: 42 total"), Some(42));
+        assert_eq!(extract_number_after_colon("no separator 42"), None);
+    }
+
+    #[test]
+    fn extracts_psnr_value() {
+        let line = "[Parsed_psnr_0 @ 0x7f] PSNR y:38.12 u:41.00 v:40.50 average:39.02 min:35.10 max:42.00 psnr: 39.02";
+        assert_eq!(extract_psnr_value(line), Some(39.02));
+        assert_eq!(extract_psnr_value("psnr: inf"), Some(f64::INFINITY));
+        assert_eq!(extract_psnr_value("no metrics"), None);
+    }
+
+    #[test]
+    fn recognises_sky_colors() {
+        assert!(is_sky_color(92, 168, 252));
+        assert!(is_sky_color(0, 200, 255));
+        assert!(!is_sky_color(255, 255, 255));
+        assert!(!is_sky_color(200, 50, 50));
+        assert!(!is_sky_color(30, 140, 220));
+    }
+
+    #[test]
+    fn region_scores_favor_detail_over_sky() {
+        let mut img = RgbImage::from_pixel(16, 16, image::Rgb([50, 180, 240]));
+        let sky_score = analyze_region_fast(&img, 0, 0, 16, 16);
+
+        for (i, pixel) in img.pixels_mut().enumerate() {
+            *pixel = image::Rgb([(i * 7 % 256) as u8, (i * 3 % 256) as u8, 40]);
+        }
+        let detail_score = analyze_region_fast(&img, 0, 0, 16, 16);
+
+        assert_eq!(sky_score, 0.0);
+        assert!(detail_score > 0.5, "detail score was {}", detail_score);
+        assert_eq!(analyze_region_fast(&img, 0, 0, 0, 0), 0.0);
+    }
+}
@@ -1,16 +1,38 @@
-use tauri::{command, AppHandle};
+use tauri::{command, AppHandle, Emitter, State};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
 use anyhow::Result;
 use crate::commands::{VideoMetadata, VideoClip};
-use crate::commands::binary_utils::{get_ffmpeg_path, get_ffprobe_path};
+use crate::commands::binary_utils::{
+    app_temp_dir, get_ffmpeg_path, get_ffprobe_path, machine_concurrency_limit, output_path_beside, JobCost,
+};
+use crate::commands::filesystem::check_file_integrity;
+use crate::commands::text_to_video::XFADE_TRANSITIONS;
+use crate::commands::ai_styler::escape_filter_path;
+use crate::commands::operations::{
+    cancel_ffmpeg_process, cancellable_output, deregister_operation, register_operation, store_cancellable_child,
+    wait_cancellable_child, OperationRegistry, TrackedOutput, TrackedOutputAsync,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TrimParams {
     pub input_path: String,
+    /// Left empty to write `<stem>_trimmed_<timestamp>` beside the input
+    #[serde(default)]
     pub output_path: String,
     pub start_time: f64,
     pub end_time: f64,
+    /// Move the start point to the nearest keyframe so the stream-copy cut is clean
+    #[serde(default)]
+    pub snap_to_keyframe: bool,
+    /// Video stream to keep (`-map 0:v:<index>`), or None for FFmpeg's default selection
+    #[serde(default)]
+    pub stream_index: Option<u32>,
+    /// Re-encode on this hardware encoder for a frame-accurate cut; None or `none` keeps the stream copy
+    #[serde(default)]
+    pub hardware_accel: Option<HardwareAccel>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,10 +40,741 @@ pub struct ExportParams {
     pub clips: Vec<VideoClip>,
     pub output_path: String,
     pub resolution: String,
+    /// Video stream read from each input (`<input>:v:<index>`), or None for the first one
+    #[serde(default)]
+    pub stream_index: Option<u32>,
+    /// Encoder to render with, or None for libx264
+    #[serde(default)]
+    pub hardware_accel: Option<HardwareAccel>,
+}
+
+/// H.264 encoder backend; `None` is the libx264 software encoder
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HardwareAccel {
+    Nvenc,
+    VideoToolbox,
+    Vaapi,
+    None,
+}
+
+impl HardwareAccel {
+    const ALL: [HardwareAccel; 4] = [HardwareAccel::None, HardwareAccel::Nvenc, HardwareAccel::VideoToolbox, HardwareAccel::Vaapi];
+
+    pub fn encoder(self) -> &'static str {
+        match self {
+            HardwareAccel::Nvenc => "h264_nvenc",
+            HardwareAccel::VideoToolbox => "h264_videotoolbox",
+            HardwareAccel::Vaapi => "h264_vaapi",
+            HardwareAccel::None => "libx264",
+        }
+    }
+
+    /// Input options that open the device, placed before the first `-i`
+    fn device_args(self) -> Vec<String> {
+        match self {
+            HardwareAccel::Vaapi => vec!["-vaapi_device".to_string(), VAAPI_DEVICE.to_string()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Filter that moves software frames onto the device, for encoders that only take hardware frames
+    fn upload_filter(self) -> Option<&'static str> {
+        match self {
+            HardwareAccel::Vaapi => Some("format=nv12,hwupload"),
+            _ => None,
+        }
+    }
+
+    /// `-c:v` plus the encoder's rate control, roughly matching libx264 CRF 23 quality
+    fn video_encode_args(self, width: u32, height: u32, fps: f64) -> Vec<String> {
+        let mut args = vec!["-c:v".to_string(), self.encoder().to_string()];
+        match self {
+            HardwareAccel::None => args.extend([
+                "-preset".to_string(), "medium".to_string(),
+                "-crf".to_string(), "23".to_string(),
+                "-level".to_string(), resolution_to_h264_level(width, height, fps).to_string(),
+            ]),
+            // NVENC has no CRF, so target the bitrate libx264 would land on
+            HardwareAccel::Nvenc => {
+                let kbps = crf23_video_kbps(width, height);
+                args.extend([
+                    "-preset".to_string(), "p5".to_string(),
+                    "-b:v".to_string(), format!("{}k", kbps),
+                    "-maxrate".to_string(), format!("{}k", kbps * 3 / 2),
+                    "-bufsize".to_string(), format!("{}k", kbps * 2),
+                ]);
+            }
+            HardwareAccel::VideoToolbox => args.extend(["-q:v".to_string(), VIDEOTOOLBOX_QUALITY.to_string()]),
+            HardwareAccel::Vaapi => args.extend(["-qp".to_string(), "23".to_string()]),
+        }
+        args
+    }
+}
+
+// Default DRM render node on Linux
+const VAAPI_DEVICE: &str = "/dev/dri/renderD128";
+
+// h264_videotoolbox -q:v runs 1-100, higher is better; 65 is close to libx264 CRF 23
+const VIDEOTOOLBOX_QUALITY: u32 = 65;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AvailableEncoder {
+    pub hardware_accel: HardwareAccel,
+    pub encoder: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SmartTrimResult {
+    pub output_path: String,
+    pub used_stream_copy: bool,
+    pub actual_start: f64,
+    pub actual_end: f64,
+    /// Estimated encode time avoided by stream copying, 0 when the segment was re-encoded
+    pub time_saved_ms: u64,
+}
+
+// How far a keyframe may sit from a requested cut for smart_trim to stream copy
+const SMART_TRIM_KEYFRAME_TOLERANCE: f64 = 0.5;
+
+// Rough libx264 fast-preset speed, used to estimate the time a stream copy saves
+const ASSUMED_REENCODE_SPEED: f64 = 2.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoStreamInfo {
+    /// Position among the file's video streams, as used by `-map 0:v:<index>`
+    pub index: u32,
+    pub codec_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    pub is_cover_art: bool,
+}
+
+/// Payload of the `clipforge://export-progress` event, emitted per FFmpeg progress report during `export_timeline`.
+/// Named like the app's other `clipforge://` events rather than `export_progress`, so listen for that name.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportProgress {
+    pub fraction: f64,
+    /// Seconds remaining at the current encode speed, or -1 while the speed is unknown
+    pub eta_seconds: f64,
+    pub current_time_ms: u64,
+    pub speed: f64,
+    pub current_frame: u64,
+    /// Timeline duration times the output frame rate
+    pub total_frames: u64,
+    pub elapsed_secs: f64,
+    /// Frames encoded per second
+    pub fps: f64,
+    pub percent: f64,
+}
+
+// Operation id (from `store_cancellable_child`) of the running export, for `cancel_export`
+static CURRENT_EXPORT_PROCESS: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+// Set while `export_timeline` runs; a second export is rejected rather than taking over `cancel_export`
+static EXPORT_RUNNING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Claim on the single export slot, released when the export returns however it ends
+struct ExportSlot;
+
+impl ExportSlot {
+    fn claim() -> Result<Self, String> {
+        EXPORT_RUNNING
+            .compare_exchange(false, true, std::sync::atomic::Ordering::AcqRel, std::sync::atomic::Ordering::Acquire)
+            .map(|_| ExportSlot)
+            .map_err(|_| "An export is already running. Cancel it or wait for it to finish.".to_string())
+    }
+}
+
+impl Drop for ExportSlot {
+    fn drop(&mut self) {
+        if let Ok(mut current) = CURRENT_EXPORT_PROCESS.lock() {
+            *current = None;
+        }
+        EXPORT_RUNNING.store(false, std::sync::atomic::Ordering::Release);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportValidationResult {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub estimated_size_bytes: u64,
+    pub estimated_duration_seconds: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FrameExportResult {
+    pub output_path: String,
+    pub actual_width: u32,
+    pub actual_height: u32,
+    pub file_size_bytes: u64,
+    pub format: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepairResult {
+    pub success: bool,
+    pub output_path: String,
+    pub detected_issues: Vec<String>,
+    pub strategy_used: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncInfo {
+    pub video_event_at: f64,
+    pub audio_event_at: f64,
+    pub offset_seconds: f64,
+    pub confidence: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AvSyncMeasurement {
+    /// Audio start relative to video start; positive means the audio is late
+    pub audio_offset_ms: f64,
+    pub is_audio_early: bool,
+    pub max_jitter_ms: f64,
+    pub total_packets_analyzed: u64,
+    pub warning: Option<String>,
+}
+
+// Offsets beyond this are noticeable to most viewers
+const PERCEPTIBLE_AV_OFFSET_MS: f64 = 40.0;
+
+// Only the opening of the file is needed to measure stream offsets
+const AV_SYNC_PROBE_SECONDS: u32 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThumbnailRequest {
+    pub clip_id: String,
+    pub input_path: String,
+    pub timestamp: f64,
+    pub width: u32,
+    pub height: u32,
+    pub output_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThumbnailResult {
+    pub clip_id: String,
+    pub output_path: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Payload of the `clipforge://thumbnail-batch-progress` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThumbnailBatchProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub result: ThumbnailResult,
+}
+
+// A thumbnail extraction is a short single-threaded decode; more than 4 at once just contend for disk I/O
+const THUMBNAIL_JOB_COST: JobCost = JobCost { cores: 1, ram_mb: 256, max_jobs: 4 };
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SocialAsset {
+    pub platform: String,
+    pub output_path: String,
+    pub thumbnail_path: Option<String>,
+    pub resolution: String,
+    pub duration: f64,
+    pub file_size_bytes: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// Delivery targets (platform id, width, height, max duration in seconds or 0 for no limit)
+const SOCIAL_PLATFORMS: &[(&str, u32, u32, f64)] = &[
+    ("youtube", 1920, 1080, 0.0),
+    ("youtube_shorts", 1080, 1920, 60.0),
+    ("tiktok", 1080, 1920, 600.0),
+    ("instagram_reels", 1080, 1920, 90.0),
+    ("instagram_feed", 1080, 1080, 60.0),
+    ("twitter", 1280, 720, 140.0),
+    ("linkedin", 1920, 1080, 600.0),
+    ("facebook", 1280, 720, 0.0),
+];
+
+// Each platform transcode is a full libx264 encode that already spreads over several threads
+const SOCIAL_TRANSCODE_JOB_COST: JobCost = JobCost { cores: 4, ram_mb: 1024, max_jobs: 4 };
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoQualityReport {
+    pub mean_luminance: f64,
+    pub out_of_range_pixels_percent: f64,
+    pub video_repetition_percent: f64,
+    pub blocking_score: f64,
+    pub blur_score: f64,
+    pub overall_grade: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SceneComplexity {
+    pub time_start: f64,
+    pub time_end: f64,
+    pub mean_complexity: f64,
+    pub max_complexity: f64,
+    pub suggested_crf_adjustment: i32,
+}
+
+// Mean scene score bounds for the high and low complexity CRF adjustments
+const HIGH_COMPLEXITY_THRESHOLD: f64 = 0.7;
+const LOW_COMPLEXITY_THRESHOLD: f64 = 0.3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShakeMeasurement {
+    pub mean_motion: f64,
+    pub max_motion: f64,
+    pub shake_level: String,
+    pub stabilization_recommended: bool,
+}
+
+// Upper bounds on mean inter-frame motion (pixels) for the none, light and moderate shake levels
+const SHAKE_LEVELS: &[(f64, &str)] = &[(1.0, "none"), (3.0, "light"), (7.0, "moderate")];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WaveformData {
+    pub sample_rate: u32,
+    pub duration: f64,
+    pub samples: Vec<f32>,
+    pub channel_count: u32,
+}
+
+// Waveform audio is decoded at this rate (or higher) and reduced to one peak per output sample
+const WAVEFORM_DECODE_RATE: u32 = 8000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MontageResult {
+    pub bpm_estimate: f64,
+    pub total_cuts: usize,
+    pub output_path: String,
+}
+
+// A momentary loudness peak this far above the previous second counts as a beat
+const BEAT_LOUDNESS_RISE_LU: f64 = 1.5;
+// Peaks closer than this are one beat (caps detection at 240 BPM)
+const MIN_BEAT_SPACING_SECONDS: f64 = 0.25;
+// Shortest looped piece worth emitting when filling a beat window from a short clip
+const MONTAGE_MIN_PIECE_SECONDS: f64 = 0.01;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscodeResult {
+    pub output_path: String,
+    pub chapters_preserved: usize,
+    pub chapter_titles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeduplicationResult {
+    pub original_frame_count: u64,
+    pub output_frame_count: u64,
+    pub frames_removed: u64,
+    pub size_reduction_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SplitStrategy {
+    ByCount { parts: u32 },
+    ByBytes { max_bytes: u64 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PipPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AspectRatioPreset {
+    pub name: String,
+    pub ratio: String,
+    pub description: String,
+}
+
+// Common aspect ratio presets (name, ratio, description)
+const ASPECT_RATIO_PRESETS: &[(&str, &str, &str)] = &[
+    ("Widescreen", "16:9", "HD video, YouTube, most monitors"),
+    ("Standard", "4:3", "Classic TV and presentation slides"),
+    ("Square", "1:1", "Instagram feed posts"),
+    ("Vertical", "9:16", "TikTok, Reels, Shorts and Stories"),
+    ("Ultrawide", "21:9", "Cinematic ultrawide"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation {
+    Cw90,
+    Ccw90,
+    Rotate180,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl Rotation {
+    /// Filter applied after FFmpeg's autorotate, so it acts on the picture as displayed
+    fn video_filter(self) -> &'static str {
+        match self {
+            Rotation::Cw90 => "transpose=clock",
+            Rotation::Ccw90 => "transpose=cclock",
+            Rotation::Rotate180 => "hflip,vflip",
+            Rotation::FlipHorizontal => "hflip",
+            Rotation::FlipVertical => "vflip",
+        }
+    }
+
+    /// Counter-clockwise degrees added to the display matrix (the `-display_rotation` convention)
+    fn display_rotation_delta(self) -> f64 {
+        match self {
+            Rotation::Cw90 => -90.0,
+            Rotation::Ccw90 => 90.0,
+            Rotation::Rotate180 => 180.0,
+            Rotation::FlipHorizontal | Rotation::FlipVertical => 0.0,
+        }
+    }
+}
+
+/// Orientation held in a display matrix, as `-display_rotation` followed by `-display_hflip` writes it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DisplayOrientation {
+    /// Counter-clockwise degrees in (-180, 180]
+    rotation: f64,
+    hflip: bool,
+}
+
+impl DisplayOrientation {
+    const IDENTITY: DisplayOrientation = DisplayOrientation { rotation: 0.0, hflip: false };
+
+    /// Decode the nine fixed point values ffprobe prints for a displaymatrix side data entry
+    fn from_display_matrix(matrix: &str) -> Option<Self> {
+        let values: Vec<f64> = matrix
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .flat_map(|(_, row)| row.split_whitespace().map(str::parse::<f64>))
+            .collect::<Result<_, _>>()
+            .ok()?;
+        if values.len() != 9 {
+            return None;
+        }
+
+        // A mirror makes the determinant negative; undo it on the first column to leave a pure rotation
+        let (mut a, b, mut c, d) = (values[0], values[1], values[3], values[4]);
+        let hflip = a * d - b * c < 0.0;
+        if hflip {
+            a = -a;
+            c = -c;
+        }
+        let (scale_x, scale_y) = (a.hypot(c), b.hypot(d));
+        if scale_x == 0.0 || scale_y == 0.0 {
+            return None;
+        }
+        // Same angle av_display_rotation_get reports, rounded off the fixed point noise
+        let rotation = -(b / scale_y).atan2(a / scale_x).to_degrees();
+        Some(DisplayOrientation { rotation: normalize_degrees((rotation * 100.0).round() / 100.0), hflip })
+    }
+
+    /// The orientation once `rotation` is applied to the picture as currently displayed.
+    /// Turning a mirrored picture turns the underlying frame the other way.
+    fn then(self, rotation: Rotation) -> Self {
+        let (turn, mirror) = match rotation {
+            Rotation::FlipHorizontal => (0.0, true),
+            // A vertical flip is a horizontal one turned by half a revolution
+            Rotation::FlipVertical => (180.0, true),
+            other => (other.display_rotation_delta(), false),
+        };
+        let turn = if self.hflip { -turn } else { turn };
+        DisplayOrientation {
+            rotation: normalize_degrees(self.rotation + turn),
+            hflip: self.hflip != mirror,
+        }
+    }
+}
+
+/// Wrap an angle into (-180, 180]
+fn normalize_degrees(degrees: f64) -> f64 {
+    let degrees = degrees.rem_euclid(360.0);
+    if degrees > 180.0 {
+        degrees - 360.0
+    } else {
+        degrees
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Mp3,
+    Wav,
+    Aac,
+    Flac,
+}
+
+// Bitrate for lossy audio extraction when none is given
+const DEFAULT_AUDIO_BITRATE_KBPS: u32 = 192;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncodingPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub codec: String,
+    pub preset: String,
+    pub crf: u32,
+    pub target_use_case: String,
+    pub estimated_bitrate_kbps_per_1080p: u32,
+}
+
+// Recommended encoder settings (id, name, description, codec, preset, crf, use case, kbps at 1080p30).
+// VP9 has no -preset; its column holds the -deadline value instead.
+type EncodingPresetRow = (&'static str, &'static str, &'static str, &'static str, &'static str, u32, &'static str, u32);
+const ENCODING_PRESETS: &[EncodingPresetRow] = &[
+    ("h264_web", "Web Streaming", "Fast H.264 encode that plays everywhere", "libx264", "fast", 23, "web", 5000),
+    ("h264_archive", "Archival", "Slow, near-transparent H.264 for long-term storage", "libx264", "veryslow", 18, "archive", 12000),
+    ("h264_social", "Social Media", "Balanced H.264 that survives platform re-encoding", "libx264", "medium", 21, "social", 8000),
+    ("h264_screen", "Screen Recording", "Fast H.264 tuned for mostly static screen content", "libx264", "fast", 28, "screen", 2500),
+    ("h264_lossless", "Lossless", "Mathematically lossless H.264 (-qp 0), very large files", "libx264", "ultrafast", 0, "lossless", 80000),
+    ("hevc_web", "Web Streaming (HEVC)", "H.265 at roughly half the H.264 bitrate for the same quality", "libx265", "medium", 28, "web", 2500),
+    ("hevc_archive", "Archival (HEVC)", "High quality H.265 for compact archives", "libx265", "slow", 20, "archive", 7000),
+    ("hevc_screen", "Screen Recording (HEVC)", "Small H.265 files for screen content", "libx265", "fast", 30, "screen", 1200),
+    ("vp9_web", "Web Streaming (VP9)", "Royalty-free VP9 WebM for browsers", "libvpx-vp9", "good", 31, "web", 2800),
+    ("vp9_archive", "Archival (VP9)", "High quality VP9 WebM", "libvpx-vp9", "good", 24, "archive", 6500),
+    ("vp9_social", "Social Media (VP9)", "VP9 WebM for platforms that accept it", "libvpx-vp9", "good", 28, "social", 4000),
+];
+
+// Color schemes accepted by showspectrum, with a matching line color for showwaves/showfreqs
+const VISUALIZATION_COLOR_SCHEMES: &[(&str, &str)] = &[
+    ("intensity", "white"),
+    ("rainbow", "violet"),
+    ("channel", "white"),
+    ("moreland", "0x3b4cc0"),
+    ("nebulae", "0x8a2be2"),
+    ("fire", "orange"),
+    ("fiery", "red"),
+    ("fruit", "0xffa500"),
+    ("cool", "cyan"),
+    ("magma", "0xfc8961"),
+    ("green", "green"),
+    ("viridis", "0x21918c"),
+    ("plasma", "0xcc4778"),
+    ("cividis", "0xffea46"),
+    ("terrain", "0x2e8b57"),
+];
+
+const VISUALIZATION_FPS: u32 = 30;
+
+/// Output frame size for an export resolution name
+fn export_resolution(resolution: &str) -> (u32, u32) {
+    match resolution {
+        "720p" => (1280, 720),
+        "1080p" => (1920, 1080),
+        "2k" => (2560, 1440),
+        "4k" => (3840, 2160),
+        _ => (1920, 1080), // Default to 1080p
+    }
+}
+
+/// Rough libx264 CRF 23 video bitrate for a frame size
+fn crf23_video_kbps(width: u32, height: u32) -> u32 {
+    match width as u64 * height as u64 {
+        pixels if pixels <= 1280 * 720 => 5_000,
+        pixels if pixels <= 1920 * 1080 => 8_000,
+        pixels if pixels <= 2560 * 1440 => 16_000,
+        _ => 35_000,
+    }
+}
+
+/// Parse `ffmpeg -encoders` output into the H.264 backends this build was compiled with
+fn parse_available_encoders(encoders_output: &str) -> Vec<AvailableEncoder> {
+    // Rows look like " V....D h264_nvenc           NVIDIA NVENC H.264 encoder (codec h264)"
+    let rows: Vec<(&str, String)> = encoders_output
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let flags = columns.next()?;
+            let name = columns.next()?;
+            flags.starts_with('V').then(|| (name, columns.collect::<Vec<_>>().join(" ")))
+        })
+        .collect();
+
+    HardwareAccel::ALL
+        .iter()
+        .filter_map(|accel| {
+            let (name, description) = rows.iter().find(|(name, _)| *name == accel.encoder())?;
+            Some(AvailableEncoder {
+                hardware_accel: *accel,
+                encoder: name.to_string(),
+                description: description.clone(),
+            })
+        })
+        .collect()
+}
+
+/// H.264 encoders compiled into the ffmpeg build, without checking for the hardware behind them
+async fn compiled_encoders(app: &AppHandle) -> Result<Vec<AvailableEncoder>, String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-encoders")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(parse_available_encoders(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Fail early when the requested encoder is missing from the ffmpeg build
+async fn ensure_encoder_compiled(app: &AppHandle, accel: HardwareAccel) -> Result<(), String> {
+    if accel == HardwareAccel::None || compiled_encoders(app).await?.iter().any(|e| e.hardware_accel == accel) {
+        Ok(())
+    } else {
+        Err(format!("{} is not available in this FFmpeg build", accel.encoder()))
+    }
+}
+
+/// Encoders usable on this machine; hardware encoders must also encode a test frame, since builds ship them regardless of the GPU
+#[command]
+pub async fn get_available_encoders(app: AppHandle) -> Result<Vec<AvailableEncoder>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut available = Vec::new();
+
+    for encoder in compiled_encoders(&app).await? {
+        let accel = encoder.hardware_accel;
+        if accel == HardwareAccel::None {
+            available.push(encoder);
+            continue;
+        }
+
+        let mut command = TokioCommand::new(&ffmpeg_path);
+        command.arg("-hide_banner").arg("-v").arg("error").args(accel.device_args());
+        command.arg("-f").arg("lavfi").arg("-i").arg("color=c=black:s=256x256:d=0.1");
+        if let Some(upload) = accel.upload_filter() {
+            command.arg("-vf").arg(upload);
+        }
+        let probe = command
+            .arg("-frames:v")
+            .arg("1")
+            .arg("-c:v")
+            .arg(accel.encoder())
+            .arg("-f")
+            .arg("null")
+            .arg("-")
+            .output()
+            .await;
+
+        if probe.is_ok_and(|output| output.status.success()) {
+            available.push(encoder);
+        }
+    }
+
+    Ok(available)
+}
+
+// H.264 levels exports choose from: (level, max frame size in macroblocks, max macroblocks per second)
+const H264_LEVELS: &[(&str, u64, u64)] = &[
+    ("4.0", 8_192, 245_760),
+    ("5.0", 22_080, 589_824),
+    ("5.1", 36_864, 983_040),
+    ("5.2", 36_864, 2_073_600),
+];
+
+/// Minimum H.264 level able to carry the given frame size at `fps`: 4.0 for 1080p30, 5.0 for 1440p30, 5.1 for 4K30,
+/// and 5.2 for anything larger or faster
+pub fn resolution_to_h264_level(width: u32, height: u32, fps: f64) -> &'static str {
+    let macroblocks = (width as u64).div_ceil(16) * (height as u64).div_ceil(16);
+    let macroblocks_per_second = (macroblocks as f64 * fps.max(0.0)).ceil() as u64;
+    H264_LEVELS
+        .iter()
+        .find(|(_, max_frame, max_rate)| macroblocks <= *max_frame && macroblocks_per_second <= *max_rate)
+        .map_or("5.2", |(level, _, _)| level)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FfmpegCapabilities {
+    /// First line of `ffmpeg -version`
+    pub version: String,
+    /// Highest level libx264 accepts, or None when the build has no libx264
+    pub max_h264_level: Option<String>,
+}
+
+// Capabilities of the bundled build, probed once per session
+static FFMPEG_CAPABILITIES: std::sync::OnceLock<FfmpegCapabilities> = std::sync::OnceLock::new();
+
+/// Probe the bundled FFmpeg's version and the highest H.264 level its libx264 will encode at
+async fn ffmpeg_capabilities(app: &AppHandle) -> Result<&'static FfmpegCapabilities, String> {
+    if let Some(capabilities) = FFMPEG_CAPABILITIES.get() {
+        return Ok(capabilities);
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = TokioCommand::new(&ffmpeg_path)
+        .arg("-version")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    let version = String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or_default().to_string();
+
+    // Older x264 builds reject levels missing from their table, so encode a test frame at each, highest first
+    let mut max_h264_level = None;
+    for (level, _, _) in H264_LEVELS.iter().rev() {
+        let probe = TokioCommand::new(&ffmpeg_path)
+            .args(["-hide_banner", "-v", "error", "-f", "lavfi", "-i", "color=c=black:s=256x256:d=0.1"])
+            .args(["-frames:v", "1", "-c:v", "libx264", "-level", level, "-f", "null", "-"])
+            .output()
+            .await;
+        if probe.is_ok_and(|output| output.status.success()) {
+            max_h264_level = Some(level.to_string());
+            break;
+        }
+    }
+
+    Ok(FFMPEG_CAPABILITIES.get_or_init(|| FfmpegCapabilities { version, max_h264_level }))
+}
+
+#[command]
+pub async fn get_ffmpeg_capabilities(app: AppHandle) -> Result<FfmpegCapabilities, String> {
+    ffmpeg_capabilities(&app).await.cloned()
+}
+
+/// Major and minor release from the first line of `ffmpeg -version`, or None for git snapshot builds
+fn ffmpeg_release(version_line: &str) -> Option<(u32, u32)> {
+    let version = version_line.strip_prefix("ffmpeg version ")?.trim_start_matches('n');
+    let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next().and_then(|minor| minor.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// Fail early when libx264 in the bundled FFmpeg cannot encode at the level a resolution needs
+pub(crate) async fn ensure_h264_level_supported(app: &AppHandle, width: u32, height: u32, fps: f64) -> Result<(), String> {
+    let level = resolution_to_h264_level(width, height, fps);
+    let rank = |level: &str| H264_LEVELS.iter().position(|(known, _, _)| *known == level);
+    match &ffmpeg_capabilities(app).await?.max_h264_level {
+        Some(max_level) if rank(max_level) >= rank(level) => Ok(()),
+        Some(max_level) => Err(format!(
+            "{}x{} at {} fps needs H.264 level {}, but the bundled FFmpeg supports up to level {}",
+            width, height, fps, level, max_level
+        )),
+        None => Err("The bundled FFmpeg cannot encode H.264 with libx264".to_string()),
+    }
 }
 
 #[command]
-pub async fn get_video_metadata(app: AppHandle, file_path: String) -> Result<VideoMetadata, String> {
+pub async fn get_video_metadata(app: AppHandle, file_path: String, stream_index: Option<u32>) -> Result<VideoMetadata, String> {
     let ffprobe_path = get_ffprobe_path(&app)?;
     let output = Command::new(ffprobe_path)
         .args([
@@ -46,8 +799,16 @@ pub async fn get_video_metadata(app: AppHandle, file_path: String) -> Result<Vid
     
     let video_stream = json_output["streams"]
         .as_array()
-        .and_then(|streams| streams.iter().find(|s| s["codec_type"] == "video"))
-        .ok_or("No video stream found")?;
+        .and_then(|streams| {
+            streams
+                .iter()
+                .filter(|s| s["codec_type"] == "video")
+                .nth(stream_index.unwrap_or(0) as usize)
+        })
+        .ok_or_else(|| match stream_index {
+            Some(index) => format!("No video stream {} found", index),
+            None => "No video stream found".to_string(),
+        })?;
 
     let duration = format["duration"]
         .as_str()
@@ -82,6 +843,28 @@ pub async fn get_video_metadata(app: AppHandle, file_path: String) -> Result<Vid
         .unwrap_or("unknown")
         .to_string();
 
+    let codec_name = video_stream["codec_name"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+
+    let codec_long_name = video_stream["codec_long_name"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+
+    let codec_profile = video_stream["profile"]
+        .as_str()
+        .map(|p| p.to_string());
+
+    // Prefer the stream bit rate, falling back to the container's overall rate
+    let bit_rate_kbps = video_stream["bit_rate"]
+        .as_str()
+        .or_else(|| format["bit_rate"].as_str())
+        .and_then(|b| b.parse::<u64>().ok())
+        .map(|b| (b / 1000) as u32)
+        .unwrap_or(0);
+
     Ok(VideoMetadata {
         duration,
         width,
@@ -89,35 +872,145 @@ pub async fn get_video_metadata(app: AppHandle, file_path: String) -> Result<Vid
         fps,
         file_size,
         format: format_name,
+        codec_name,
+        codec_long_name,
+        codec_profile,
+        bit_rate_kbps,
     })
 }
 
+// Attached pictures in MKV/MP4 are often flagged only by being a single MJPEG or PNG frame
+const COVER_ART_MAX_DURATION_SECONDS: f64 = 0.1;
+
+/// List the video streams in a file, flagging embedded cover art
 #[command]
-pub async fn trim_video(app: AppHandle, params: TrimParams) -> Result<String, String> {
-    let ffmpeg_path = get_ffmpeg_path(&app)?;
-    let output = Command::new(ffmpeg_path)
+pub async fn get_video_streams(app: AppHandle, file_path: String) -> Result<Vec<VideoStreamInfo>, String> {
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let output = Command::new(ffprobe_path)
         .args([
-            "-i", &params.input_path,
-            "-ss", &params.start_time.to_string(),
-            "-t", &(params.end_time - params.start_time).to_string(),
-            "-c", "copy",
-            "-avoid_negative_ts", "make_zero",
-            &params.output_path,
+            "-v", "quiet",
+            "-select_streams", "v",
+            "-show_streams",
+            "-print_format", "json",
+            &file_path,
         ])
         .output()
-        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
 
     if !output.status.success() {
-        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
     }
 
-    Ok(params.output_path)
+    let json_output: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+    let streams = json_output["streams"].as_array().ok_or("No video stream found")?;
+
+    Ok(streams
+        .iter()
+        .enumerate()
+        .map(|(index, stream)| {
+            let codec_name = stream["codec_name"].as_str().unwrap_or("unknown").to_string();
+            let fps = stream["r_frame_rate"]
+                .as_str()
+                .and_then(|rate| rate.split_once('/'))
+                .and_then(|(num, den)| Some(num.parse::<f64>().ok()? / den.parse::<f64>().ok()?))
+                .filter(|fps| fps.is_finite())
+                .unwrap_or(0.0);
+            let duration = stream["duration"].as_str().and_then(|d| d.parse::<f64>().ok());
+            let attached_pic = stream["disposition"]["attached_pic"].as_i64() == Some(1);
+            let still_image = matches!(codec_name.as_str(), "mjpeg" | "png")
+                && duration.is_none_or(|d| d < COVER_ART_MAX_DURATION_SECONDS);
+
+            VideoStreamInfo {
+                index: index as u32,
+                width: stream["width"].as_u64().unwrap_or(0) as u32,
+                height: stream["height"].as_u64().unwrap_or(0) as u32,
+                fps,
+                is_cover_art: attached_pic || still_image,
+                codec_name,
+            }
+        })
+        .collect())
 }
 
 #[command]
-pub async fn convert_mov_to_mp4(app: AppHandle, input_path: String) -> Result<String, String> {
-    let output_path = input_path.replace(".mov", "_converted.mp4");
-    
+pub async fn trim_video(app: AppHandle, mut params: TrimParams) -> Result<String, String> {
+    if params.output_path.is_empty() {
+        let extension = std::path::Path::new(&params.input_path)
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("mp4");
+        params.output_path = output_path_beside(&params.input_path, "trimmed", extension)
+            .to_string_lossy()
+            .to_string();
+    }
+
+    let start_time = if params.snap_to_keyframe {
+        let keyframes = read_keyframes(&app, &params.input_path)?;
+        keyframes
+            .iter()
+            .cloned()
+            .min_by(|a, b| {
+                (a - params.start_time)
+                    .abs()
+                    .partial_cmp(&(b - params.start_time).abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .filter(|keyframe| *keyframe < params.end_time)
+            .unwrap_or(params.start_time)
+    } else {
+        params.start_time
+    };
+
+    let accel = params.hardware_accel.unwrap_or(HardwareAccel::None);
+    if accel != HardwareAccel::None {
+        ensure_encoder_compiled(&app, accel).await?;
+    }
+
+    let mut args = accel.device_args();
+    args.extend([
+        "-i".to_string(), params.input_path.clone(),
+        "-ss".to_string(), start_time.to_string(),
+        "-t".to_string(), (params.end_time - start_time).to_string(),
+    ]);
+    if let Some(index) = params.stream_index {
+        // Explicit maps replace the default selection, so keep any audio alongside the chosen stream
+        args.extend(["-map".to_string(), format!("0:v:{}", index), "-map".to_string(), "0:a?".to_string()]);
+    }
+    if accel == HardwareAccel::None {
+        args.extend(["-c".to_string(), "copy".to_string()]);
+    } else {
+        let metadata = get_video_metadata(app.clone(), params.input_path.clone(), params.stream_index).await?;
+        if let Some(upload) = accel.upload_filter() {
+            args.extend(["-vf".to_string(), upload.to_string()]);
+        }
+        args.extend(accel.video_encode_args(metadata.width, metadata.height, metadata.fps));
+        args.extend(["-c:a".to_string(), "aac".to_string(), "-b:a".to_string(), "128k".to_string()]);
+    }
+    args.extend([
+        "-avoid_negative_ts".to_string(), "make_zero".to_string(),
+        params.output_path.clone(),
+    ]);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "trim", &params.input_path, &params.output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(params.output_path)
+}
+
+#[command]
+pub async fn convert_mov_to_mp4(app: AppHandle, input_path: String) -> Result<String, String> {
+    let output_path = output_path_beside(&input_path, "converted", "mp4")
+        .to_string_lossy()
+        .to_string();
+    
     let ffmpeg_path = get_ffmpeg_path(&app)?;
     let output = Command::new(ffmpeg_path)
         .args([
@@ -128,7 +1021,7 @@ pub async fn convert_mov_to_mp4(app: AppHandle, input_path: String) -> Result<St
             "-crf", "23",
             &output_path,
         ])
-        .output()
+        .tracked_output(&app, "convert", &input_path, &output_path)
         .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
 
     if !output.status.success() {
@@ -143,8 +1036,11 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
     if params.clips.is_empty() {
         return Err("No clips to export".to_string());
     }
+    let _export_slot = ExportSlot::claim()?;
     
     let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let accel = params.hardware_accel.unwrap_or(HardwareAccel::None);
+    ensure_encoder_compiled(&app, accel).await?;
 
     // Sort clips by timeline position
     let mut sorted_clips = params.clips.clone();
@@ -160,6 +1056,7 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
 
     // Build FFmpeg command for timeline export
     let mut args = vec!["-y".to_string()]; // Overwrite output file
+    args.extend(accel.device_args());
 
     // Add input files (avoid duplicates)
     let mut unique_inputs = std::collections::HashSet::new();
@@ -174,11 +1071,7 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
     let mut filter_parts = Vec::new();
 
     // Get target resolution
-    let (width, height) = match params.resolution.as_str() {
-        "720p" => (1280, 720),
-        "1080p" => (1920, 1080),
-        _ => (1920, 1080), // Default to 1080p
-    };
+    let (width, height) = export_resolution(&params.resolution);
 
     // Create mapping from file paths to input indices
     let mut input_map = std::collections::HashMap::new();
@@ -190,6 +1083,11 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
         }
     }
 
+    let video_stream = match params.stream_index {
+        Some(index) => format!("v:{}", index),
+        None => "v".to_string(),
+    };
+
     // Process each clip and create timeline segments
     let mut timeline_segments = Vec::new();
     let mut current_time = 0.0;
@@ -223,8 +1121,8 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
         
         // Trim first (from source), then scale, and set SAR for consistency
         let video_filter = format!(
-            "[{}:v]trim=start={}:end={},setpts=PTS-STARTPTS,scale={}:{}:flags=lanczos,setsar=1[v{}_scaled]",
-            input_idx, trim_start, clip.trim_out, width, height, i
+            "[{}:{}]trim=start={}:end={},setpts=PTS-STARTPTS,scale={}:{}:flags=lanczos,setsar=1[v{}_scaled]",
+            input_idx, video_stream, trim_start, clip.trim_out, width, height, i
         );
         println!("Video filter for clip {}: {}", i, video_filter);
         filter_parts.push(video_filter);
@@ -245,10 +1143,15 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
     // Concatenate all segments
     let concat_inputs = timeline_segments.join("");
     let concat_filter = format!(
-        "{}concat=n={}:v=1:a=1[outv][outa]",
-        concat_inputs, timeline_segments.len()
+        "{}concat=n={}:v=1:a=1[{}][outa]",
+        concat_inputs,
+        timeline_segments.len(),
+        if accel.upload_filter().is_some() { "outv_sw" } else { "outv" }
     );
     filter_parts.push(concat_filter);
+    if let Some(upload) = accel.upload_filter() {
+        filter_parts.push(format!("[outv_sw]{}[outv]", upload));
+    }
 
     let filter_complex = filter_parts.join(";");
     println!("FFmpeg filter complex: {}", filter_complex);
@@ -261,13 +1164,17 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
     args.push("-map".to_string());
     args.push("[outa]".to_string());
 
+    // concat takes its frame rate from the first segment; gaps are generated at 30 fps
+    let output_fps = match sorted_clips.first() {
+        Some(clip) if clip.start_time <= 0.0 && clip.metadata.fps > 0.0 => clip.metadata.fps,
+        _ => 30.0,
+    };
+    if accel == HardwareAccel::None {
+        ensure_h264_level_supported(&app, width, height, output_fps).await?;
+    }
+
     // Output settings
-    args.push("-c:v".to_string());
-    args.push("libx264".to_string());
-    args.push("-preset".to_string());
-    args.push("medium".to_string());
-    args.push("-crf".to_string());
-    args.push("23".to_string());
+    args.extend(accel.video_encode_args(width, height, output_fps));
     args.push("-c:a".to_string());
     args.push("aac".to_string());
     args.push("-b:a".to_string());
@@ -284,22 +1191,3500 @@ pub async fn export_timeline(app: AppHandle, params: ExportParams) -> Result<Str
     let total_duration = max_end_time + 0.1; // Add 100ms padding
     args.push("-t".to_string());
     args.push(total_duration.to_string());
+
+    // Machine-readable progress on stdout, keeping stderr for errors only
+    args.push("-progress".to_string());
+    args.push("pipe:1".to_string());
+    args.push("-nostats".to_string());
     
     args.push(params.output_path.clone());
 
     println!("FFmpeg command: ffmpeg {}", args.join(" "));
 
-    let output = Command::new(&ffmpeg_path)
+    let mut child = TokioCommand::new(&ffmpeg_path)
         .args(&args)
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
 
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        println!("FFmpeg error: {}", error_msg);
+    // Drain stderr concurrently so a full pipe can't stall FFmpeg
+    let mut stderr = child.stderr.take().ok_or("Failed to capture ffmpeg stderr")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = String::new();
+        let _ = stderr.read_to_string(&mut buffer).await;
+        buffer
+    });
+
+    let stdout = child.stdout.take().ok_or("Failed to capture ffmpeg stdout")?;
+    let process_id = store_cancellable_child(&app, "export_timeline", "", &params.output_path, child)?;
+    if let Ok(mut current) = CURRENT_EXPORT_PROCESS.lock() {
+        *current = Some(process_id.clone());
+    }
+
+    let total_frames = (max_end_time * output_fps).round() as u64;
+    let started = std::time::Instant::now();
+
+    let mut lines = BufReader::new(stdout).lines();
+    let mut current_time_ms = 0u64;
+    let mut speed = 0.0;
+    let mut current_frame = 0u64;
+    let mut encode_fps = 0.0;
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            // Despite the name, FFmpeg reports out_time_ms in microseconds
+            "out_time_ms" => {
+                if let Ok(micros) = value.trim().parse::<u64>() {
+                    current_time_ms = micros / 1000;
+                }
+            }
+            "speed" => {
+                speed = value.trim().trim_end_matches('x').parse().unwrap_or(0.0);
+            }
+            "frame" => {
+                current_frame = value.trim().parse().unwrap_or(current_frame);
+            }
+            "fps" => {
+                encode_fps = value.trim().parse().unwrap_or(0.0);
+            }
+            "progress" => {
+                let finished = value.trim() == "end";
+                let current_seconds = current_time_ms as f64 / 1000.0;
+                let fraction = if finished {
+                    1.0
+                } else if max_end_time > 0.0 {
+                    (current_seconds / max_end_time).min(1.0)
+                } else {
+                    0.0
+                };
+                let remaining_seconds = (max_end_time - current_seconds).max(0.0);
+                let eta_seconds = if finished {
+                    0.0
+                } else if speed > 0.0 {
+                    remaining_seconds / speed
+                } else {
+                    -1.0
+                };
+
+                let progress = ExportProgress {
+                    fraction,
+                    eta_seconds,
+                    current_time_ms,
+                    speed,
+                    current_frame,
+                    total_frames,
+                    elapsed_secs: started.elapsed().as_secs_f64(),
+                    fps: encode_fps,
+                    percent: fraction * 100.0,
+                };
+                if let Err(e) = app.emit("clipforge://export-progress", progress) {
+                    log::warn!("Failed to emit export progress: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let status = wait_cancellable_child(&app, &process_id).await?;
+    let error_msg = stderr_task.await.unwrap_or_default();
+
+    if !status.success() {
+        log::error!("FFmpeg error: {}", error_msg);
         return Err(format!("ffmpeg failed: {}", error_msg));
     }
 
     println!("Export completed successfully: {}", params.output_path);
     Ok(params.output_path)
 }
+
+/// Kill the running `export_timeline` FFmpeg process; the export then returns a cancellation error
+#[command]
+pub async fn cancel_export(state: State<'_, std::sync::Mutex<OperationRegistry>>) -> Result<(), String> {
+    let process_id = CURRENT_EXPORT_PROCESS
+        .lock()
+        .map_err(|e| format!("Failed to read export state: {}", e))?
+        .take()
+        .ok_or("No export is running")?;
+    cancel_ffmpeg_process(state, process_id).await
+}
+
+/// Count the decoded video frames in a file using ffprobe
+fn count_video_frames(app: &AppHandle, file_path: &str) -> Result<u64, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-count_frames",
+            "-show_entries", "stream=nb_read_frames",
+            "-of", "csv=p=0",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .trim_end_matches(',')
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to parse frame count: {}", e))
+}
+
+#[command]
+pub async fn deduplicate_frames(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    threshold: f64,
+    constant_fps: bool,
+) -> Result<DeduplicationResult, String> {
+    if !(0.001..=1.0).contains(&threshold) {
+        return Err("Threshold must be between 0.001 and 1.0".to_string());
+    }
+
+    let original_frame_count = count_video_frames(&app, &input_path)?;
+
+    // mpdecimate compares 8x8 blocks; scale the normalized threshold to its hi/lo units
+    let hi = (threshold * 64.0 * 12.0 * 3.0).round().max(1.0) as u64;
+    let lo = (hi / 2).max(1);
+
+    // mpdecimate drops frames without retiming the rest, so the output has a
+    // variable frame rate. Some players and editors expect a constant frame rate,
+    // which `constant_fps` restores by resampling back to the source rate.
+    let mut video_filter = format!("mpdecimate=max=0:hi={}:lo={}", hi, lo);
+    if constant_fps {
+        let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+        video_filter.push_str(&format!(",fps={}", metadata.fps));
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(), input_path.clone(),
+        "-vf".to_string(), video_filter,
+    ];
+    if !constant_fps {
+        args.push("-vsync".to_string());
+        args.push("vfr".to_string());
+    }
+    args.push("-c:a".to_string());
+    args.push("copy".to_string());
+    args.push(output_path.clone());
+
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "deduplicate_frames", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let output_frame_count = count_video_frames(&app, &output_path)?;
+
+    let input_size = std::fs::metadata(&input_path).map(|m| m.len()).unwrap_or(0);
+    let output_size = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+    let size_reduction_percent = if input_size > 0 {
+        (1.0 - output_size as f64 / input_size as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(DeduplicationResult {
+        original_frame_count,
+        output_frame_count,
+        frames_removed: original_frame_count.saturating_sub(output_frame_count),
+        size_reduction_percent,
+    })
+}
+
+/// Smooth uneven frame timing by dropping stalled frames and motion-interpolating across the gaps to `target_fps`.
+/// The surviving frames keep their original timestamps, so the duration is unchanged and any audio is copied as is.
+#[command]
+pub async fn smooth_motion(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    target_fps: f64,
+    denoise_level: f64,
+) -> Result<String, String> {
+    if !(target_fps > 0.0 && target_fps <= 120.0) {
+        return Err("Target FPS must be greater than 0 and at most 120".to_string());
+    }
+    if !(0.0..=10.0).contains(&denoise_level) {
+        return Err("Denoise level must be between 0.0 and 10.0".to_string());
+    }
+
+    // Denoise first so compression noise is not mistaken for motion by mpdecimate and minterpolate
+    let mut video_filter = String::new();
+    if denoise_level > 0.0 {
+        video_filter.push_str(&format!("hqdn3d={},", denoise_level));
+    }
+    video_filter.push_str(&format!(
+        "mpdecimate=max=0:hi=64:lo=32,minterpolate=fps={}:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:me=epzs",
+        target_fps
+    ));
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = TokioCommand::new(&ffmpeg_path)
+        .arg("-y")
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-map")
+        .arg("0:v:0")
+        .arg("-map")
+        .arg("0:a?")
+        .arg("-vf")
+        .arg(&video_filter)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("medium")
+        .arg("-crf")
+        .arg("20")
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-c:a")
+        .arg("copy")
+        .arg(&output_path)
+        .tracked_output(&app, "smooth_motion", &input_path, &output_path)
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn apply_noise_gate(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    threshold_db: f64,
+    attack_ms: f64,
+    release_ms: f64,
+) -> Result<String, String> {
+    if !(-80.0..=-10.0).contains(&threshold_db) {
+        return Err("Threshold must be between -80 dB and -10 dB".to_string());
+    }
+    if !(0.01..=9000.0).contains(&attack_ms) {
+        return Err("Attack must be between 0.01 ms and 9000 ms".to_string());
+    }
+    if !(0.01..=9000.0).contains(&release_ms) {
+        return Err("Release must be between 0.01 ms and 9000 ms".to_string());
+    }
+
+    // agate expects a linear amplitude threshold rather than decibels
+    let linear_threshold = 10f64.powf(threshold_db / 20.0);
+    let audio_filter = format!(
+        "agate=threshold={}:attack={}:release={}",
+        linear_threshold, attack_ms, release_ms
+    );
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-af", &audio_filter,
+            "-c:v", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "noise_gate", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn measure_noise_floor(app: AppHandle, input_path: String) -> Result<f64, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-t", "2",
+            "-i", &input_path,
+            "-vn",
+            "-af", "astats=metadata=0",
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // astats prints per-channel sections followed by an "Overall" section; the last value wins
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let noise_floor = stderr
+        .lines()
+        .filter_map(|line| line.split("Noise floor dB:").nth(1))
+        .filter_map(|value| value.trim().parse::<f64>().ok())
+        .next_back()
+        .ok_or("Could not measure noise floor (no audio stream found?)")?;
+
+    // Digital silence reports -inf, which cannot be serialized; clamp to the 24-bit floor
+    Ok(if noise_floor.is_finite() { noise_floor } else { -144.0 })
+}
+
+#[command]
+pub async fn get_aspect_ratio_presets() -> Result<Vec<AspectRatioPreset>, String> {
+    Ok(ASPECT_RATIO_PRESETS
+        .iter()
+        .map(|(name, ratio, description)| AspectRatioPreset {
+            name: name.to_string(),
+            ratio: ratio.to_string(),
+            description: description.to_string(),
+        })
+        .collect())
+}
+
+/// Recommended encoding settings; the single source of preset values for encoding commands
+pub fn encoding_presets() -> Vec<EncodingPreset> {
+    ENCODING_PRESETS
+        .iter()
+        .map(|(id, name, description, codec, preset, crf, use_case, bitrate)| EncodingPreset {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            codec: codec.to_string(),
+            preset: preset.to_string(),
+            crf: *crf,
+            target_use_case: use_case.to_string(),
+            estimated_bitrate_kbps_per_1080p: *bitrate,
+        })
+        .collect()
+}
+
+/// One entry of `encoding_presets()` by id
+pub fn encoding_preset(id: &str) -> Result<EncodingPreset, String> {
+    encoding_presets()
+        .into_iter()
+        .find(|preset| preset.id == id)
+        .ok_or_else(|| format!("Unknown encoding preset: {}", id))
+}
+
+#[command]
+pub async fn get_encoding_presets() -> Result<Vec<EncodingPreset>, String> {
+    Ok(encoding_presets())
+}
+
+/// Parse an aspect ratio string like "16:9" into its two components
+fn parse_aspect_ratio(aspect: &str) -> Result<(u32, u32), String> {
+    let (w, h) = aspect
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid aspect ratio '{}', expected W:H", aspect))?;
+    let w = w.trim().parse::<u32>().map_err(|_| format!("Invalid aspect ratio '{}'", aspect))?;
+    let h = h.trim().parse::<u32>().map_err(|_| format!("Invalid aspect ratio '{}'", aspect))?;
+    if w == 0 || h == 0 {
+        return Err(format!("Invalid aspect ratio '{}'", aspect));
+    }
+    Ok((w, h))
+}
+
+#[command]
+pub async fn crop_to_aspect_ratio(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    aspect: String,
+    anchor: String,
+) -> Result<String, String> {
+    let (ratio_w, ratio_h) = parse_aspect_ratio(&aspect)?;
+
+    if !["center", "top", "bottom", "left", "right"].contains(&anchor.as_str()) {
+        return Err(format!(
+            "Unknown anchor: {}. Use center, top, bottom, left or right",
+            anchor
+        ));
+    }
+
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+    let (width, height) = (metadata.width, metadata.height);
+    if width == 0 || height == 0 {
+        return Err("Could not determine input resolution".to_string());
+    }
+
+    // Keep as much of the frame as possible: crop whichever side is too long
+    let target = ratio_w as f64 / ratio_h as f64;
+    let (crop_w, crop_h) = if width as f64 / height as f64 > target {
+        ((height as f64 * target).round() as u32, height)
+    } else {
+        (width, (width as f64 / target).round() as u32)
+    };
+    // Keep dimensions even for yuv420p encoders
+    let crop_w = (crop_w.min(width) / 2) * 2;
+    let crop_h = (crop_h.min(height) / 2) * 2;
+
+    let center_x = (width - crop_w) / 2;
+    let center_y = (height - crop_h) / 2;
+    let (x, y) = match anchor.as_str() {
+        "top" => (center_x, 0),
+        "bottom" => (center_x, height - crop_h),
+        "left" => (0, center_y),
+        "right" => (width - crop_w, center_y),
+        _ => (center_x, center_y),
+    };
+
+    let crop_filter = format!("crop={}:{}:{}:{}", crop_w, crop_h, x, y);
+    println!("Cropping {} to {} with filter: {}", input_path, aspect, crop_filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-vf", &crop_filter,
+            "-c:v", "libx264",
+            "-preset", "medium",
+            "-crf", "23",
+            "-c:a", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "crop_to_aspect_ratio", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_audio_visualization(
+    app: AppHandle,
+    audio_path: String,
+    output_path: String,
+    width: u32,
+    height: u32,
+    visualization_type: String,
+    color_scheme: String,
+    duration: Option<f64>,
+) -> Result<String, String> {
+    if width == 0 || height == 0 || !width.is_multiple_of(2) || !height.is_multiple_of(2) {
+        return Err("Width and height must be positive even numbers".to_string());
+    }
+
+    let line_color = VISUALIZATION_COLOR_SCHEMES
+        .iter()
+        .find(|(name, _)| *name == color_scheme)
+        .map(|(_, color)| *color)
+        .ok_or_else(|| format!("Unknown color scheme: {}", color_scheme))?;
+
+    let visualization_filter = match visualization_type.as_str() {
+        "spectrum" => format!(
+            "showspectrum=s={}x{}:slide=scroll:mode=combined:color={}",
+            width, height, color_scheme
+        ),
+        "waves" => format!(
+            "showwaves=s={}x{}:mode=cline:rate={}:colors={}",
+            width, height, VISUALIZATION_FPS, line_color
+        ),
+        "bars" => format!(
+            "showfreqs=s={}x{}:mode=bar:ascale=log:colors={}",
+            width, height, line_color
+        ),
+        _ => {
+            return Err(format!(
+                "Unknown visualization type: {}. Use spectrum, waves or bars",
+                visualization_type
+            ))
+        }
+    };
+
+    // Draw the visualization over a solid background so transparent areas render black
+    let filter_complex = format!(
+        "color=c=black:s={}x{}:r={}[bg];[0:a]{}[viz];[bg][viz]overlay=shortest=1,format=yuv420p[outv]",
+        width, height, VISUALIZATION_FPS, visualization_filter
+    );
+    println!("Audio visualization filter: {}", filter_complex);
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        audio_path.clone(),
+        "-filter_complex".to_string(),
+        filter_complex,
+        "-map".to_string(),
+        "[outv]".to_string(),
+        "-map".to_string(),
+        "0:a".to_string(),
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "medium".to_string(),
+        "-crf".to_string(),
+        "23".to_string(),
+        "-c:a".to_string(),
+        "copy".to_string(),
+    ];
+
+    if let Some(duration) = duration {
+        if duration <= 0.0 {
+            return Err("Duration must be greater than zero".to_string());
+        }
+        args.push("-t".to_string());
+        args.push(duration.to_string());
+    }
+
+    args.push(output_path.clone());
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "audio_visualization", &audio_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Check that the corners, taken in order around the shape, form a convex quadrilateral
+fn is_convex_quadrilateral(points: &[(f64, f64); 4]) -> bool {
+    let mut sign = 0.0;
+    for i in 0..4 {
+        let (ax, ay) = points[i];
+        let (bx, by) = points[(i + 1) % 4];
+        let (cx, cy) = points[(i + 2) % 4];
+        let cross = (bx - ax) * (cy - by) - (by - ay) * (cx - bx);
+        if cross.abs() < f64::EPSILON {
+            return false;
+        }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+/// Orientation stored in the first video stream's display matrix, the identity when it has none
+fn probe_display_orientation(app: &AppHandle, file_path: &str) -> Result<DisplayOrientation, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "stream_side_data=displaymatrix",
+            "-of", "json",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+    let orientation = json["streams"][0]["side_data_list"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|side_data| side_data["displaymatrix"].as_str())
+        .find_map(DisplayOrientation::from_display_matrix);
+    Ok(orientation.unwrap_or(DisplayOrientation::IDENTITY))
+}
+
+/// Rotate or flip a video, either by rewriting the display matrix over a stream copy or by re-encoding through a filter
+#[command]
+pub async fn rotate_video(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    rotation: Rotation,
+    lossless: bool,
+) -> Result<String, String> {
+    let mut args: Vec<String> = vec!["-y".to_string()];
+
+    if lossless {
+        // Recent FFmpeg ignores `-metadata:s:v rotate=`; the display matrix input options that replace it arrived in 6.1
+        let capabilities = ffmpeg_capabilities(&app).await?;
+        if ffmpeg_release(&capabilities.version).is_some_and(|release| release < (6, 1)) {
+            return Err(format!(
+                "Lossless rotation needs FFmpeg 6.1 or newer, but found \"{}\". Turn off lossless to re-encode instead.",
+                capabilities.version
+            ));
+        }
+
+        // The options replace the stored matrix, so fold in the rotation and mirroring it already has
+        let orientation = probe_display_orientation(&app, &input_path)?.then(rotation);
+        args.extend(["-display_rotation:v:0".to_string(), orientation.rotation.to_string()]);
+        if orientation.hflip {
+            args.push("-display_hflip:v:0".to_string());
+        }
+        args.extend(["-i".to_string(), input_path.clone(), "-map".to_string(), "0".to_string()]);
+        args.extend(["-c".to_string(), "copy".to_string()]);
+    } else {
+        let encoding = encoding_preset("h264_web")?;
+        args.extend([
+            "-i".to_string(), input_path.clone(),
+            "-vf".to_string(), rotation.video_filter().to_string(),
+            "-c:v".to_string(), encoding.codec,
+            "-preset".to_string(), encoding.preset,
+            "-crf".to_string(), encoding.crf.to_string(),
+            "-c:a".to_string(), "copy".to_string(),
+        ]);
+    }
+    args.push(output_path.clone());
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "rotate_video", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn apply_perspective_correction(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    top_left: (f64, f64),
+    top_right: (f64, f64),
+    bottom_left: (f64, f64),
+    bottom_right: (f64, f64),
+) -> Result<String, String> {
+    for (name, (x, y)) in [
+        ("top_left", top_left),
+        ("top_right", top_right),
+        ("bottom_left", bottom_left),
+        ("bottom_right", bottom_right),
+    ] {
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+            return Err(format!("{} must be within 0.0 and 1.0, got ({}, {})", name, x, y));
+        }
+    }
+
+    // Walk the corners clockwise so consecutive points share an edge
+    if !is_convex_quadrilateral(&[top_left, top_right, bottom_right, bottom_left]) {
+        return Err("Corner points must form a convex quadrilateral".to_string());
+    }
+
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+    let (width, height) = (metadata.width as f64, metadata.height as f64);
+    if width == 0.0 || height == 0.0 {
+        return Err("Could not determine input resolution".to_string());
+    }
+
+    let perspective_filter = format!(
+        "perspective=x0={}:y0={}:x1={}:y1={}:x2={}:y2={}:x3={}:y3={}:interpolation=linear",
+        top_left.0 * width, top_left.1 * height,
+        top_right.0 * width, top_right.1 * height,
+        bottom_left.0 * width, bottom_left.1 * height,
+        bottom_right.0 * width, bottom_right.1 * height
+    );
+    println!("Applying perspective correction with filter: {}", perspective_filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-vf", &perspective_filter,
+            "-c:v", "libx264",
+            "-preset", "medium",
+            "-crf", "23",
+            "-c:a", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "perspective_correction", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Map a bar type to its lavfi source name
+fn color_bar_source(bar_type: &str) -> Result<&'static str, String> {
+    match bar_type {
+        "smpte" => Ok("smptebars"),
+        "smpte_hd" => Ok("smptehdbars"),
+        _ => Err(format!("Unknown bar type: {}. Use smpte or smpte_hd", bar_type)),
+    }
+}
+
+#[command]
+pub async fn generate_color_bars(
+    app: AppHandle,
+    output_path: String,
+    duration: f64,
+    width: u32,
+    height: u32,
+    bar_type: String,
+) -> Result<String, String> {
+    if duration <= 0.0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+    if width == 0 || height == 0 {
+        return Err("Width and height must be greater than zero".to_string());
+    }
+
+    let source = format!(
+        "{}=size={}x{}:rate=30:duration={}",
+        color_bar_source(&bar_type)?, width, height, duration
+    );
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-f", "lavfi",
+            "-i", &source,
+            "-c:v", "libx264",
+            "-pix_fmt", "yuv420p",
+            &output_path,
+        ])
+        .tracked_output(&app, "color_bars", "", &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn generate_tone(
+    app: AppHandle,
+    output_path: String,
+    frequency_hz: f64,
+    duration: f64,
+    amplitude: f64,
+    sample_rate: u32,
+) -> Result<String, String> {
+    if duration <= 0.0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+    if sample_rate == 0 {
+        return Err("Sample rate must be greater than zero".to_string());
+    }
+    if frequency_hz <= 0.0 || frequency_hz > sample_rate as f64 / 2.0 {
+        return Err("Frequency must be between 0 Hz and half the sample rate".to_string());
+    }
+    if !(0.0..=1.0).contains(&amplitude) {
+        return Err("Amplitude must be between 0.0 and 1.0".to_string());
+    }
+
+    let source = format!(
+        "sine=frequency={}:sample_rate={}:duration={}",
+        frequency_hz, sample_rate, duration
+    );
+    // sine has no amplitude option and always generates at 1/8 of full scale
+    let volume = format!("volume={}", amplitude * 8.0);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-f", "lavfi",
+            "-i", &source,
+            "-af", &volume,
+            &output_path,
+        ])
+        .tracked_output(&app, "tone", "", &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn generate_test_pattern(
+    app: AppHandle,
+    output_path: String,
+    duration: f64,
+    pattern_type: String,
+) -> Result<String, String> {
+    if duration <= 0.0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+
+    // Broadcast line-up: 1080p bars with a 1 kHz reference tone
+    let video_source = format!(
+        "{}=size=1920x1080:rate=30:duration={}",
+        color_bar_source(&pattern_type)?, duration
+    );
+    let audio_source = format!("sine=frequency=1000:sample_rate=48000:duration={}", duration);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-f", "lavfi",
+            "-i", &video_source,
+            "-f", "lavfi",
+            "-i", &audio_source,
+            "-c:v", "libx264",
+            "-pix_fmt", "yuv420p",
+            "-c:a", "aac",
+            "-b:a", "128k",
+            "-shortest",
+            &output_path,
+        ])
+        .tracked_output(&app, "test_pattern", "", &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn split_video_by_size(
+    app: AppHandle,
+    input_path: String,
+    output_dir: String,
+    strategy: SplitStrategy,
+) -> Result<Vec<String>, String> {
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+    if metadata.duration <= 0.0 {
+        return Err("Could not determine input duration".to_string());
+    }
+
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let stem = std::path::Path::new(&input_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid input path")?
+        .to_string();
+    let part_path = |index: usize| {
+        std::path::Path::new(&output_dir)
+            .join(format!("{}_part{:03}.mp4", stem, index))
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let mut output_paths = Vec::new();
+
+    match strategy {
+        SplitStrategy::ByCount { parts } => {
+            if parts == 0 {
+                return Err("Part count must be greater than zero".to_string());
+            }
+            let part_duration = metadata.duration / parts as f64;
+            for i in 0..parts as usize {
+                let output_path = part_path(i + 1);
+                let _ = std::fs::remove_file(&output_path);
+                let params = TrimParams {
+                    input_path: input_path.clone(),
+                    output_path,
+                    start_time: i as f64 * part_duration,
+                    end_time: ((i + 1) as f64 * part_duration).min(metadata.duration),
+                    snap_to_keyframe: false,
+                    stream_index: None,
+                    hardware_accel: None,
+                };
+                output_paths.push(trim_video(app.clone(), params).await?);
+            }
+        }
+        SplitStrategy::ByBytes { max_bytes } => {
+            if max_bytes == 0 {
+                return Err("Maximum part size must be greater than zero".to_string());
+            }
+            if metadata.file_size <= max_bytes {
+                return Err("Video is already smaller than the maximum part size".to_string());
+            }
+
+            // Start from an even split by average bitrate, then shrink parts that come out too large
+            let part_count = (metadata.file_size as f64 / max_bytes as f64).ceil();
+            let estimated_duration = metadata.duration / part_count;
+            let mut start_time = 0.0;
+
+            while start_time < metadata.duration - 0.01 {
+                let output_path = part_path(output_paths.len() + 1);
+                let mut part_duration = estimated_duration;
+
+                for attempt in 0..5 {
+                    let _ = std::fs::remove_file(&output_path);
+                    let params = TrimParams {
+                        input_path: input_path.clone(),
+                        output_path: output_path.clone(),
+                        start_time,
+                        end_time: (start_time + part_duration).min(metadata.duration),
+                        snap_to_keyframe: false,
+                        stream_index: None,
+                        hardware_accel: None,
+                    };
+                    trim_video(app.clone(), params).await?;
+
+                    let actual_size = std::fs::metadata(&output_path)
+                        .map_err(|e| format!("Failed to read part size: {}", e))?
+                        .len();
+                    if actual_size <= max_bytes || attempt == 4 || part_duration <= 1.0 {
+                        break;
+                    }
+
+                    println!(
+                        "Part {} is {} bytes (limit {}), shortening and retrying",
+                        output_paths.len() + 1, actual_size, max_bytes
+                    );
+                    part_duration = (part_duration * max_bytes as f64 / actual_size as f64 * 0.95).max(1.0);
+                }
+
+                output_paths.push(output_path);
+                start_time += part_duration;
+            }
+        }
+    }
+
+    Ok(output_paths)
+}
+
+/// Cut a video into consecutive stream-copied parts at `split_points`; cuts land on the nearest following keyframe
+#[command]
+pub async fn split_video_at_points(
+    app: AppHandle,
+    input_path: String,
+    split_points: Vec<f64>,
+    output_dir: String,
+) -> Result<Vec<String>, String> {
+    let mut split_points: Vec<f64> = split_points.into_iter().filter(|point| point.is_finite() && *point > 0.0).collect();
+    if split_points.is_empty() {
+        return Err("At least one split point after the start of the video is required".to_string());
+    }
+    split_points.sort_by(f64::total_cmp);
+    split_points.dedup_by(|a, b| (*a - *b).abs() < 0.001);
+
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let input = std::path::Path::new(&input_path);
+    let stem = input.file_stem().and_then(|s| s.to_str()).ok_or("Invalid input path")?.to_string();
+    let extension = input.extension().and_then(|s| s.to_str()).unwrap_or("mp4");
+
+    // The segment muxer can only number its outputs, so write under a temporary name and rename from its list
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let output_dir_path = std::path::Path::new(&output_dir);
+    let segment_pattern = output_dir_path.join(format!(".{}_{}_%03d.{}", stem, batch_id, extension));
+    let segment_list = output_dir_path.join(format!(".{}_{}.csv", stem, batch_id));
+    let segment_times = split_points.iter().map(|point| point.to_string()).collect::<Vec<_>>().join(",");
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-map", "0",
+            "-c", "copy",
+            "-f", "segment",
+            "-segment_times", &segment_times,
+            "-segment_list", &segment_list.to_string_lossy(),
+            "-segment_list_type", "csv",
+            "-reset_timestamps", "1",
+            &segment_pattern.to_string_lossy(),
+        ])
+        .tracked_output(&app, "split_at_points", &input_path, &output_dir)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    let list = std::fs::read_to_string(&segment_list).unwrap_or_default();
+    let _ = std::fs::remove_file(&segment_list);
+    let segment_prefix = format!(".{}_{}_", stem, batch_id);
+
+    if !output.status.success() {
+        remove_split_segments(output_dir_path, &segment_prefix, &[]);
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let mut output_paths = Vec::new();
+    for (index, row) in list.lines().enumerate() {
+        let Some((file_name, start)) = parse_segment_list_row(row) else {
+            continue;
+        };
+        let final_path = output_dir_path
+            .join(format!("{}_{:03}_{:.1}s.{}", stem, index + 1, start, extension))
+            .to_string_lossy()
+            .to_string();
+        if let Err(e) = std::fs::rename(output_dir_path.join(&file_name), &final_path) {
+            remove_split_segments(output_dir_path, &segment_prefix, &output_paths);
+            return Err(format!("Failed to rename segment {}: {}", index + 1, e));
+        }
+        output_paths.push(final_path);
+    }
+
+    if output_paths.is_empty() {
+        remove_split_segments(output_dir_path, &segment_prefix, &[]);
+        return Err("ffmpeg did not produce any segments".to_string());
+    }
+
+    Ok(output_paths)
+}
+
+/// Parse a `-segment_list_type csv` row, "<file name>,<start seconds>,<end seconds>", into its file name and start.
+/// FFmpeg quotes file names containing commas or quotes and doubles any embedded quote.
+fn parse_segment_list_row(row: &str) -> Option<(String, f64)> {
+    let row = row.trim_end_matches('\r');
+    let (file_name, rest) = match row.strip_prefix('"') {
+        Some(quoted) => {
+            let mut file_name = String::new();
+            let mut chars = quoted.char_indices();
+            loop {
+                match chars.next()? {
+                    (i, '"') if quoted[i + 1..].starts_with('"') => {
+                        file_name.push('"');
+                        chars.next();
+                    }
+                    (i, '"') => break (file_name, quoted[i + 1..].strip_prefix(',')?),
+                    (_, c) => file_name.push(c),
+                }
+            }
+        }
+        None => {
+            let (file_name, rest) = row.split_once(',')?;
+            (file_name.to_string(), rest)
+        }
+    };
+    let start = rest.split(',').next()?.trim().parse().ok()?;
+    Some((file_name, start))
+}
+
+/// Remove a failed split's hidden segments and any parts already renamed from them
+fn remove_split_segments(output_dir: &std::path::Path, segment_prefix: &str, renamed: &[String]) {
+    for path in renamed {
+        let _ = std::fs::remove_file(path);
+    }
+    let Ok(entries) = std::fs::read_dir(output_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(segment_prefix) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Free space in bytes on the volume holding `dir`, if it can be determined
+#[cfg(windows)]
+pub(crate) fn available_disk_space(dir: &std::path::Path) -> Option<u64> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let mut available = 0u64;
+    unsafe { GetDiskFreeSpaceExW(&HSTRING::from(dir.as_os_str()), Some(&mut available), None, None) }.ok()?;
+    Some(available)
+}
+
+/// Free space in bytes on the volume holding `dir`, if it can be determined
+#[cfg(not(windows))]
+pub(crate) fn available_disk_space(dir: &std::path::Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // POSIX output: header line, then "filesystem blocks used available capacity mount"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let available_kb: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+#[command]
+pub async fn validate_export_params(params: ExportParams) -> Result<ExportValidationResult, String> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    if params.clips.is_empty() {
+        errors.push("No clips to export".to_string());
+    }
+
+    let mut sorted_clips = params.clips.clone();
+    sorted_clips.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+
+    for (i, clip) in sorted_clips.iter().enumerate() {
+        if !std::path::Path::new(&clip.file_path).exists() {
+            errors.push(format!("Clip {}: file not found: {}", i + 1, clip.file_path));
+        }
+        if clip.trim_in < 0.0 {
+            errors.push(format!("Clip {}: trim in point is negative ({}s)", i + 1, clip.trim_in));
+        }
+        if clip.metadata.duration > 0.0 && clip.trim_out > clip.metadata.duration {
+            errors.push(format!(
+                "Clip {}: trim out point {}s is past the end of the source ({}s)",
+                i + 1, clip.trim_out, clip.metadata.duration
+            ));
+        }
+        if clip.trim_out <= clip.trim_in {
+            errors.push(format!("Clip {}: trim out point must be after the trim in point", i + 1));
+        }
+    }
+
+    for pair in sorted_clips.windows(2) {
+        if pair[1].start_time < pair[0].end_time {
+            errors.push(format!(
+                "Clips overlap on the timeline: {} ({}s - {}s) and {} ({}s - {}s)",
+                pair[0].id, pair[0].start_time, pair[0].end_time,
+                pair[1].id, pair[1].start_time, pair[1].end_time
+            ));
+        }
+    }
+
+    let frame_rates: Vec<f64> = sorted_clips.iter().map(|clip| clip.metadata.fps).filter(|fps| *fps > 0.0).collect();
+    if let (Some(min_fps), Some(max_fps)) = (
+        frame_rates.iter().cloned().reduce(f64::min),
+        frame_rates.iter().cloned().reduce(f64::max),
+    ) {
+        if max_fps - min_fps > 0.01 {
+            warnings.push(format!(
+                "Clips have mixed frame rates ({:.2} to {:.2} fps), motion may stutter",
+                min_fps, max_fps
+            ));
+        }
+    }
+
+    // Same timeline length export_timeline renders
+    let estimated_duration_seconds = sorted_clips.iter().map(|clip| clip.end_time).fold(0.0, f64::max);
+
+    // Rough libx264 CRF 23 bitrate for the target resolution plus 128k AAC audio
+    let (width, height) = export_resolution(&params.resolution);
+    let video_kbps = crf23_video_kbps(width, height);
+    let estimated_size_bytes = (estimated_duration_seconds * (video_kbps + 128) as f64 * 1000.0 / 8.0) as u64;
+
+    let output_dir = std::path::Path::new(&params.output_path)
+        .parent()
+        .map(|dir| if dir.as_os_str().is_empty() { std::path::Path::new(".") } else { dir })
+        .unwrap_or(std::path::Path::new("."));
+    if !output_dir.is_dir() {
+        errors.push(format!("Output directory does not exist: {}", output_dir.display()));
+    } else {
+        let probe_file = output_dir.join(format!(".clipforge_write_test_{}", std::process::id()));
+        match std::fs::write(&probe_file, b"") {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&probe_file);
+            }
+            Err(e) => errors.push(format!("Output directory is not writable: {}", e)),
+        }
+
+        match available_disk_space(output_dir) {
+            Some(available) if available < estimated_size_bytes => errors.push(format!(
+                "Not enough disk space: export needs about {} MB but only {} MB is free",
+                estimated_size_bytes / 1_000_000,
+                available / 1_000_000
+            )),
+            Some(_) => {}
+            None => warnings.push("Could not determine free disk space".to_string()),
+        }
+    }
+
+    Ok(ExportValidationResult {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+        estimated_size_bytes,
+        estimated_duration_seconds,
+    })
+}
+
+#[command]
+pub async fn export_frame_as_image(
+    app: AppHandle,
+    input_path: String,
+    timestamp: f64,
+    output_path: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    format: String,
+) -> Result<FrameExportResult, String> {
+    if timestamp < 0.0 {
+        return Err("Timestamp cannot be negative".to_string());
+    }
+
+    let format = format.to_lowercase();
+    let format_args: &[&str] = match format.as_str() {
+        "png" => &["-compression_level", "6"],
+        "jpg" | "jpeg" => &["-q:v", "2"],
+        "tiff" => &["-pix_fmt", "rgb48"],
+        _ => return Err(format!("Unsupported image format: {}. Use png, jpg or tiff", format)),
+    };
+
+    // -1 lets FFmpeg derive the missing dimension from the aspect ratio
+    let scale_filter = match (width, height) {
+        (None, None) => None,
+        (Some(w), None) => Some(format!("scale={}:-1", w)),
+        (None, Some(h)) => Some(format!("scale=-1:{}", h)),
+        (Some(w), Some(h)) => Some(format!("scale={}:{}", w, h)),
+    };
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        timestamp.to_string(),
+        "-i".to_string(),
+        input_path.clone(),
+        "-frames:v".to_string(),
+        "1".to_string(),
+    ];
+    if let Some(filter) = scale_filter {
+        args.push("-vf".to_string());
+        args.push(filter);
+    }
+    args.extend(format_args.iter().map(|arg| arg.to_string()));
+    args.push(output_path.clone());
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // A timestamp past the end of the video still exits successfully but writes nothing
+    let file_size_bytes = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+    if file_size_bytes == 0 {
+        return Err(format!("No frame was written at {}s, check the timestamp is within the video", timestamp));
+    }
+
+    let image_metadata = get_video_metadata(app, output_path.clone(), None).await?;
+
+    Ok(FrameExportResult {
+        output_path,
+        actual_width: image_metadata.width,
+        actual_height: image_metadata.height,
+        file_size_bytes,
+        format,
+    })
+}
+
+#[command]
+pub async fn repair_video(app: AppHandle, input_path: String, output_path: String) -> Result<RepairResult, String> {
+    if !std::path::Path::new(&input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    let report = check_file_integrity(app.clone(), input_path.clone()).await?;
+    let mut detected_issues: Vec<String> = report.errors.iter().chain(report.warnings.iter()).cloned().collect();
+    let missing_moov = report.errors.iter().any(|e| e.contains("moov atom not found"));
+
+    // Cheapest strategy first: a plain remux often rebuilds the moov of a fragmented MP4
+    let mut strategies: Vec<(&str, Vec<&str>)> = vec![
+        ("stream_copy", vec!["-y", "-i", &input_path, "-c", "copy", &output_path]),
+        ("regenerate_timestamps", vec!["-y", "-fflags", "+genpts", "-i", &input_path, "-c", "copy", &output_path]),
+    ];
+    if missing_moov {
+        strategies.push((
+            "discard_corrupt",
+            vec![
+                "-y",
+                "-fflags", "+discardcorrupt",
+                "-i", &input_path,
+                "-map", "0",
+                "-ignore_unknown",
+                "-c", "copy",
+                &output_path,
+            ],
+        ));
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    for (strategy, args) in strategies {
+        println!("Trying repair strategy {} for {}", strategy, input_path);
+        let output = Command::new(&ffmpeg_path)
+            .args(&args)
+            .tracked_output(&app, "repair", &input_path, &output_path)
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+        let output_size = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+        if output.status.success() && output_size > 0 {
+            return Ok(RepairResult {
+                success: true,
+                output_path,
+                detected_issues,
+                strategy_used: strategy.to_string(),
+            });
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or("unknown error").trim().to_string();
+        detected_issues.push(format!("{} failed: {}", strategy, last_line));
+    }
+
+    let _ = std::fs::remove_file(&output_path);
+
+    Ok(RepairResult {
+        success: false,
+        output_path,
+        detected_issues,
+        strategy_used: "none".to_string(),
+    })
+}
+
+/// Parse the number that follows `key` in an FFmpeg log line, e.g. "silence_end: 1.25 | ..."
+fn parse_log_value(line: &str, key: &str) -> Option<f64> {
+    let rest = &line[line.find(key)? + key.len()..];
+    rest.split_whitespace().next()?.trim_end_matches('|').parse().ok()
+}
+
+#[command]
+pub async fn detect_audio_sync_offset(app: AppHandle, input_path: String) -> Result<SyncInfo, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+
+    // Visual event: negate the picture so blackdetect finds the first near-white flash frame
+    let video_output = Command::new(&ffmpeg_path)
+        .args([
+            "-t", "30",
+            "-i", &input_path,
+            "-an",
+            "-vf", "negate,blackdetect=d=0:pic_th=0.90:pix_th=0.10",
+            "-f", "null",
+            "-",
+        ])
+        .tracked_output(&app, "audio_sync_analysis", &input_path, "")
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !video_output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&video_output.stderr)));
+    }
+
+    let video_event_at = String::from_utf8_lossy(&video_output.stderr)
+        .lines()
+        .find_map(|line| parse_log_value(line, "black_start:"))
+        .ok_or("No clear visual reference event (flash or clapper board) found in the first 30 seconds")?;
+
+    // Audio event: the loudest momentary peak, pinned to the preceding silence_end for a sharp onset
+    let audio_output = Command::new(&ffmpeg_path)
+        .args([
+            "-t", "30",
+            "-i", &input_path,
+            "-vn",
+            "-af", "silencedetect=noise=-35dB:d=0.3,ebur128=peak=true",
+            "-f", "null",
+            "-",
+        ])
+        .tracked_output(&app, "audio_sync_analysis", &input_path, "")
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !audio_output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&audio_output.stderr)));
+    }
+
+    let audio_log = String::from_utf8_lossy(&audio_output.stderr);
+    let silence_ends: Vec<f64> = audio_log
+        .lines()
+        .filter_map(|line| parse_log_value(line, "silence_end:"))
+        .collect();
+    let loudest_peak = audio_log
+        .lines()
+        .filter(|line| line.contains("Parsed_ebur128"))
+        .filter_map(|line| Some((parse_log_value(line, " t:")?, parse_log_value(line, " M:")?)))
+        .filter(|(_, loudness)| loudness.is_finite())
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let (peak_time, _) = loudest_peak
+        .ok_or("No clear audio reference event (clap or transient) found in the first 30 seconds")?;
+
+    // ebur128 reports the end of a 400 ms momentary window, so the onset sits inside that window
+    let window_start = peak_time - 0.4;
+    let onset = silence_ends
+        .iter()
+        .cloned()
+        .filter(|end| *end >= window_start - 0.1 && *end <= peak_time)
+        .reduce(f64::max);
+
+    let (audio_event_at, confidence) = match onset {
+        Some(onset) => (onset, "high"),
+        None => {
+            println!(
+                "Warning: no silence before the loudest audio peak in {}, sync offset is approximate",
+                input_path
+            );
+            (window_start.max(0.0), "low")
+        }
+    };
+
+    Ok(SyncInfo {
+        video_event_at,
+        audio_event_at,
+        offset_seconds: audio_event_at - video_event_at,
+        confidence: confidence.to_string(),
+    })
+}
+
+/// Whether the file contains at least one audio stream
+fn has_audio_stream(app: &AppHandle, file_path: &str) -> Result<bool, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", "a",
+            "-show_entries", "stream=index",
+            "-of", "csv=p=0",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+/// Number of channels in a file's first audio stream
+fn audio_channel_count(app: &AppHandle, file_path: &str) -> Result<u32, String> {
+    probe_audio_channels(app, file_path)?.ok_or_else(|| format!("No audio stream found in {}", file_path))
+}
+
+/// Channel count of the first audio stream, or None when the file has no audio
+fn probe_audio_channels(app: &AppHandle, file_path: &str) -> Result<Option<u32>, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", "a:0",
+            "-show_entries", "stream=channels",
+            "-of", "csv=p=0",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().parse().ok())
+}
+
+/// Extract the audio track to its own file; `bitrate_kbps` applies to MP3 and AAC only
+#[command]
+pub async fn extract_audio(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    format: AudioFormat,
+    bitrate_kbps: Option<u32>,
+) -> Result<String, String> {
+    if probe_audio_channels(&app, &input_path)?.is_none() {
+        return Err(format!("{} has no audio track to extract", input_path));
+    }
+
+    let bitrate = bitrate_kbps.unwrap_or(DEFAULT_AUDIO_BITRATE_KBPS);
+    if bitrate == 0 {
+        return Err("Bitrate must be greater than zero".to_string());
+    }
+
+    let codec_args: Vec<String> = match format {
+        AudioFormat::Mp3 => vec!["-c:a".to_string(), "libmp3lame".to_string(), "-b:a".to_string(), format!("{}k", bitrate)],
+        AudioFormat::Aac => vec!["-c:a".to_string(), "aac".to_string(), "-b:a".to_string(), format!("{}k", bitrate)],
+        AudioFormat::Wav => vec!["-c:a".to_string(), "pcm_s16le".to_string()],
+        AudioFormat::Flac => vec!["-c:a".to_string(), "flac".to_string(), "-compression_level".to_string(), "8".to_string()],
+    };
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(["-y", "-i", &input_path, "-vn", "-map", "0:a:0"])
+        .args(&codec_args)
+        .arg(&output_path)
+        .tracked_output(&app, "extract_audio", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn add_audio_track(
+    app: AppHandle,
+    video_path: String,
+    audio_path: String,
+    output_path: String,
+    mix_volume: f64,
+    loop_audio: bool,
+) -> Result<String, String> {
+    if !(0.0..=2.0).contains(&mix_volume) {
+        return Err("Mix volume must be between 0.0 and 2.0".to_string());
+    }
+    if !std::path::Path::new(&video_path).exists() {
+        return Err(format!("Video file not found: {}", video_path));
+    }
+    if !std::path::Path::new(&audio_path).exists() {
+        return Err(format!("Audio file not found: {}", audio_path));
+    }
+
+    let filter_complex = if has_audio_stream(&app, &video_path)? {
+        format!("[0:a][1:a]amix=inputs=2:duration=first:weights=1 {}[aout]", mix_volume)
+    } else {
+        format!("[1:a]volume={}[aout]", mix_volume)
+    };
+
+    let mut args = vec!["-y".to_string(), "-i".to_string(), video_path.clone()];
+    if loop_audio {
+        args.push("-stream_loop".to_string());
+        args.push("-1".to_string());
+    }
+    args.extend([
+        "-i".to_string(),
+        audio_path.clone(),
+        "-filter_complex".to_string(),
+        filter_complex,
+        "-map".to_string(),
+        "0:v".to_string(),
+        "-map".to_string(),
+        "[aout]".to_string(),
+        "-c:v".to_string(),
+        "copy".to_string(),
+        "-c:a".to_string(),
+        "aac".to_string(),
+        "-b:a".to_string(),
+        "192k".to_string(),
+        // Stop at the end of the video, which also ends an infinitely looped track
+        "-shortest".to_string(),
+        output_path.clone(),
+    ]);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "add_audio_track", &video_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Timestamps of every keyframe in the first video stream, in ascending order
+fn read_keyframes(app: &AppHandle, file_path: &str) -> Result<Vec<f64>, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", "v",
+            "-show_packets",
+            "-show_entries", "packet=pts_time,flags",
+            "-of", "csv=p=0",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // Each line is "<pts_time>,<flags>", e.g. "2.002000,K__"
+    let mut keyframes: Vec<f64> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pts_time, flags) = line.trim().split_once(',')?;
+            if !flags.contains('K') {
+                return None;
+            }
+            pts_time.parse::<f64>().ok()
+        })
+        .collect();
+    keyframes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(keyframes)
+}
+
+#[command]
+pub async fn get_video_keyframes(app: AppHandle, file_path: String) -> Result<Vec<f64>, String> {
+    read_keyframes(&app, &file_path)
+}
+
+#[command]
+pub async fn get_next_keyframe(app: AppHandle, file_path: String, timestamp: f64) -> Result<f64, String> {
+    read_keyframes(&app, &file_path)?
+        .into_iter()
+        .find(|keyframe| *keyframe >= timestamp)
+        .ok_or_else(|| format!("No keyframe at or after {}s", timestamp))
+}
+
+/// Trim with a stream copy when keyframes sit close to both cut points, otherwise re-encode for exact timing
+#[command]
+pub async fn smart_trim(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    start_time: f64,
+    end_time: f64,
+) -> Result<SmartTrimResult, String> {
+    if start_time < 0.0 || end_time <= start_time {
+        return Err("End time must be after a non-negative start time".to_string());
+    }
+
+    let duration = get_video_metadata(app.clone(), input_path.clone(), None).await?.duration;
+    let end_time = if duration > 0.0 { end_time.min(duration) } else { end_time };
+    let keyframes = read_keyframes(&app, &input_path)?;
+
+    // The end of the file is as clean a boundary as a keyframe
+    let copy_start = keyframes.iter().copied().rev().find(|keyframe| *keyframe <= start_time);
+    let copy_end = keyframes
+        .iter()
+        .copied()
+        .find(|keyframe| *keyframe >= end_time)
+        .or((duration > 0.0).then_some(duration));
+
+    let stream_copy_bounds = match (copy_start, copy_end) {
+        (Some(start), Some(end))
+            if start_time - start <= SMART_TRIM_KEYFRAME_TOLERANCE && end - end_time <= SMART_TRIM_KEYFRAME_TOLERANCE =>
+        {
+            Some((start, end))
+        }
+        _ => None,
+    };
+    let (actual_start, actual_end) = stream_copy_bounds.unwrap_or((start_time, end_time));
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(), actual_start.to_string(),
+        "-i".to_string(), input_path.clone(),
+        "-t".to_string(), (actual_end - actual_start).to_string(),
+    ];
+    if stream_copy_bounds.is_some() {
+        args.extend(["-c".to_string(), "copy".to_string(), "-avoid_negative_ts".to_string(), "make_zero".to_string()]);
+    } else {
+        args.extend([
+            "-c:v".to_string(), "libx264".to_string(),
+            "-preset".to_string(), "fast".to_string(),
+            "-crf".to_string(), "23".to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+        ]);
+    }
+    args.push(output_path.clone());
+
+    let started = std::time::Instant::now();
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "smart_trim", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let time_saved_ms = if stream_copy_bounds.is_some() {
+        let estimated_encode_ms = (actual_end - actual_start) / ASSUMED_REENCODE_SPEED * 1000.0;
+        (estimated_encode_ms - started.elapsed().as_millis() as f64).max(0.0) as u64
+    } else {
+        0
+    };
+
+    Ok(SmartTrimResult {
+        output_path,
+        used_stream_copy: stream_copy_bounds.is_some(),
+        actual_start,
+        actual_end,
+        time_saved_ms,
+    })
+}
+
+/// Diagnostic render of the decoder's motion vectors drawn as arrows over the video
+#[command]
+pub async fn visualize_motion_vectors(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    max_vectors: u32,
+) -> Result<String, String> {
+    let lower_output = output_path.to_lowercase();
+    if !lower_output.ends_with(".mp4") && !lower_output.ends_with(".mkv") {
+        return Err("Output path must end in .mp4 or .mkv".to_string());
+    }
+
+    // codecview draws each vector type in its own color, so max_vectors picks how many to layer:
+    // 1 = P-frame forward, 2 = + B-frame forward, 3 = + B-frame backward
+    let vector_types = match max_vectors {
+        1 => "pf",
+        2 => "pf+bf",
+        3 => "pf+bf+bb",
+        _ => return Err("max_vectors must be 1 (P forward), 2 (+ B forward) or 3 (+ B backward)".to_string()),
+    };
+    let codecview_filter = format!("codecview=mv={}", vector_types);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-flags2", "+export_mvs",
+            "-i", &input_path,
+            "-vf", &codecview_filter,
+            "-an",
+            "-c:v", "libx264",
+            "-preset", "ultrafast",
+            "-y",
+            &output_path,
+        ])
+        .tracked_output(&app, "motion_vectors", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[command]
+pub async fn convert_360_to_flat(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    yaw: f64,
+    pitch: f64,
+    roll: f64,
+    fov: f64,
+) -> Result<String, String> {
+    if !(-180.0..=180.0).contains(&yaw) {
+        return Err("Yaw must be between -180 and 180 degrees".to_string());
+    }
+    if !(-90.0..=90.0).contains(&pitch) {
+        return Err("Pitch must be between -90 and 90 degrees".to_string());
+    }
+    if !(-180.0..=180.0).contains(&roll) {
+        return Err("Roll must be between -180 and 180 degrees".to_string());
+    }
+    if !(1.0..=179.0).contains(&fov) {
+        return Err("Field of view must be between 1 and 179 degrees".to_string());
+    }
+
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+    if metadata.width < 3840 || metadata.height < 1920 {
+        return Err(format!(
+            "Input resolution {}x{} is too low for equirectangular footage (minimum 3840x1920)",
+            metadata.width, metadata.height
+        ));
+    }
+
+    let (output_width, output_height) = (1920u32, 1080u32);
+    // Derive the vertical FOV from the horizontal one so the flat view isn't stretched
+    let half_h_fov = (fov / 2.0).to_radians();
+    let v_fov = 2.0 * (half_h_fov.tan() * output_height as f64 / output_width as f64).atan().to_degrees();
+
+    let v360_filter = format!(
+        "v360=equirect:flat:yaw={}:pitch={}:roll={}:h_fov={}:v_fov={:.3}:w={}:h={}",
+        yaw, pitch, roll, fov, v_fov, output_width, output_height
+    );
+    println!("Converting 360 video with filter: {}", v360_filter);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-vf", &v360_filter,
+            "-c:v", "libx264",
+            "-preset", "medium",
+            "-crf", "23",
+            "-c:a", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "convert_360", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Extract a single resized frame for a thumbnail request
+async fn extract_thumbnail(ffmpeg_path: std::path::PathBuf, request: ThumbnailRequest) -> ThumbnailResult {
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-y")
+        .arg("-ss")
+        .arg(request.timestamp.to_string())
+        .arg("-i")
+        .arg(&request.input_path)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-vf")
+        .arg(format!("scale={}:{}", request.width, request.height))
+        .arg(&request.output_path)
+        .output()
+        .await;
+
+    let error = match output {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr))),
+        Err(e) => Some(format!("Failed to execute ffmpeg: {}", e)),
+    };
+
+    ThumbnailResult {
+        clip_id: request.clip_id,
+        output_path: request.output_path,
+        success: error.is_none(),
+        error,
+    }
+}
+
+#[command]
+pub async fn batch_generate_thumbnails(
+    app: AppHandle,
+    requests: Vec<ThumbnailRequest>,
+) -> Result<Vec<ThumbnailResult>, String> {
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let total = requests.len();
+    let concurrency = total.min(machine_concurrency_limit(THUMBNAIL_JOB_COST));
+
+    let mut pending = requests.into_iter();
+    let mut tasks = tokio::task::JoinSet::new();
+    for request in pending.by_ref().take(concurrency) {
+        tasks.spawn(extract_thumbnail(ffmpeg_path.clone(), request));
+    }
+
+    let mut results = Vec::with_capacity(total);
+    while let Some(joined) = tasks.join_next().await {
+        // A panicked task has lost its request, so record it without a clip id
+        let result = joined.unwrap_or_else(|e| ThumbnailResult {
+            clip_id: String::new(),
+            output_path: String::new(),
+            success: false,
+            error: Some(format!("Thumbnail task failed: {}", e)),
+        });
+
+        let progress = ThumbnailBatchProgress {
+            completed: results.len() + 1,
+            total,
+            result: result.clone(),
+        };
+        if let Err(e) = app.emit("clipforge://thumbnail-batch-progress", progress) {
+            log::warn!("Failed to emit thumbnail progress: {}", e);
+        }
+        results.push(result);
+
+        // Keep the pool full until every request has started
+        if let Some(request) = pending.next() {
+            tasks.spawn(extract_thumbnail(ffmpeg_path.clone(), request));
+        }
+    }
+
+    Ok(results)
+}
+
+/// Encode one platform's rendition (cover-scaled and center-cropped to its frame) plus a thumbnail from the result
+async fn transcode_for_platform(
+    app: AppHandle,
+    ffmpeg_path: std::path::PathBuf,
+    input_path: String,
+    output_dir: std::path::PathBuf,
+    platform: String,
+    source_duration: f64,
+) -> SocialAsset {
+    let mut asset = SocialAsset {
+        platform: platform.clone(),
+        output_path: String::new(),
+        thumbnail_path: None,
+        resolution: String::new(),
+        duration: 0.0,
+        file_size_bytes: 0,
+        success: false,
+        error: None,
+    };
+
+    let Some(&(_, width, height, max_duration)) = SOCIAL_PLATFORMS.iter().find(|(id, ..)| *id == platform) else {
+        let known: Vec<&str> = SOCIAL_PLATFORMS.iter().map(|(id, ..)| *id).collect();
+        asset.error = Some(format!("Unknown platform: {}. Use one of: {}", platform, known.join(", ")));
+        return asset;
+    };
+
+    let stem = std::path::Path::new(&input_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("clip");
+    let output_path = output_dir.join(format!("{}_{}.mp4", stem, platform)).to_string_lossy().to_string();
+    let duration = if max_duration > 0.0 && source_duration > max_duration { max_duration } else { source_duration };
+
+    asset.output_path = output_path.clone();
+    asset.resolution = format!("{}x{}", width, height);
+    asset.duration = duration;
+
+    let encoding = match encoding_preset("h264_social") {
+        Ok(encoding) => encoding,
+        Err(e) => {
+            asset.error = Some(e);
+            return asset;
+        }
+    };
+
+    let mut command = TokioCommand::new(&ffmpeg_path);
+    command
+        .arg("-y")
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(format!(
+            "scale={w}:{h}:force_original_aspect_ratio=increase,crop={w}:{h},setsar=1",
+            w = width,
+            h = height
+        ));
+    if max_duration > 0.0 {
+        command.arg("-t").arg(max_duration.to_string());
+    }
+    command
+        .arg("-c:v")
+        .arg(&encoding.codec)
+        .arg("-preset")
+        .arg(&encoding.preset)
+        .arg("-crf")
+        .arg(encoding.crf.to_string())
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-b:a")
+        .arg("128k")
+        .arg("-movflags")
+        .arg("+faststart")
+        .arg(&output_path);
+
+    match cancellable_output(&app, "social_asset", &input_path, &output_path, &mut command).await {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            asset.error = Some(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+            return asset;
+        }
+        Err(e) => {
+            asset.error = Some(e);
+            return asset;
+        }
+    }
+
+    asset.file_size_bytes = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+    asset.success = true;
+
+    // The rendition is already at the platform's aspect ratio, so a straight scale does not distort
+    let thumbnail = extract_thumbnail(
+        ffmpeg_path,
+        ThumbnailRequest {
+            clip_id: platform,
+            input_path: output_path.clone(),
+            timestamp: (duration / 2.0).min(1.0),
+            width,
+            height,
+            output_path: output_dir.join(format!("{}_{}.png", stem, asset.platform)).to_string_lossy().to_string(),
+        },
+    )
+    .await;
+    if thumbnail.success {
+        asset.thumbnail_path = Some(thumbnail.output_path);
+    } else {
+        asset.error = thumbnail.error.map(|e| format!("Thumbnail failed: {}", e));
+    }
+
+    asset
+}
+
+/// Render the same clip for several social platforms; a failed platform is reported in its entry instead of aborting the batch
+#[command]
+pub async fn generate_social_assets(
+    app: AppHandle,
+    input_path: String,
+    output_dir: String,
+    platforms: Vec<String>,
+) -> Result<Vec<SocialAsset>, String> {
+    if platforms.is_empty() {
+        return Err("No platforms selected".to_string());
+    }
+
+    let output_dir = std::path::PathBuf::from(&output_dir);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    let probe_file = output_dir.join(format!(".clipforge_write_test_{}", std::process::id()));
+    std::fs::write(&probe_file, b"").map_err(|e| format!("Output directory is not writable: {}", e))?;
+    let _ = std::fs::remove_file(&probe_file);
+
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+
+    // Each transcode runs in its own task so a panic still leaves the platform it was rendering
+    let spawn_transcode = |tasks: &mut tokio::task::JoinSet<(usize, String, Result<SocialAsset, tokio::task::JoinError>)>,
+                           index: usize,
+                           platform: String| {
+        let transcode = tokio::spawn(transcode_for_platform(
+            app.clone(),
+            ffmpeg_path.clone(),
+            input_path.clone(),
+            output_dir.clone(),
+            platform.clone(),
+            metadata.duration,
+        ));
+        tasks.spawn(async move { (index, platform, transcode.await) });
+    };
+
+    let total = platforms.len();
+    let mut pending = platforms.into_iter().enumerate();
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, platform) in pending.by_ref().take(machine_concurrency_limit(SOCIAL_TRANSCODE_JOB_COST)) {
+        spawn_transcode(&mut tasks, index, platform);
+    }
+
+    // Slots are filled as transcodes finish, so the result keeps the order of `platforms`
+    let mut assets: Vec<Option<SocialAsset>> = vec![None; total];
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((index, platform, transcoded)) = joined else {
+            continue;
+        };
+        assets[index] = Some(transcoded.unwrap_or_else(|e| SocialAsset {
+            platform,
+            output_path: String::new(),
+            thumbnail_path: None,
+            resolution: String::new(),
+            duration: 0.0,
+            file_size_bytes: 0,
+            success: false,
+            error: Some(format!("Transcode task failed: {}", e)),
+        }));
+
+        if let Some((index, platform)) = pending.next() {
+            spawn_transcode(&mut tasks, index, platform);
+        }
+    }
+
+    Ok(assets.into_iter().flatten().collect())
+}
+
+#[command]
+pub async fn measure_video_quality_metrics(app: AppHandle, input_path: String) -> Result<VideoQualityReport, String> {
+    // One decode pass: every filter tags frame metadata and the metadata filter logs it all
+    let filter_chain = "signalstats=stat=tout+brng+vrep,blockdetect,blurdetect,metadata=mode=print";
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-an")
+        .arg("-vf")
+        .arg(filter_chain)
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .tracked_output(&app, "quality_metrics", &input_path, "")
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // Per-key running (sum, count) of the per-frame values
+    let mut totals: std::collections::HashMap<&str, (f64, u64)> = std::collections::HashMap::new();
+    let keys = [
+        "lavfi.signalstats.YAVG",
+        "lavfi.signalstats.TOUT",
+        "lavfi.signalstats.BRNG",
+        "lavfi.signalstats.VREP",
+        "lavfi.block",
+        "lavfi.blur",
+    ];
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stderr.lines() {
+        let Some((_, entry)) = line.split_once("] ") else {
+            continue;
+        };
+        let Some((key, value)) = entry.trim().split_once('=') else {
+            continue;
+        };
+        if let (Some(key), Ok(value)) = (keys.iter().find(|k| **k == key), value.trim().parse::<f64>()) {
+            let total = totals.entry(key).or_insert((0.0, 0));
+            total.0 += value;
+            total.1 += 1;
+        }
+    }
+
+    let mean = |key: &str| {
+        totals
+            .get(key)
+            .filter(|(_, count)| *count > 0)
+            .map(|(sum, count)| sum / *count as f64)
+    };
+
+    let mean_luminance = mean("lavfi.signalstats.YAVG")
+        .ok_or("No frame statistics were reported, is this a video file?")?;
+    // signalstats reports these as the fraction of pixels or lines affected
+    let temporal_outliers_percent = mean("lavfi.signalstats.TOUT").unwrap_or(0.0) * 100.0;
+    let out_of_range_pixels_percent = mean("lavfi.signalstats.BRNG").unwrap_or(0.0) * 100.0;
+    let video_repetition_percent = mean("lavfi.signalstats.VREP").unwrap_or(0.0) * 100.0;
+    let blocking_score = mean("lavfi.block").unwrap_or(0.0);
+    let blur_score = mean("lavfi.blur").unwrap_or(0.0);
+
+    // Weighted penalties: compression blocking and blur hurt perceived quality most
+    let score = 100.0
+        - (blocking_score * 6.0).min(30.0)
+        - (blur_score * 4.0).min(30.0)
+        - (out_of_range_pixels_percent * 2.0).min(15.0)
+        - (temporal_outliers_percent * 2.0).min(15.0)
+        - (video_repetition_percent * 0.5).min(10.0);
+    let overall_grade = match score {
+        s if s >= 90.0 => "A",
+        s if s >= 80.0 => "B",
+        s if s >= 70.0 => "C",
+        s if s >= 60.0 => "D",
+        _ => "F",
+    };
+
+    Ok(VideoQualityReport {
+        mean_luminance,
+        out_of_range_pixels_percent,
+        video_repetition_percent,
+        blocking_score,
+        blur_score,
+        overall_grade: overall_grade.to_string(),
+    })
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_pip(
+    app: AppHandle,
+    main_path: String,
+    pip_path: String,
+    output_path: String,
+    position: PipPosition,
+    size_percent: f64,
+    border_color: String,
+    border_width: u32,
+    shadow: bool,
+) -> Result<String, String> {
+    if !std::path::Path::new(&main_path).exists() {
+        return Err(format!("Main video not found: {}", main_path));
+    }
+    if !std::path::Path::new(&pip_path).exists() {
+        return Err(format!("Picture-in-picture video not found: {}", pip_path));
+    }
+    if !(1.0..=100.0).contains(&size_percent) {
+        return Err("PiP size must be between 1% and 100% of the main video width".to_string());
+    }
+    if border_width > 0
+        && (border_color.is_empty()
+            || !border_color.chars().all(|c| c.is_ascii_alphanumeric() || "#@.".contains(c)))
+    {
+        return Err(format!("Invalid border color: {}", border_color));
+    }
+
+    let main_metadata = get_video_metadata(app.clone(), main_path.clone(), None).await?;
+    if main_metadata.width == 0 {
+        return Err("Could not determine main video resolution".to_string());
+    }
+
+    // Width follows the main video, height follows the PiP's own aspect ratio (-2 keeps it even)
+    let pip_width = (((main_metadata.width as f64 * size_percent / 100.0).round() as u32) / 2 * 2).max(2);
+    let margin = (main_metadata.width as f64 * 0.02).round() as u32;
+
+    let mut filter_parts = vec![format!("[1:v]scale={}:-2,format=rgba[pip_scaled]", pip_width)];
+    let mut pip_label = "pip_scaled".to_string();
+
+    if border_width > 0 {
+        filter_parts.push(format!(
+            "[{}]drawbox=x=0:y=0:w=iw:h=ih:color={}:t={}[pip_border]",
+            pip_label, border_color, border_width
+        ));
+        pip_label = "pip_border".to_string();
+    }
+
+    if shadow {
+        // Offset a translucent black copy of the PiP behind it
+        let offset = (pip_width / 80).max(4);
+        filter_parts.push(format!(
+            "[{label}]split[pip_fg][pip_sh];\
+[pip_sh]drawbox=x=0:y=0:w=iw:h=ih:color=black@0.5:t=fill,pad=iw+{o}:ih+{o}:{o}:{o}:color=black@0[pip_shadow];\
+[pip_shadow][pip_fg]overlay=0:0[pip_shadowed]",
+            label = pip_label,
+            o = offset
+        ));
+        pip_label = "pip_shadowed".to_string();
+    }
+
+    let (x, y) = match position {
+        PipPosition::TopLeft => (format!("{}", margin), format!("{}", margin)),
+        PipPosition::TopRight => (format!("W-w-{}", margin), format!("{}", margin)),
+        PipPosition::BottomLeft => (format!("{}", margin), format!("H-h-{}", margin)),
+        PipPosition::BottomRight => (format!("W-w-{}", margin), format!("H-h-{}", margin)),
+        PipPosition::Center => ("(W-w)/2".to_string(), "(H-h)/2".to_string()),
+    };
+    // eof_action=pass removes the PiP once it ends instead of freezing its last frame
+    filter_parts.push(format!(
+        "[0:v][{}]overlay=x={}:y={}:eof_action=pass,format=yuv420p[outv]",
+        pip_label, x, y
+    ));
+
+    let filter_complex = filter_parts.join(";");
+    println!("PiP filter complex: {}", filter_complex);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &main_path,
+            "-i", &pip_path,
+            "-filter_complex", &filter_complex,
+            "-map", "[outv]",
+            "-map", "0:a?",
+            "-c:v", "libx264",
+            "-preset", "medium",
+            "-crf", "23",
+            "-c:a", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "pip", &main_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Midpoints of silent stretches in a file's audio, used as natural cut points
+fn silence_midpoints(app: &AppHandle, file_path: &str) -> Result<Vec<f64>, String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-i", file_path,
+            "-vn",
+            "-af", "silencedetect=noise=-35dB:d=0.4",
+            "-f", "null",
+            "-",
+        ])
+        .tracked_output(app, "silence_detection", file_path, "")
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut midpoints = Vec::new();
+    let mut silence_start = None;
+    for line in stderr.lines() {
+        if let Some(start) = parse_log_value(line, "silence_start:") {
+            silence_start = Some(start);
+        } else if let Some(end) = parse_log_value(line, "silence_end:") {
+            if let Some(start) = silence_start.take() {
+                midpoints.push((start + end) / 2.0);
+            }
+        }
+    }
+
+    Ok(midpoints)
+}
+
+/// Export resolution name closest to a source frame height
+fn resolution_name_for_height(height: u32) -> &'static str {
+    match height {
+        0..=900 => "720p",
+        901..=1260 => "1080p",
+        1261..=1800 => "2k",
+        _ => "4k",
+    }
+}
+
+#[command]
+pub async fn create_multi_angle_sequence(
+    app: AppHandle,
+    camera_paths: Vec<String>,
+    output_path: String,
+    cuts_per_minute: f64,
+    sync_to_audio: bool,
+    reference_audio_path: Option<String>,
+) -> Result<String, String> {
+    if camera_paths.is_empty() {
+        return Err("At least one camera angle is required".to_string());
+    }
+    if cuts_per_minute <= 0.0 {
+        return Err("Cuts per minute must be greater than zero".to_string());
+    }
+
+    let mut camera_metadata = Vec::with_capacity(camera_paths.len());
+    for path in &camera_paths {
+        if !std::path::Path::new(path).exists() {
+            return Err(format!("Camera file not found: {}", path));
+        }
+        camera_metadata.push(get_video_metadata(app.clone(), path.clone(), None).await?);
+    }
+
+    let (first_width, first_height) = (camera_metadata[0].width, camera_metadata[0].height);
+    for (path, metadata) in camera_paths.iter().zip(&camera_metadata).skip(1) {
+        if (metadata.width, metadata.height) != (first_width, first_height) {
+            // export_timeline scales and pads every segment to the output size
+            log::warn!(
+                "{} is {}x{} but the first camera is {}x{}; it will be scaled to fit",
+                path, metadata.width, metadata.height, first_width, first_height
+            );
+        }
+    }
+
+    // Cameras are assumed to start in sync, so the sequence ends with the shortest one
+    let total_duration = camera_metadata
+        .iter()
+        .map(|metadata| metadata.duration)
+        .fold(f64::INFINITY, f64::min);
+    if !total_duration.is_finite() || total_duration <= 0.0 {
+        return Err("Could not determine camera durations".to_string());
+    }
+
+    let clip_duration = 60.0 / cuts_per_minute;
+    let mut cut_points = vec![0.0];
+    if sync_to_audio {
+        let reference = reference_audio_path.unwrap_or_else(|| camera_paths[0].clone());
+        // cuts_per_minute still sets the shortest allowed shot so rapid pauses don't cause flicker
+        for midpoint in silence_midpoints(&app, &reference)? {
+            let last_cut = *cut_points.last().unwrap_or(&0.0);
+            if midpoint - last_cut >= clip_duration && total_duration - midpoint >= clip_duration / 2.0 {
+                cut_points.push(midpoint);
+            }
+        }
+    } else {
+        let mut next_cut = clip_duration;
+        while total_duration - next_cut >= clip_duration / 2.0 {
+            cut_points.push(next_cut);
+            next_cut += clip_duration;
+        }
+    }
+    cut_points.push(total_duration);
+
+    println!("Cutting {} cameras into {} segments", camera_paths.len(), cut_points.len() - 1);
+
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_multi_angle")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let result = async {
+        let mut clips = Vec::with_capacity(cut_points.len() - 1);
+        let mut timeline_position = 0.0;
+        for (i, window) in cut_points.windows(2).enumerate() {
+            let camera_path = &camera_paths[i % camera_paths.len()];
+            let segment_path = trim_video(app.clone(), TrimParams {
+                input_path: camera_path.clone(),
+                output_path: work_dir.join(format!("segment_{:04}.mp4", i)).to_string_lossy().to_string(),
+                start_time: window[0],
+                end_time: window[1],
+                snap_to_keyframe: false,
+                stream_index: None,
+                hardware_accel: None,
+            })
+            .await?;
+
+            let metadata = get_video_metadata(app.clone(), segment_path.clone(), None).await?;
+            let duration = metadata.duration;
+            clips.push(VideoClip {
+                id: format!("angle_{}", i),
+                file_path: segment_path,
+                metadata,
+                start_time: timeline_position,
+                end_time: timeline_position + duration,
+                trim_in: 0.0,
+                trim_out: duration,
+                volume: 1.0,
+                filters: Vec::new(),
+            });
+            timeline_position += duration;
+        }
+
+        export_timeline(app.clone(), ExportParams {
+            clips,
+            output_path,
+            resolution: resolution_name_for_height(first_height).to_string(),
+            stream_index: None,
+            hardware_accel: None,
+        })
+        .await
+    }
+    .await;
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result
+}
+
+#[command]
+pub async fn analyze_scene_complexity(
+    app: AppHandle,
+    input_path: String,
+    window_seconds: f64,
+) -> Result<Vec<SceneComplexity>, String> {
+    if window_seconds <= 0.0 {
+        return Err("Window length must be greater than zero".to_string());
+    }
+
+    // select with a zero threshold keeps every frame but makes the scene filter score each one
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-an")
+        .arg("-vf")
+        .arg("select='gte(scene,0)',metadata=print:key=lavfi.scene_score")
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .tracked_output(&app, "scene_complexity", &input_path, "")
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // metadata=print logs a "pts_time:" line for each frame followed by its score
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut scores: Vec<(f64, f64)> = Vec::new();
+    let mut frame_time = None;
+    for line in stderr.lines() {
+        if let Some(time) = parse_log_value(line, "pts_time:") {
+            frame_time = Some(time);
+        } else if let Some(score) = parse_log_value(line, "lavfi.scene_score=") {
+            if let Some(time) = frame_time.take() {
+                scores.push((time, score));
+            }
+        }
+    }
+
+    if scores.is_empty() {
+        return Err("No scene scores were reported, is this a video file?".to_string());
+    }
+
+    let last_time = scores.iter().map(|(time, _)| *time).fold(0.0, f64::max);
+    let bucket_count = (last_time / window_seconds).floor() as usize + 1;
+    let mut buckets: Vec<(f64, f64, u64)> = vec![(0.0, 0.0, 0); bucket_count];
+    for (time, score) in scores {
+        let bucket = &mut buckets[((time / window_seconds).floor() as usize).min(bucket_count - 1)];
+        bucket.0 += score;
+        bucket.1 = bucket.1.max(score);
+        bucket.2 += 1;
+    }
+
+    Ok(buckets
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (_, _, count))| *count > 0)
+        .map(|(i, (sum, max_complexity, count))| {
+            let mean_complexity = sum / count as f64;
+            let suggested_crf_adjustment = if mean_complexity > HIGH_COMPLEXITY_THRESHOLD {
+                -5
+            } else if mean_complexity < LOW_COMPLEXITY_THRESHOLD {
+                5
+            } else {
+                0
+            };
+            let time_start = i as f64 * window_seconds;
+            SceneComplexity {
+                time_start,
+                // The final bucket ends at the last scored frame rather than a full window
+                time_end: ((i + 1) as f64 * window_seconds).min(last_time).max(time_start),
+                mean_complexity,
+                max_complexity,
+                suggested_crf_adjustment,
+            }
+        })
+        .collect())
+}
+
+/// Camera motion per frame from a vid.stab transforms file: the magnitude of the median local motion vector
+fn parse_vidstab_frame_motion(transforms: &str) -> Vec<f64> {
+    let median = |values: &mut Vec<f64>| {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        values[values.len() / 2]
+    };
+
+    // Frame lines look like "Frame 12 (List 3 [(LM 4 -3 24 24 32 0.8 0.1),(LM ...)])"
+    transforms
+        .lines()
+        .filter(|line| line.starts_with("Frame "))
+        .map(|line| {
+            let (mut xs, mut ys) = (Vec::new(), Vec::new());
+            for field in line.split("(LM ").skip(1) {
+                let mut values = field.split_whitespace().map(|value| value.parse::<f64>());
+                if let (Some(Ok(x)), Some(Ok(y))) = (values.next(), values.next()) {
+                    xs.push(x);
+                    ys.push(y);
+                }
+            }
+            // The median ignores subjects moving through the frame, leaving the camera's own motion
+            if xs.is_empty() {
+                0.0
+            } else {
+                median(&mut xs).hypot(median(&mut ys))
+            }
+        })
+        .collect()
+}
+
+#[command]
+pub async fn measure_camera_shake(app: AppHandle, input_path: String) -> Result<ShakeMeasurement, String> {
+    if !std::path::Path::new(&input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    // vidstabdetect writes its motion data to the result file rather than the log
+    let transforms_path = app_temp_dir(&app).join(format!("clipforge_shake_{}.trf", uuid::Uuid::new_v4()));
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-an")
+        .arg("-vf")
+        .arg(format!(
+            "vidstabdetect=shakiness=5:accuracy=4:result='{}'",
+            escape_filter_path(&transforms_path)
+        ))
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .tracked_output(&app, "camera_shake", &input_path, "")
+        .await
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e));
+
+    let transforms = std::fs::read_to_string(&transforms_path);
+    let _ = std::fs::remove_file(&transforms_path);
+
+    let output = output?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let motion = parse_vidstab_frame_motion(&transforms.map_err(|e| format!("Failed to read motion data: {}", e))?);
+    if motion.is_empty() {
+        return Err("No motion data was reported, is this a video file?".to_string());
+    }
+
+    let mean_motion = motion.iter().sum::<f64>() / motion.len() as f64;
+    let max_motion = motion.iter().copied().fold(0.0, f64::max);
+    let shake_level = SHAKE_LEVELS
+        .iter()
+        .find(|(limit, _)| mean_motion < *limit)
+        .map(|(_, level)| *level)
+        .unwrap_or("heavy");
+
+    Ok(ShakeMeasurement {
+        mean_motion,
+        max_motion,
+        shake_level: shake_level.to_string(),
+        stabilization_recommended: matches!(shake_level, "moderate" | "heavy"),
+    })
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn render_image_transition(
+    app: AppHandle,
+    image_a: String,
+    image_b: String,
+    output_path: String,
+    transition: String,
+    duration: f64,
+    width: u32,
+    height: u32,
+) -> Result<String, String> {
+    for image in [&image_a, &image_b] {
+        if !std::path::Path::new(image).exists() {
+            return Err(format!("Image not found: {}", image));
+        }
+    }
+    if !XFADE_TRANSITIONS.contains(&transition.as_str()) {
+        return Err(format!(
+            "Unknown transition type: {}. Supported: {}",
+            transition,
+            XFADE_TRANSITIONS.join(", ")
+        ));
+    }
+    if duration <= 0.0 {
+        return Err("Transition duration must be greater than zero".to_string());
+    }
+    if width < 2 || height < 2 || !width.is_multiple_of(2) || !height.is_multiple_of(2) {
+        return Err("Width and height must be even numbers of at least 2".to_string());
+    }
+
+    // xfade's output is len(a) + len(b) - duration, so each still must last the full duration
+    let still_duration = duration.to_string();
+    let fit = format!(
+        "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps=30,format=yuv420p",
+        w = width,
+        h = height
+    );
+    let filter_complex = format!(
+        "[0:v]{fit}[a];[1:v]{fit}[b];[a][b]xfade=transition={}:duration={}:offset=0[outv]",
+        transition,
+        duration,
+        fit = fit
+    );
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-loop", "1", "-t", &still_duration, "-i", &image_a,
+            "-loop", "1", "-t", &still_duration, "-i", &image_b,
+            "-filter_complex", &filter_complex,
+            "-map", "[outv]",
+            "-t", &still_duration,
+            "-c:v", "libx264",
+            "-preset", "medium",
+            "-crf", "18",
+            &output_path,
+        ])
+        .tracked_output(&app, "image_transition", &image_a, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Decode a file's audio through `audio_filter` (which must yield mono) into f32 samples at `sample_rate`
+async fn decode_mono_pcm(
+    app: &AppHandle,
+    input_path: &str,
+    audio_filter: &str,
+    sample_rate: u32,
+    operation: &str,
+) -> Result<Vec<f32>, String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let mut child = TokioCommand::new(ffmpeg_path)
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vn")
+        .arg("-af")
+        .arg(audio_filter)
+        .arg("-ar")
+        .arg(sample_rate.to_string())
+        .arg("-f")
+        .arg("f32le")
+        .arg("pipe:1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    let mut stderr = child.stderr.take().ok_or("Failed to capture ffmpeg stderr")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = String::new();
+        let _ = stderr.read_to_string(&mut buffer).await;
+        buffer
+    });
+
+    let mut stdout = child.stdout.take().ok_or("Failed to capture ffmpeg stdout")?;
+    let operation_id = register_operation(app, operation, input_path, "", child.id());
+
+    let mut pcm = Vec::new();
+    let read_result = stdout.read_to_end(&mut pcm).await;
+    let status = child.wait().await;
+    deregister_operation(app, &operation_id);
+
+    read_result.map_err(|e| format!("Failed to read ffmpeg output: {}", e))?;
+    let status = status.map_err(|e| format!("Failed to wait for ffmpeg: {}", e))?;
+    if !status.success() {
+        return Err(format!("ffmpeg failed: {}", stderr_task.await.unwrap_or_default()));
+    }
+
+    Ok(pcm
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect())
+}
+
+#[command]
+pub async fn extract_waveform_data(
+    app: AppHandle,
+    input_path: String,
+    samples_per_second: u32,
+    channel: u32,
+) -> Result<WaveformData, String> {
+    if samples_per_second == 0 {
+        return Err("Samples per second must be greater than zero".to_string());
+    }
+
+    let channel_count = audio_channel_count(&app, &input_path)?;
+    if channel >= channel_count {
+        return Err(format!(
+            "Channel {} does not exist, the audio has {} channel(s)",
+            channel, channel_count
+        ));
+    }
+
+    // Decode above the output rate so each output sample is the peak of a bucket, not a resampled value
+    let decode_rate = WAVEFORM_DECODE_RATE.max(samples_per_second);
+    let bucket_size = (decode_rate / samples_per_second) as usize;
+    let decode_rate = bucket_size as u32 * samples_per_second;
+
+    let decoded = decode_mono_pcm(&app, &input_path, &format!("pan=mono|c0=c{}", channel), decode_rate, "waveform_data").await?;
+
+    // Keep the sign of the largest excursion so the frontend can draw above and below the axis
+    let samples = decoded
+        .chunks(bucket_size)
+        .map(|bucket| {
+            bucket
+                .iter()
+                .cloned()
+                .fold(0.0f32, |peak, sample| if sample.abs() > peak.abs() { sample } else { peak })
+                .clamp(-1.0, 1.0)
+        })
+        .collect();
+
+    Ok(WaveformData {
+        sample_rate: samples_per_second,
+        duration: decoded.len() as f64 / decode_rate as f64,
+        samples,
+        channel_count,
+    })
+}
+
+/// Linear RMS level (0.0 - 1.0) per `1 / samples_per_second` bucket of the mono downmix, for timeline waveforms
+#[command]
+pub async fn extract_audio_waveform(app: AppHandle, file_path: String, samples_per_second: u32) -> Result<Vec<f32>, String> {
+    if samples_per_second == 0 {
+        return Err("Samples per second must be greater than zero".to_string());
+    }
+    if probe_audio_channels(&app, &file_path)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let bucket_size = (WAVEFORM_DECODE_RATE.max(samples_per_second) / samples_per_second) as usize;
+    let decode_rate = bucket_size as u32 * samples_per_second;
+    let decoded = decode_mono_pcm(&app, &file_path, "aformat=channel_layouts=mono", decode_rate, "audio_waveform").await?;
+
+    Ok(decoded
+        .chunks(bucket_size)
+        .map(|bucket| {
+            let mean_square = bucket.iter().map(|sample| sample * sample).sum::<f32>() / bucket.len() as f32;
+            mean_square.sqrt().clamp(0.0, 1.0)
+        })
+        .collect())
+}
+
+/// Packet presentation times for the first stream of a type ("a:0" or "v:0")
+fn read_packet_times(app: &AppHandle, file_path: &str, stream: &str) -> Result<Vec<f64>, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let read_interval = format!("%+{}", AV_SYNC_PROBE_SECONDS);
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-select_streams", stream,
+            "-read_intervals", &read_interval,
+            "-show_entries", "packet=pts_time,stream_index,flags",
+            "-of", "compact",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // Lines look like "packet|pts_time=0.021333|stream_index=1|flags=K__"
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            line.split('|')
+                .find_map(|field| field.strip_prefix("pts_time="))
+                .and_then(|value| value.parse().ok())
+        })
+        .collect())
+}
+
+#[command]
+pub async fn measure_av_sync(app: AppHandle, input_path: String) -> Result<AvSyncMeasurement, String> {
+    let audio_times = read_packet_times(&app, &input_path, "a:0")?;
+    let video_times = read_packet_times(&app, &input_path, "v:0")?;
+    if audio_times.is_empty() {
+        return Err("No audio packets found".to_string());
+    }
+    if video_times.is_empty() {
+        return Err("No video packets found".to_string());
+    }
+
+    // Video packets arrive in decode order, so compare presentation starts rather than first packets
+    let audio_start = audio_times.iter().cloned().fold(f64::INFINITY, f64::min);
+    let video_start = video_times.iter().cloned().fold(f64::INFINITY, f64::min);
+    let audio_offset_ms = (audio_start - video_start) * 1000.0;
+
+    // Audio packets should be evenly spaced and always increasing
+    let intervals: Vec<f64> = audio_times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let non_monotonic = intervals.iter().filter(|interval| **interval <= 0.0).count();
+    let mut sorted_intervals: Vec<f64> = intervals.iter().cloned().filter(|interval| *interval > 0.0).collect();
+    sorted_intervals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let max_jitter_ms = match sorted_intervals.get(sorted_intervals.len() / 2) {
+        Some(median) => intervals
+            .iter()
+            .map(|interval| (interval - median).abs() * 1000.0)
+            .fold(0.0, f64::max),
+        None => 0.0,
+    };
+
+    let mut warnings = Vec::new();
+    if audio_offset_ms.abs() > PERCEPTIBLE_AV_OFFSET_MS {
+        warnings.push(format!(
+            "Audio is {:.0} ms {} the video, which is perceptible",
+            audio_offset_ms.abs(),
+            if audio_offset_ms < 0.0 { "ahead of" } else { "behind" }
+        ));
+    }
+    if non_monotonic > 0 {
+        warnings.push(format!("{} audio packets have non-increasing timestamps", non_monotonic));
+    }
+
+    Ok(AvSyncMeasurement {
+        audio_offset_ms,
+        is_audio_early: audio_offset_ms < 0.0,
+        max_jitter_ms,
+        total_packets_analyzed: (audio_times.len() + video_times.len()) as u64,
+        warning: if warnings.is_empty() { None } else { Some(warnings.join("; ")) },
+    })
+}
+
+/// Shift the audio to cancel a measured offset; pass `audio_offset_ms` from `measure_av_sync`
+#[command]
+pub async fn correct_av_sync(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    offset_ms: f64,
+) -> Result<String, String> {
+    // Late audio (positive offset) needs an earlier start, so the audio input is offset by the negated value
+    let audio_shift = format!("{:.3}", -offset_ms / 1000.0);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-y",
+            "-i", &input_path,
+            "-itsoffset", &audio_shift,
+            "-i", &input_path,
+            "-map", "0:v:0",
+            "-map", "1:a:0",
+            "-c", "copy",
+            &output_path,
+        ])
+        .tracked_output(&app, "correct_av_sync", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+/// Chapter titles in a file, with untitled chapters numbered
+fn read_chapter_titles(app: &AppHandle, file_path: &str) -> Result<Vec<String>, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-show_chapters",
+            "-of", "json",
+            file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+    Ok(json["chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .enumerate()
+                .map(|(i, chapter)| {
+                    chapter["tags"]["title"]
+                        .as_str()
+                        .map(|title| title.to_string())
+                        .unwrap_or_else(|| format!("Chapter {}", i + 1))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+#[command]
+pub async fn transcode_with_chapters(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    codec: String,
+    crf: u32,
+) -> Result<TranscodeResult, String> {
+    if !std::path::Path::new(&input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+    if !encoding_presets().iter().any(|preset| preset.codec == codec) {
+        return Err(format!("Unsupported codec: {}. Use libx264, libx265 or libvpx-vp9", codec));
+    }
+    // libvpx-vp9 accepts up to 63, the x26x encoders up to 51
+    let max_crf = if codec == "libvpx-vp9" { 63 } else { 51 };
+    if crf > max_crf {
+        return Err(format!("CRF for {} must be between 0 and {}", codec, max_crf));
+    }
+
+    let chapter_titles = read_chapter_titles(&app, &input_path)?;
+
+    let crf = crf.to_string();
+    let mut args = vec![
+        "-y", "-i", &input_path,
+        "-map", "0:v", "-map", "0:a?",
+        "-map_metadata", "0",
+        "-map_chapters", "0",
+        "-c:v", &codec,
+        "-crf", &crf,
+    ];
+    if codec == "libvpx-vp9" {
+        // Constant quality mode in VP9 needs the bitrate cap removed
+        args.extend(["-b:v", "0"]);
+    }
+    args.extend(["-c:a", "copy", &output_path]);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "transcode", &input_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(TranscodeResult {
+        output_path,
+        chapters_preserved: chapter_titles.len(),
+        chapter_titles,
+    })
+}
+
+/// Momentary loudness (time, LUFS) every 100 ms from ebur128's frame log
+fn read_momentary_loudness(app: &AppHandle, audio_path: &str) -> Result<Vec<(f64, f64)>, String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = Command::new(ffmpeg_path)
+        .args([
+            "-i", audio_path,
+            "-vn",
+            "-af", "ebur128=framelog=info",
+            "-f", "null",
+            "-",
+        ])
+        .tracked_output(app, "beat_detection", audio_path, "")
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(stderr
+        .lines()
+        .filter(|line| line.contains("Parsed_ebur128"))
+        .filter_map(|line| Some((parse_log_value(line, " t:")?, parse_log_value(line, " M:")?)))
+        .collect())
+}
+
+/// Approximate beat times: local loudness peaks that rise clearly above the preceding second
+fn detect_loudness_beats(loudness: &[(f64, f64)]) -> Vec<f64> {
+    let mut beats: Vec<f64> = Vec::new();
+    for i in 1..loudness.len().saturating_sub(1) {
+        let (time, level) = loudness[i];
+        if level <= loudness[i - 1].1 || level < loudness[i + 1].1 || level < -70.0 {
+            continue;
+        }
+
+        let window = &loudness[i.saturating_sub(10)..i];
+        let baseline = window.iter().map(|(_, m)| m).sum::<f64>() / window.len() as f64;
+        if level - baseline < BEAT_LOUDNESS_RISE_LU {
+            continue;
+        }
+
+        if beats.last().is_none_or(|last| time - last >= MIN_BEAT_SPACING_SECONDS) {
+            beats.push(time);
+        }
+    }
+    beats
+}
+
+#[command]
+pub async fn generate_beat_synced_montage(
+    app: AppHandle,
+    clips: Vec<String>,
+    audio_path: String,
+    output_path: String,
+    clip_duration_beats: u32,
+) -> Result<MontageResult, String> {
+    if clips.is_empty() {
+        return Err("At least one clip is required".to_string());
+    }
+    if clip_duration_beats == 0 {
+        return Err("Clip duration must be at least one beat".to_string());
+    }
+    for path in &clips {
+        if !std::path::Path::new(path).exists() {
+            return Err(format!("Clip file not found: {}", path));
+        }
+    }
+    if !std::path::Path::new(&audio_path).exists() {
+        return Err(format!("Audio file not found: {}", audio_path));
+    }
+
+    let mut clip_metadata = Vec::with_capacity(clips.len());
+    for path in &clips {
+        let metadata = get_video_metadata(app.clone(), path.clone(), None).await?;
+        if metadata.duration <= 0.0 {
+            return Err(format!("Clip has no duration: {}", path));
+        }
+        clip_metadata.push(metadata);
+    }
+
+    let loudness = read_momentary_loudness(&app, &audio_path)?;
+    let audio_duration = loudness.last().map(|(time, _)| *time).unwrap_or(0.0);
+    let beats = detect_loudness_beats(&loudness);
+    if beats.len() < 2 || audio_duration <= 0.0 {
+        return Err("Could not detect a beat in the audio".to_string());
+    }
+
+    // Median spacing is robust to the odd missed or extra beat
+    let mut intervals: Vec<f64> = beats.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    intervals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let bpm_estimate = 60.0 / intervals[intervals.len() / 2];
+
+    let mut cut_points = vec![0.0];
+    cut_points.extend(
+        beats
+            .iter()
+            .skip(clip_duration_beats as usize)
+            .step_by(clip_duration_beats as usize)
+            .cloned()
+            .filter(|beat| *beat < audio_duration),
+    );
+    cut_points.push(audio_duration);
+
+    println!(
+        "Detected {} beats (~{:.0} BPM), cutting {} clips into {} segments",
+        beats.len(), bpm_estimate, clips.len(), cut_points.len() - 1
+    );
+
+    // Each source keeps its own playhead so repeated clips show new footage
+    let mut playheads = vec![0.0; clips.len()];
+    let mut timeline_clips = Vec::with_capacity(cut_points.len() - 1);
+    let mut timeline_position = 0.0;
+    for (i, window) in cut_points.windows(2).enumerate() {
+        let source = i % clips.len();
+        let metadata = &clip_metadata[source];
+        let shot_length = window[1] - window[0];
+        if playheads[source] + shot_length > metadata.duration {
+            playheads[source] = 0.0;
+        }
+
+        // A clip shorter than its window is looped so the next cut still lands on the beat
+        let mut remaining = shot_length;
+        while remaining > MONTAGE_MIN_PIECE_SECONDS {
+            if metadata.duration - playheads[source] < MONTAGE_MIN_PIECE_SECONDS {
+                playheads[source] = 0.0;
+            }
+            let trim_in = playheads[source];
+            let piece_length = remaining.min(metadata.duration - trim_in);
+            timeline_clips.push(VideoClip {
+                id: format!("beat_{}_{}", i, timeline_clips.len()),
+                file_path: clips[source].clone(),
+                metadata: metadata.clone(),
+                start_time: timeline_position,
+                end_time: timeline_position + piece_length,
+                trim_in,
+                trim_out: trim_in + piece_length,
+                volume: 1.0,
+                filters: Vec::new(),
+            });
+            playheads[source] += piece_length;
+            timeline_position += piece_length;
+            remaining -= piece_length;
+        }
+    }
+    let total_cuts = cut_points.len() - 2;
+
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_beat_montage")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let result = async {
+        let video_path = work_dir.join("montage_video.mp4").to_string_lossy().to_string();
+        export_timeline(app.clone(), ExportParams {
+            clips: timeline_clips,
+            output_path: video_path.clone(),
+            resolution: resolution_name_for_height(clip_metadata[0].height).to_string(),
+            stream_index: None,
+            hardware_accel: None,
+        })
+        .await?;
+
+        // Replace the clips' own audio with the track the cuts were timed to
+        let ffmpeg_path = get_ffmpeg_path(&app)?;
+        let output = Command::new(ffmpeg_path)
+            .args([
+                "-y",
+                "-i", &video_path,
+                "-i", &audio_path,
+                "-map", "0:v",
+                "-map", "1:a",
+                "-c:v", "copy",
+                "-c:a", "aac",
+                "-b:a", "192k",
+                "-shortest",
+                &output_path,
+            ])
+            .tracked_output(&app, "beat_montage", &audio_path, &output_path)
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+    result?;
+
+    Ok(MontageResult {
+        bpm_estimate,
+        total_cuts,
+        output_path,
+    })
+}
+
+#[command]
+pub async fn merge_with_crossfades(
+    app: AppHandle,
+    clips: Vec<VideoClip>,
+    crossfade_duration: f64,
+    output_path: String,
+) -> Result<String, String> {
+    if clips.len() < 2 {
+        return Err("At least two clips are required to crossfade".to_string());
+    }
+    if crossfade_duration < 0.0 {
+        return Err("Crossfade duration cannot be negative".to_string());
+    }
+    for clip in &clips {
+        if !std::path::Path::new(&clip.file_path).exists() {
+            return Err(format!("Clip file not found: {}", clip.file_path));
+        }
+    }
+
+    let durations: Vec<f64> = clips.iter().map(|clip| clip.trim_out - clip.trim_in).collect();
+    let shortest = durations.iter().cloned().fold(f64::INFINITY, f64::min);
+    if crossfade_duration >= shortest {
+        return Err(format!(
+            "Crossfade duration must be shorter than the shortest clip ({:.2}s)",
+            shortest
+        ));
+    }
+
+    let mut has_audio = Vec::with_capacity(clips.len());
+    for clip in &clips {
+        has_audio.push(has_audio_stream(&app, &clip.file_path)?);
+    }
+    let any_audio = has_audio.iter().any(|audio| *audio);
+
+    // xfade needs matching size, frame rate, pixel format and timebase on both inputs
+    let mut filters = Vec::new();
+    for (i, clip) in clips.iter().enumerate() {
+        filters.push(format!(
+            "[{i}:v]trim=start={}:end={},setpts=PTS-STARTPTS,\
+scale=1920:1080:force_original_aspect_ratio=decrease,pad=1920:1080:(ow-iw)/2:(oh-ih)/2,\
+setsar=1,fps=30,format=yuv420p,settb=AVTB[v{i}]",
+            clip.trim_in, clip.trim_out
+        ));
+        if any_audio {
+            if has_audio[i] {
+                filters.push(format!(
+                    "[{i}:a]atrim=start={}:end={},asetpts=PTS-STARTPTS,\
+aformat=sample_rates=48000:channel_layouts=stereo[a{i}]",
+                    clip.trim_in, clip.trim_out
+                ));
+            } else {
+                // Silent filler keeps the audio chain the same length as the video
+                filters.push(format!(
+                    "anullsrc=channel_layout=stereo:sample_rate=48000,atrim=duration={}[a{i}]",
+                    durations[i]
+                ));
+            }
+        }
+    }
+
+    if crossfade_duration == 0.0 {
+        // A zero-length crossfade is a hard cut, e.g. prepending a countdown clip
+        let inputs: String = (0..clips.len())
+            .map(|i| if any_audio { format!("[v{i}][a{i}]") } else { format!("[v{i}]") })
+            .collect();
+        filters.push(if any_audio {
+            format!("{}concat=n={}:v=1:a=1[outv][outa]", inputs, clips.len())
+        } else {
+            format!("{}concat=n={}:v=1:a=0[outv]", inputs, clips.len())
+        });
+    } else {
+        let mut video_label = "v0".to_string();
+        let mut audio_label = "a0".to_string();
+        let mut elapsed = durations[0];
+        for (i, duration) in durations.iter().enumerate().skip(1) {
+            // Each crossfade overlaps the previous result, so the next one starts that much earlier
+            let offset = elapsed - crossfade_duration * i as f64;
+            let next_video = if i == clips.len() - 1 { "outv".to_string() } else { format!("vx{}", i) };
+            filters.push(format!(
+                "[{}][v{}]xfade=transition=fade:duration={}:offset={:.3}[{}]",
+                video_label, i, crossfade_duration, offset, next_video
+            ));
+            video_label = next_video;
+
+            if any_audio {
+                let next_audio = if i == clips.len() - 1 { "outa".to_string() } else { format!("ax{}", i) };
+                filters.push(format!(
+                    "[{}][a{}]acrossfade=d={}[{}]",
+                    audio_label, i, crossfade_duration, next_audio
+                ));
+                audio_label = next_audio;
+            }
+            elapsed += duration;
+        }
+    }
+
+    let mut args = vec!["-y".to_string()];
+    for clip in &clips {
+        args.push("-i".to_string());
+        args.push(clip.file_path.clone());
+    }
+    args.extend(["-filter_complex".to_string(), filters.join(";")]);
+    args.extend(["-map".to_string(), "[outv]".to_string()]);
+    if any_audio {
+        args.extend(["-map".to_string(), "[outa]".to_string(), "-c:a".to_string(), "aac".to_string()]);
+    }
+    args.extend([
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "medium".to_string(),
+        "-crf".to_string(),
+        "20".to_string(),
+        output_path.clone(),
+    ]);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .args(&args)
+        .tracked_output(&app, "crossfade_merge", &clips[0].file_path, &output_path)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_list_rows_parse_plain_and_quoted_names() {
+        assert_eq!(
+            parse_segment_list_row(".clip_000.mp4,0.000000,12.480000"),
+            Some((".clip_000.mp4".to_string(), 0.0))
+        );
+        assert_eq!(
+            parse_segment_list_row("\".intro, take \"\"2\"\"_001.mp4\",12.480000,30.000000\r"),
+            Some((".intro, take \"2\"_001.mp4".to_string(), 12.48))
+        );
+        assert_eq!(parse_segment_list_row("\"unterminated.mp4,1.0,2.0"), None);
+    }
+
+    const ENCODERS_OUTPUT: &str = "Encoders:
+ V..... = Video
+ A..... = Audio
+ ------
+ V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10 (codec h264)
+ V....D h264_nvenc           NVIDIA NVENC H.264 encoder (codec h264)
+ V....D h264_vaapi           H.264/AVC (VAAPI) (codec h264)
+ V....D hevc_nvenc           NVIDIA NVENC hevc encoder (codec hevc)
+ A....D aac                  AAC (Advanced Audio Coding)
+";
+
+    #[test]
+    fn parses_compiled_h264_encoders() {
+        let encoders = parse_available_encoders(ENCODERS_OUTPUT);
+        let accels: Vec<HardwareAccel> = encoders.iter().map(|e| e.hardware_accel).collect();
+        assert_eq!(accels, [HardwareAccel::None, HardwareAccel::Nvenc, HardwareAccel::Vaapi]);
+        assert_eq!(encoders[1].encoder, "h264_nvenc");
+        assert_eq!(encoders[1].description, "NVIDIA NVENC H.264 encoder (codec h264)");
+    }
+
+    #[test]
+    fn skips_encoders_missing_from_build() {
+        let encoders = parse_available_encoders(" V....D libx264              libx264 H.264\n");
+        assert_eq!(encoders.len(), 1);
+        assert_eq!(encoders[0].hardware_accel, HardwareAccel::None);
+        assert!(parse_available_encoders("").is_empty());
+    }
+
+    #[test]
+    fn ignores_header_and_non_video_rows() {
+        let encoders = parse_available_encoders(" V..... = Video\n A....D h264_nvenc  not really video\n");
+        assert!(encoders.is_empty());
+    }
+
+    #[test]
+    fn software_encoder_uses_crf() {
+        let args = HardwareAccel::None.video_encode_args(1920, 1080, 30.0);
+        assert_eq!(args[..2], ["-c:v", "libx264"]);
+        assert!(args.windows(2).any(|pair| pair == ["-crf", "23"]));
+        assert!(args.windows(2).any(|pair| pair == ["-level", "4.0"]));
+    }
+
+    #[test]
+    fn h264_level_follows_resolution() {
+        assert_eq!(resolution_to_h264_level(1920, 1080, 30.0), "4.0");
+        assert_eq!(resolution_to_h264_level(2560, 1440, 30.0), "5.0");
+        assert_eq!(resolution_to_h264_level(3840, 2160, 30.0), "5.1");
+        assert_eq!(resolution_to_h264_level(7680, 4320, 30.0), "5.2");
+    }
+
+    #[test]
+    fn h264_level_boundaries() {
+        // 1080p is 8160 macroblocks, so level 4.0's 245760 per second allows up to about 30.1 fps
+        assert_eq!(resolution_to_h264_level(1920, 1080, 30.1), "4.0");
+        assert_eq!(resolution_to_h264_level(1920, 1080, 30.2), "5.0");
+        assert_eq!(resolution_to_h264_level(1920, 1080, 60.0), "5.0");
+        // 2048x1088 is 8704 macroblocks, past level 4.0's 8192 frame size
+        assert_eq!(resolution_to_h264_level(2048, 1088, 24.0), "5.0");
+        assert_eq!(resolution_to_h264_level(2560, 1440, 60.0), "5.1");
+        assert_eq!(resolution_to_h264_level(3840, 2160, 30.0), "5.1");
+        assert_eq!(resolution_to_h264_level(3840, 2160, 60.0), "5.2");
+        // 4096x2304 is level 5.x's 36864 macroblock frame size limit
+        assert_eq!(resolution_to_h264_level(4096, 2304, 24.0), "5.1");
+        assert_eq!(resolution_to_h264_level(4112, 2304, 24.0), "5.2");
+    }
+
+    #[test]
+    fn nvenc_uses_bitrate_for_resolution() {
+        let args = HardwareAccel::Nvenc.video_encode_args(3840, 2160, 30.0);
+        assert_eq!(args[..2], ["-c:v", "h264_nvenc"]);
+        assert!(args.windows(2).any(|pair| pair == ["-b:v", "35000k"]));
+        assert!(!args.iter().any(|arg| arg == "-crf"));
+    }
+
+    #[test]
+    fn videotoolbox_uses_quality_scale() {
+        let args = HardwareAccel::VideoToolbox.video_encode_args(1920, 1080, 30.0);
+        assert_eq!(args, ["-c:v", "h264_videotoolbox", "-q:v", "65"]);
+    }
+
+    #[test]
+    fn vaapi_opens_device_and_uploads_frames() {
+        let args = HardwareAccel::Vaapi.video_encode_args(1920, 1080, 30.0);
+        assert_eq!(args[..2], ["-c:v", "h264_vaapi"]);
+        assert_eq!(HardwareAccel::Vaapi.device_args(), ["-vaapi_device", VAAPI_DEVICE]);
+        assert_eq!(HardwareAccel::Vaapi.upload_filter(), Some("format=nv12,hwupload"));
+        assert!(HardwareAccel::Nvenc.device_args().is_empty());
+        assert!(HardwareAccel::None.upload_filter().is_none());
+    }
+
+    #[test]
+    fn hardware_accel_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&HardwareAccel::VideoToolbox).unwrap(), "\"videotoolbox\"");
+        let params: ExportParams =
+            serde_json::from_str(r#"{"clips":[],"output_path":"out.mp4","resolution":"1080p","hardware_accel":"nvenc"}"#)
+                .unwrap();
+        assert_eq!(params.hardware_accel, Some(HardwareAccel::Nvenc));
+    }
+
+    fn orientation(rotation: f64, hflip: bool) -> DisplayOrientation {
+        DisplayOrientation { rotation, hflip }
+    }
+
+    #[test]
+    fn display_matrix_decodes_rotation_and_mirroring() {
+        // As ffprobe prints them for `-display_rotation -90` and `-display_hflip`
+        let rotated = "\n00000000:           0       65536           0\n00000001:      -65536           0           0\n00000002:           0           0  1073741824\n";
+        let mirrored = "\n00000000:      -65536           0           0\n00000001:           0       65536           0\n00000002:           0           0  1073741824\n";
+        assert_eq!(DisplayOrientation::from_display_matrix(rotated), Some(orientation(-90.0, false)));
+        assert_eq!(DisplayOrientation::from_display_matrix(mirrored), Some(orientation(0.0, true)));
+        assert_eq!(DisplayOrientation::from_display_matrix("not a matrix"), None);
+    }
+
+    #[test]
+    fn rotations_compose_onto_the_displayed_orientation() {
+        assert_eq!(DisplayOrientation::IDENTITY.then(Rotation::Cw90), orientation(-90.0, false));
+        assert_eq!(orientation(-90.0, false).then(Rotation::Cw90), orientation(180.0, false));
+        assert_eq!(orientation(180.0, false).then(Rotation::Rotate180), orientation(0.0, false));
+        assert_eq!(orientation(90.0, false).then(Rotation::FlipHorizontal), orientation(90.0, true));
+        assert_eq!(orientation(90.0, false).then(Rotation::FlipVertical), orientation(-90.0, true));
+    }
+
+    #[test]
+    fn existing_mirroring_is_kept_and_reverses_turns() {
+        assert_eq!(orientation(0.0, true).then(Rotation::Cw90), orientation(90.0, true));
+        assert_eq!(orientation(0.0, true).then(Rotation::Ccw90), orientation(-90.0, true));
+        assert_eq!(orientation(0.0, true).then(Rotation::FlipHorizontal), orientation(0.0, false));
+        assert_eq!(orientation(0.0, true).then(Rotation::FlipVertical), orientation(180.0, false));
+    }
+
+    #[test]
+    fn ffmpeg_release_reads_tagged_builds_only() {
+        assert_eq!(ffmpeg_release("ffmpeg version 6.1.1-static https://johnvansickle.com/ffmpeg/"), Some((6, 1)));
+        assert_eq!(ffmpeg_release("ffmpeg version n7.0 Copyright (c) 2000-2024"), Some((7, 0)));
+        assert_eq!(ffmpeg_release("ffmpeg version 5.1.4-0+deb12u1 Copyright"), Some((5, 1)));
+        assert_eq!(ffmpeg_release("ffmpeg version N-113237-g1a2b3c4d5e Copyright"), None);
+    }
+}
@@ -1,16 +1,177 @@
-use tauri::{command, AppHandle};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::io::{Read, Seek, SeekFrom};
+use std::process::Command;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
 use uuid::Uuid;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use crate::commands::VideoClip;
+use crate::commands::binary_utils::{
+    app_temp_dir, get_ffmpeg_path, get_ffprobe_path, supported_input_formats, ClipForgeConfig, FormatInfo,
+};
+use crate::commands::ffmpeg::available_disk_space;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub fingerprint: String,
+    pub clips: Vec<String>,
+    pub confirmed_by_hash: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub video_packet_count: Option<u64>,
+    pub audio_packet_count: Option<u64>,
+    pub can_be_repaired: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FontInfo {
+    pub family: String,
+    pub style: String,
+    pub path: String,
+}
+
+// Font enumeration is slow, so the list is built once per session
+static FONT_CACHE: OnceLock<Vec<FontInfo>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TempDirStats {
+    pub path: String,
+    pub used_bytes: u64,
+    pub file_count: usize,
+    pub oldest_file_age_seconds: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CleanupResult {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleResult {
+    pub output_path: String,
+    pub file_count: usize,
+    /// Combined size of the media files packed into the bundle
+    pub total_size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChecksumResult {
+    pub file_path: String,
+    pub algorithm: String,
+    pub checksum: String,
+    pub file_size_bytes: u64,
+    /// Unix seconds when the hash was computed, which predates this call for cached results
+    pub computed_at: u64,
+}
+
+/// Payload of the `clipforge://checksum-progress` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChecksumProgress {
+    pub file_path: String,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+}
+
+/// Computed checksums keyed by `<algorithm>:<path>`, held as managed state
+#[derive(Debug, Default)]
+pub struct ChecksumCache {
+    /// Modification time (ns since the epoch) the hash was computed for, the hash, and when it was computed
+    entries: HashMap<String, (u64, String, u64)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteSpeedEstimate {
+    pub measured_mbps: f64,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImportResult {
+    Video { clip: Box<VideoClip> },
+    Image { path: String },
+}
+
+const CHECKSUM_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+// Bytes hashed between checksum progress events
+const CHECKSUM_PROGRESS_INTERVAL_BYTES: u64 = 100 * 1024 * 1024;
+
+// Size of the probe file written to measure destination throughput
+const WRITE_PROBE_BYTES: usize = 4 * 1024 * 1024;
+
+// EDL timecodes are written at a fixed 30 fps non-drop rate
+const EDL_FPS: u64 = 30;
+
+// Common extensions used to classify pasted files and to list formats in import errors
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "mkv", "webm", "avi", "m4v", "flv", "wmv", "mpg", "mpeg", "ts"];
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "tiff", "heic"];
+
+// FFmpeg decoder messages that mean the file is damaged
+const CORRUPTION_ERRORS: &[&str] = &[
+    "error while decoding MB",
+    "Invalid data found",
+    "moov atom not found",
+    "Invalid NAL unit size",
+    "partial file",
+    "corrupt decoded frame",
+];
+
+// FFmpeg decoder messages that usually still leave a playable file
+const CORRUPTION_WARNINGS: &[&str] = &[
+    "concealing",
+    "Truncated",
+    "non monotonically increasing dts",
+    "missing picture",
+    "Packet corrupt",
+];
+
+// Size above which read_file_bytes warns about buffering the whole file
+const LARGE_FILE_WARNING_BYTES: u64 = 100 * 1024 * 1024;
+
+// Largest slice read_file_bytes_chunked returns in one call
+const MAX_CHUNK_BYTES: usize = 64 * 1024 * 1024;
+
+// Free space a custom temp directory needs before it is accepted
+const MIN_TEMP_DIR_FREE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
 
 #[command]
-pub async fn import_video(app: AppHandle, file_path: String) -> Result<VideoClip, String> {
+pub async fn import_video(app: AppHandle, file_path: String, preflight_check: Option<bool>) -> Result<VideoClip, String> {
     if !Path::new(&file_path).exists() {
         return Err("File does not exist".to_string());
     }
 
+    if preflight_check.unwrap_or(false) {
+        let report = check_file_integrity(app.clone(), file_path.clone()).await?;
+        if !report.valid {
+            return Err(format!("File failed integrity check: {}", report.errors.join("; ")));
+        }
+    }
+
     // Get video metadata using ffprobe
-    let metadata = crate::commands::ffmpeg::get_video_metadata(app, file_path.clone()).await?;
+    let metadata = match crate::commands::ffmpeg::get_video_metadata(app.clone(), file_path.clone(), None).await {
+        Ok(metadata) => metadata,
+        // Only consult the demuxer list once ffprobe fails, so a successful import never waits on building it
+        Err(e) => {
+            let formats = match supported_input_formats(&app).await {
+                Ok(formats) => formats,
+                Err(format_error) => {
+                    log::warn!("Could not list supported input formats: {}", format_error);
+                    return Err(e);
+                }
+            };
+            return Err(unsupported_format_message(&file_path, formats).unwrap_or(e));
+        }
+    };
     
     let clip = VideoClip {
         id: Uuid::new_v4().to_string(),
@@ -20,15 +181,38 @@ pub async fn import_video(app: AppHandle, file_path: String) -> Result<VideoClip
         end_time: metadata.duration,
         trim_in: 0.0,
         trim_out: metadata.duration,
+        volume: 1.0,
+        filters: Vec::new(),
     };
 
     Ok(clip)
 }
 
+/// Explain an ffprobe failure by the extension, when no demuxer in `formats` claims it
+fn unsupported_format_message(file_path: &str, formats: &[FormatInfo]) -> Option<String> {
+    let extension = Path::new(file_path).extension()?.to_string_lossy().to_lowercase();
+    if formats.iter().any(|format| format.claims_extension(&extension)) {
+        return None;
+    }
+
+    let mut supported: Vec<&str> = VIDEO_EXTENSIONS
+        .iter()
+        .copied()
+        .filter(|ext| formats.iter().any(|format| format.claims_extension(ext)))
+        .collect();
+    supported.sort_unstable();
+
+    Some(format!(
+        "The file format '.{}' is not supported by the bundled FFmpeg. Supported video formats are: {}",
+        extension,
+        supported.join(", ")
+    ))
+}
+
 #[command]
 pub async fn import_video_from_file(app: AppHandle, file_name: String, file_data: Vec<u8>) -> Result<VideoClip, String> {
     // Create a temporary file path
-    let temp_dir = std::env::temp_dir();
+    let temp_dir = app_temp_dir(&app);
     let temp_path = temp_dir.join(&file_name);
     
     // Write the file data to temporary location
@@ -37,7 +221,7 @@ pub async fn import_video_from_file(app: AppHandle, file_name: String, file_data
     
     // Import the video using the existing function
     let file_path = temp_path.to_string_lossy().to_string();
-    import_video(app, file_path).await
+    import_video(app, file_path, None).await
 }
 
 #[command]
@@ -57,6 +241,16 @@ pub async fn save_video(file_path: String, data: Vec<u8>) -> Result<String, Stri
 
 #[command]
 pub async fn read_file_bytes(file_path: String) -> Result<Vec<u8>, String> {
+    if let Ok(metadata) = std::fs::metadata(&file_path) {
+        if metadata.len() > LARGE_FILE_WARNING_BYTES {
+            log::warn!(
+                "read_file_bytes is loading {} ({} MB) into memory, use read_file_bytes_chunked instead",
+                file_path,
+                metadata.len() / (1024 * 1024)
+            );
+        }
+    }
+
     let data = std::fs::read(&file_path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
     
@@ -65,3 +259,1053 @@ pub async fn read_file_bytes(file_path: String) -> Result<Vec<u8>, String> {
     Ok(data)
 }
 
+
+#[command]
+pub async fn read_file_bytes_chunked(
+    _app: AppHandle,
+    file_path: String,
+    offset: u64,
+    length: usize,
+) -> Result<Vec<u8>, String> {
+    if length > MAX_CHUNK_BYTES {
+        return Err(format!("Chunk length cannot exceed {} bytes", MAX_CHUNK_BYTES));
+    }
+
+    let mut file = std::fs::File::open(&file_path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek in file: {}", e))?;
+
+    // Reads past the end simply return a shorter (or empty) chunk
+    let mut data = Vec::with_capacity(length);
+    file.take(length as u64)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    Ok(data)
+}
+
+#[command]
+pub async fn get_file_size(file_path: String) -> Result<u64, String> {
+    std::fs::metadata(&file_path)
+        .map(|metadata| metadata.len())
+        .map_err(|e| format!("Failed to read file metadata: {}", e))
+}
+
+/// Stream a file through `D` in 8 MB chunks, emitting progress every 100 MB
+async fn hash_file_streaming<D: Digest>(app: &AppHandle, file_path: &str, total_bytes: u64) -> Result<String, String> {
+    let mut file = tokio::fs::File::open(file_path)
+        .await
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; CHECKSUM_CHUNK_BYTES];
+    let mut bytes_read = 0u64;
+    let mut next_progress = CHECKSUM_PROGRESS_INTERVAL_BYTES;
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        bytes_read += read as u64;
+
+        if bytes_read >= next_progress {
+            next_progress += CHECKSUM_PROGRESS_INTERVAL_BYTES;
+            let progress = ChecksumProgress {
+                file_path: file_path.to_string(),
+                bytes_read,
+                total_bytes,
+            };
+            if let Err(e) = app.emit("clipforge://checksum-progress", progress) {
+                log::warn!("Failed to emit checksum progress: {}", e);
+            }
+        }
+    }
+
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+#[command]
+pub async fn get_file_checksum(
+    app: AppHandle,
+    state: State<'_, Mutex<ChecksumCache>>,
+    file_path: String,
+    algorithm: String,
+) -> Result<ChecksumResult, String> {
+    let algorithm = algorithm.to_lowercase();
+    if algorithm != "sha256" && algorithm != "sha1" {
+        return Err(format!("Unsupported checksum algorithm: {}. Available: sha256, sha1", algorithm));
+    }
+
+    let metadata = std::fs::metadata(&file_path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|age| age.as_nanos() as u64)
+        .unwrap_or(0);
+    let file_size_bytes = metadata.len();
+    let cache_key = format!("{}:{}", algorithm, file_path);
+
+    let cached = state
+        .lock()
+        .map_err(|e| format!("Failed to lock checksum cache: {}", e))?
+        .entries
+        .get(&cache_key)
+        .filter(|(cached_modified, _, _)| *cached_modified == modified)
+        .cloned();
+    if let Some((_, checksum, computed_at)) = cached {
+        return Ok(ChecksumResult { file_path, algorithm, checksum, file_size_bytes, computed_at });
+    }
+
+    let checksum = if algorithm == "sha1" {
+        hash_file_streaming::<sha1::Sha1>(&app, &file_path, file_size_bytes).await?
+    } else {
+        hash_file_streaming::<Sha256>(&app, &file_path, file_size_bytes).await?
+    };
+    let computed_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    state
+        .lock()
+        .map_err(|e| format!("Failed to lock checksum cache: {}", e))?
+        .entries
+        .insert(cache_key, (modified, checksum.clone(), computed_at));
+
+    Ok(ChecksumResult { file_path, algorithm, checksum, file_size_bytes, computed_at })
+}
+
+/// Time a synced 4 MB write into the destination's directory
+fn measure_write_speed(directory: &Path) -> Result<f64, String> {
+    let probe_path = directory.join(format!(".clipforge_write_probe_{}", Uuid::new_v4()));
+    let data = vec![0u8; WRITE_PROBE_BYTES];
+
+    let start = std::time::Instant::now();
+    let written = std::fs::File::create(&probe_path).and_then(|mut file| {
+        std::io::Write::write_all(&mut file, &data)?;
+        // Without a sync the write only measures the page cache
+        file.sync_all()
+    });
+    let elapsed = start.elapsed().as_secs_f64();
+    let _ = std::fs::remove_file(&probe_path);
+
+    written.map_err(|e| format!("Failed to write to {}: {}", directory.display(), e))?;
+    Ok(WRITE_PROBE_BYTES as f64 / (1024.0 * 1024.0) / elapsed.max(1e-6))
+}
+
+#[command]
+pub async fn estimate_write_speed(destination_path: String) -> Result<WriteSpeedEstimate, String> {
+    let destination = Path::new(&destination_path);
+    let directory = if destination.is_dir() {
+        destination.to_path_buf()
+    } else {
+        destination
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .ok_or_else(|| format!("No parent directory for {}", destination_path))?
+            .to_path_buf()
+    };
+    if !directory.is_dir() {
+        return Err(format!("Directory does not exist: {}", directory.display()));
+    }
+
+    // Blocking IO off the async runtime so scheduling does not skew the timing
+    let measured_mbps = tokio::task::spawn_blocking(move || measure_write_speed(&directory))
+        .await
+        .map_err(|e| format!("Write speed measurement failed: {}", e))??;
+
+    Ok(WriteSpeedEstimate { measured_mbps, path: destination_path })
+}
+
+/// Estimated seconds to write `file_size_bytes` to the destination's directory
+#[command]
+pub async fn estimate_transfer_time(destination_path: String, file_size_bytes: u64) -> Result<f64, String> {
+    let estimate = estimate_write_speed(destination_path).await?;
+    Ok(file_size_bytes as f64 / (estimate.measured_mbps * 1024.0 * 1024.0))
+}
+
+/// Bytes hashed from the start of each file when confirming a duplicate
+const DUPLICATE_HASH_BYTES: u64 = 1024 * 1024;
+
+/// SHA-256 of the first megabyte of a file, without reading the rest
+fn hash_file_head(file_path: &str) -> Result<String, String> {
+    let file = std::fs::File::open(file_path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let file_size = file
+        .metadata()
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+
+    // Files shorter than the limit are hashed whole so read_exact doesn't hit EOF
+    let mut buffer = vec![0u8; file_size.min(DUPLICATE_HASH_BYTES) as usize];
+    std::io::BufReader::new(file)
+        .read_exact(&mut buffer)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    Ok(format!("{:x}", Sha256::digest(&buffer)))
+}
+
+#[command]
+pub async fn detect_duplicate_clips(clips: Vec<VideoClip>) -> Result<Vec<DuplicateGroup>, String> {
+    // Cheap metadata fingerprint first, keeping groups in import order
+    let mut groups: Vec<(String, Vec<&VideoClip>)> = Vec::new();
+    for clip in &clips {
+        let fingerprint = format!(
+            "{}:{}:{}x{}:{}",
+            clip.metadata.file_size,
+            (clip.metadata.duration * 1000.0).round() as u64,
+            clip.metadata.width,
+            clip.metadata.height,
+            clip.metadata.format
+        );
+        match groups.iter_mut().find(|(existing, _)| *existing == fingerprint) {
+            Some((_, members)) => members.push(clip),
+            None => groups.push((fingerprint, vec![clip])),
+        }
+    }
+
+    let duplicates = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(fingerprint, members)| {
+            // Only matching fingerprints pay for reading file contents
+            let hashes: Vec<Option<String>> = members
+                .iter()
+                .map(|clip| hash_file_head(&clip.file_path).ok())
+                .collect();
+            let confirmed_by_hash = hashes[0].is_some() && hashes.iter().all(|hash| *hash == hashes[0]);
+
+            DuplicateGroup {
+                fingerprint,
+                clips: members.iter().map(|clip| clip.id.clone()).collect(),
+                confirmed_by_hash,
+            }
+        })
+        .collect();
+
+    Ok(duplicates)
+}
+
+#[command]
+pub async fn import_video_from_url(app: AppHandle, url: String, filename: Option<String>) -> Result<VideoClip, String> {
+    let parsed_url = reqwest::Url::parse(&url)
+        .map_err(|e| format!("Invalid URL: {}", e))?;
+
+    if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
+        return Err(format!("Unsupported URL scheme '{}'. Only http and https are allowed", parsed_url.scheme()));
+    }
+
+    // Use the requested name, then the last URL path segment, then a timestamp
+    let file_name = filename
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| {
+            parsed_url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| urlencoding::decode(segment).map(|s| s.into_owned()).unwrap_or_else(|_| segment.to_string()))
+        })
+        .unwrap_or_else(|| {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            format!("download_{}.mp4", timestamp)
+        });
+    let file_name = Path::new(&file_name)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or("Invalid file name")?;
+
+    let cache_dir = app_temp_dir(&app).join("clipforge_imports");
+    std::fs::create_dir_all(&cache_dir)
+        .map_err(|e| format!("Failed to create import cache directory: {}", e))?;
+
+    // Key the cache on the URL so the same link is only downloaded once
+    let url_hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let cached_path = cache_dir.join(format!("{}_{}", url_hash, file_name));
+
+    if !cached_path.exists() {
+        println!("Downloading {} to {:?}", url, cached_path);
+
+        let mut response = reqwest::Client::new()
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download video: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to download video: HTTP {}", response.status()));
+        }
+
+        // Stream into a partial file and rename once complete so an interrupted
+        // download is never picked up as a cache hit
+        let partial_path = cached_path.with_extension("part");
+        let mut file = tokio::fs::File::create(&partial_path)
+            .await
+            .map_err(|e| format!("Failed to create download file: {}", e))?;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read download: {}", e))?
+        {
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| format!("Failed to write download file: {}", e))?;
+        }
+        file.flush()
+            .await
+            .map_err(|e| format!("Failed to write download file: {}", e))?;
+
+        tokio::fs::rename(&partial_path, &cached_path)
+            .await
+            .map_err(|e| format!("Failed to finalize download: {}", e))?;
+    } else {
+        println!("Using cached download for {}: {:?}", url, cached_path);
+    }
+
+    import_video(app, cached_path.to_string_lossy().to_string(), None).await
+}
+
+#[command]
+pub async fn clear_url_cache(app: AppHandle) -> Result<String, String> {
+    let cache_dir = app_temp_dir(&app).join("clipforge_imports");
+    if cache_dir.exists() {
+        std::fs::remove_dir_all(&cache_dir)
+            .map_err(|e| format!("Failed to clear URL cache: {}", e))?;
+    }
+
+    Ok("URL cache cleared".to_string())
+}
+
+#[command]
+pub async fn check_file_integrity(app: AppHandle, file_path: String) -> Result<IntegrityReport, String> {
+    if !Path::new(&file_path).exists() {
+        return Err("File does not exist".to_string());
+    }
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    // Decode the whole file and collect anything the decoder complains about
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let decode_output = Command::new(ffmpeg_path)
+        .args(["-v", "error", "-i", &file_path, "-f", "null", "-"])
+        .output()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    let stderr = String::from_utf8_lossy(&decode_output.stderr);
+    for line in stderr.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if CORRUPTION_ERRORS.iter().any(|pattern| line.contains(pattern)) {
+            if !errors.iter().any(|e: &String| e == line) {
+                errors.push(line.to_string());
+            }
+        } else if CORRUPTION_WARNINGS.iter().any(|pattern| line.contains(pattern))
+            && !warnings.iter().any(|w: &String| w == line)
+        {
+            warnings.push(line.to_string());
+        }
+    }
+    if !decode_output.status.success() && errors.is_empty() {
+        errors.push(format!("Decoding failed: {}", stderr.lines().last().unwrap_or("unknown error")));
+    }
+
+    // Compare the packet counts declared in the header with what can actually be read
+    let ffprobe_path = get_ffprobe_path(&app)?;
+    let probe_output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-count_packets",
+            "-show_entries", "stream=codec_type,nb_frames,nb_read_packets",
+            "-of", "csv=p=0",
+            &file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    let mut video_packet_count = None;
+    let mut audio_packet_count = None;
+    if probe_output.status.success() {
+        for line in String::from_utf8_lossy(&probe_output.stdout).lines() {
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if fields.len() < 3 {
+                continue;
+            }
+            let declared = fields[1].parse::<u64>().ok();
+            let actual = fields[2].parse::<u64>().ok();
+
+            match fields[0] {
+                "video" if video_packet_count.is_none() => video_packet_count = actual,
+                "audio" if audio_packet_count.is_none() => audio_packet_count = actual,
+                _ => {}
+            }
+
+            if let (Some(declared), Some(actual)) = (declared, actual) {
+                if declared != actual {
+                    warnings.push(format!(
+                        "{} stream declares {} packets but {} could be read",
+                        fields[0], declared, actual
+                    ));
+                }
+            }
+        }
+    } else {
+        errors.push(format!("ffprobe failed: {}", String::from_utf8_lossy(&probe_output.stderr).trim()));
+    }
+
+    let valid = errors.is_empty();
+    // Remuxing can recover a damaged file as long as some packets are still readable
+    let can_be_repaired = !valid && video_packet_count.is_some_and(|count| count > 0);
+
+    Ok(IntegrityReport {
+        valid,
+        errors,
+        warnings,
+        video_packet_count,
+        audio_packet_count,
+        can_be_repaired,
+    })
+}
+
+// On X11 and Wayland the clipboard contents are served by the process that set them, so the owner is kept for the session
+#[cfg(target_os = "linux")]
+static CLIPBOARD_OWNER: std::sync::Mutex<Option<arboard::Clipboard>> = std::sync::Mutex::new(None);
+
+fn with_clipboard<T>(write: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>) -> Result<T, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut owner = CLIPBOARD_OWNER.lock().map_err(|e| format!("Clipboard lock poisoned: {}", e))?;
+        if owner.is_none() {
+            *owner = Some(arboard::Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?);
+        }
+        let clipboard = owner.as_mut().expect("clipboard owner was just set");
+        write(clipboard).map_err(|e| format!("Failed to write to clipboard: {}", e))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| format!("Failed to access clipboard: {}", e))?;
+        write(&mut clipboard).map_err(|e| format!("Failed to write to clipboard: {}", e))
+    }
+}
+
+fn write_text_to_clipboard(text: &str) -> Result<(), String> {
+    with_clipboard(|clipboard| clipboard.set_text(text.to_string()))
+}
+
+#[command]
+pub async fn copy_file_path_to_clipboard(_app: AppHandle, file_path: String) -> Result<(), String> {
+    if !Path::new(&file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    write_text_to_clipboard(&file_path)
+}
+
+#[command]
+pub async fn copy_file_to_clipboard(_app: AppHandle, file_path: String) -> Result<(), String> {
+    let path = Path::new(&file_path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    // On macOS put the file itself on the pasteboard so it can be pasted into Finder or Mail
+    #[cfg(target_os = "macos")]
+    {
+        let absolute_path = path
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve path: {}", e))?;
+        match with_clipboard(|clipboard| clipboard.set().file_list(&[&absolute_path])) {
+            Ok(()) => return Ok(()),
+            Err(e) => log::warn!("Failed to copy file to pasteboard, falling back to path text: {}", e),
+        }
+    }
+
+    write_text_to_clipboard(&path.to_string_lossy())
+}
+
+/// Files referenced on the clipboard, either as copied files or as pasted path text
+fn read_clipboard_paths(clipboard: &mut arboard::Clipboard) -> Vec<std::path::PathBuf> {
+    // Finder and Explorer copies arrive as NSFilenamesPboardType / CF_HDROP file lists
+    if let Ok(files) = clipboard.get().file_list() {
+        let existing: Vec<_> = files.into_iter().filter(|path| path.exists()).collect();
+        if !existing.is_empty() {
+            return existing;
+        }
+    }
+
+    clipboard
+        .get_text()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().trim_matches('"'))
+        .map(|line| line.strip_prefix("file://").unwrap_or(line))
+        .filter(|line| !line.is_empty())
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Whether ffprobe finds a real video stream, for files without a known extension
+fn probe_has_video_stream(app: &AppHandle, path: &Path) -> bool {
+    let Ok(ffprobe) = get_ffprobe_path(app) else {
+        return false;
+    };
+    Command::new(ffprobe)
+        .args(["-v", "error", "-select_streams", "v:0", "-show_entries", "stream=codec_name", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .map(|output| output.status.success() && !String::from_utf8_lossy(&output.stdout).trim().is_empty())
+        .unwrap_or(false)
+}
+
+#[command]
+pub async fn import_from_clipboard(app: AppHandle) -> Result<ImportResult, String> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| format!("Failed to access clipboard: {}", e))?;
+
+    for path in read_clipboard_paths(&mut clipboard) {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            return Ok(ImportResult::Image { path: path.to_string_lossy().to_string() });
+        }
+        if VIDEO_EXTENSIONS.contains(&extension.as_str()) || probe_has_video_stream(&app, &path) {
+            let clip = import_video(app, path.to_string_lossy().to_string(), None).await?;
+            return Ok(ImportResult::Video { clip: Box::new(clip) });
+        }
+    }
+
+    // Screenshots are copied as raw bitmaps, so save them to a file the timeline can use
+    if let Ok(bitmap) = clipboard.get_image() {
+        let buffer = image::RgbaImage::from_raw(bitmap.width as u32, bitmap.height as u32, bitmap.bytes.into_owned())
+            .ok_or_else(|| "Clipboard image has an invalid size".to_string())?;
+        let temp_dir = app_temp_dir(&app).join("clipforge_clipboard");
+        std::fs::create_dir_all(&temp_dir)
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+        let image_path = temp_dir.join(format!("clipboard_{}.png", Uuid::new_v4()));
+        buffer
+            .save(&image_path)
+            .map_err(|e| format!("Failed to save clipboard image: {}", e))?;
+        return Ok(ImportResult::Image { path: image_path.to_string_lossy().to_string() });
+    }
+
+    Err("Clipboard does not contain a video file, image file or image".to_string())
+}
+
+/// Format seconds as an `HH:MM:SS:FF` non-drop timecode
+fn seconds_to_timecode(seconds: f64) -> String {
+    let total_frames = (seconds.max(0.0) * EDL_FPS as f64).round() as u64;
+    let frames = total_frames % EDL_FPS;
+    let total_seconds = total_frames / EDL_FPS;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        frames
+    )
+}
+
+/// Parse an `HH:MM:SS:FF` timecode (`;` drop-frame separators accepted) into seconds
+fn timecode_to_seconds(timecode: &str) -> Option<f64> {
+    let parts: Vec<u64> = timecode
+        .split([':', ';'])
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [hours, minutes, seconds, frames] if *frames < EDL_FPS => {
+            Some((((hours * 60 + minutes) * 60 + seconds) * EDL_FPS + frames) as f64 / EDL_FPS as f64)
+        }
+        _ => None,
+    }
+}
+
+/// Reel name for a source file: its stem with spaces replaced, since EDL fields are space separated
+fn edl_reel_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().replace(char::is_whitespace, "_"))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "AX".to_string())
+}
+
+#[command]
+pub async fn export_edl(clips: Vec<VideoClip>, project_name: String, output_path: String) -> Result<String, String> {
+    if clips.is_empty() {
+        return Err("No clips to export".to_string());
+    }
+
+    let mut sorted_clips = clips;
+    sorted_clips.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut edl = format!("TITLE: {}\nFCM: NON-DROP FRAME\n\n", project_name);
+    for (index, clip) in sorted_clips.iter().enumerate() {
+        let source = Path::new(&clip.file_path);
+        let record_out = clip.start_time + (clip.trim_out - clip.trim_in);
+        edl.push_str(&format!(
+            "{:03}  {:<8} V     C        {} {} {} {}\n",
+            index + 1,
+            edl_reel_name(source),
+            seconds_to_timecode(clip.trim_in),
+            seconds_to_timecode(clip.trim_out),
+            seconds_to_timecode(clip.start_time),
+            seconds_to_timecode(record_out)
+        ));
+        if let Some(file_name) = source.file_name() {
+            edl.push_str(&format!("* FROM CLIP NAME: {}\n", file_name.to_string_lossy()));
+        }
+        edl.push('\n');
+    }
+
+    std::fs::write(&output_path, edl)
+        .map_err(|e| format!("Failed to write EDL file: {}", e))?;
+
+    Ok(output_path)
+}
+
+struct EdlEvent {
+    reel: String,
+    clip_name: Option<String>,
+    source_in: f64,
+    source_out: f64,
+    record_in: f64,
+}
+
+/// Pull the video events out of a CMX 3600 EDL
+fn parse_edl_events(contents: &str) -> Vec<EdlEvent> {
+    let mut events: Vec<EdlEvent> = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("* FROM CLIP NAME:") {
+            if let Some(event) = events.last_mut() {
+                event.clip_name = Some(name.trim().to_string());
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 8 || fields[0].parse::<u32>().is_err() || !fields[2].contains('V') {
+            continue;
+        }
+        // Dissolves and wipes carry an extra duration field, so read the timecodes from the end
+        let timecodes: Option<Vec<f64>> = fields[fields.len() - 4..]
+            .iter()
+            .map(|field| timecode_to_seconds(field))
+            .collect();
+        if let Some([source_in, source_out, record_in, _]) = timecodes.as_deref() {
+            events.push(EdlEvent {
+                reel: fields[1].to_string(),
+                clip_name: None,
+                source_in: *source_in,
+                source_out: *source_out,
+                record_in: *record_in,
+            });
+        }
+    }
+    events
+}
+
+/// Find the media file for an event, by clip name first and then by matching the reel to a file stem
+fn resolve_edl_media(event: &EdlEvent, media_root: &Path) -> Option<std::path::PathBuf> {
+    if let Some(name) = &event.clip_name {
+        let candidate = media_root.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    std::fs::read_dir(media_root)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_file() && edl_reel_name(path).eq_ignore_ascii_case(&event.reel))
+}
+
+#[command]
+pub async fn import_edl(app: AppHandle, edl_path: String, media_root_dir: String) -> Result<Vec<VideoClip>, String> {
+    let contents = std::fs::read_to_string(&edl_path)
+        .map_err(|e| format!("Failed to read EDL file: {}", e))?;
+    let media_root = Path::new(&media_root_dir);
+    if !media_root.is_dir() {
+        return Err(format!("Media directory not found: {}", media_root_dir));
+    }
+
+    let events = parse_edl_events(&contents);
+    if events.is_empty() {
+        return Err("EDL file contains no video events".to_string());
+    }
+
+    let mut clips = Vec::with_capacity(events.len());
+    for event in events {
+        let media_path = resolve_edl_media(&event, media_root)
+            .ok_or_else(|| format!("No media found in {} for reel {}", media_root_dir, event.reel))?;
+        let file_path = media_path.to_string_lossy().to_string();
+        let metadata = crate::commands::ffmpeg::get_video_metadata(app.clone(), file_path.clone(), None).await?;
+
+        clips.push(VideoClip {
+            id: Uuid::new_v4().to_string(),
+            file_path,
+            metadata,
+            start_time: event.record_in,
+            end_time: event.record_in + (event.source_out - event.source_in),
+            trim_in: event.source_in,
+            trim_out: event.source_out,
+            volume: 1.0,
+            filters: Vec::new(),
+        });
+    }
+
+    Ok(clips)
+}
+
+/// Flattened `media/` name for a clip source: its stem plus a hash of the full path so same-named files never collide
+fn bundle_media_name(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| "clip".to_string());
+    let hash = format!("{:x}", Sha256::digest(file_path.as_bytes()));
+    match path.extension() {
+        Some(ext) => format!("{}_{}.{}", stem, &hash[..8], ext.to_string_lossy()),
+        None => format!("{}_{}", stem, &hash[..8]),
+    }
+}
+
+fn write_project_bundle(clips: Vec<VideoClip>, project_name: &str, output_path: &str) -> Result<BundleResult, String> {
+    let archive = std::fs::File::create(output_path)
+        .map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut writer = zip::ZipWriter::new(archive);
+    writer.set_comment(project_name);
+
+    let mut bundled_clips = Vec::with_capacity(clips.len());
+    let mut packed = std::collections::HashSet::new();
+    let mut total_size_bytes = 0;
+    for mut clip in clips {
+        let entry_name = format!("media/{}", bundle_media_name(&clip.file_path));
+
+        // Clips cut from the same source share one copy
+        if packed.insert(entry_name.clone()) {
+            let mut source = std::fs::File::open(&clip.file_path)
+                .map_err(|e| format!("Failed to open {}: {}", clip.file_path, e))?;
+            let size = source.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            // Video is already compressed, so store it as-is
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored)
+                .large_file(size >= u32::MAX as u64);
+            writer
+                .start_file(entry_name.as_str(), options)
+                .map_err(|e| format!("Failed to add {} to bundle: {}", clip.file_path, e))?;
+            std::io::copy(&mut source, &mut writer)
+                .map_err(|e| format!("Failed to add {} to bundle: {}", clip.file_path, e))?;
+            total_size_bytes += size;
+        }
+
+        clip.file_path = entry_name;
+        bundled_clips.push(clip);
+    }
+
+    let project_json = serde_json::to_vec_pretty(&bundled_clips)
+        .map_err(|e| format!("Failed to serialize project: {}", e))?;
+    writer
+        .start_file("project.json", zip::write::SimpleFileOptions::default())
+        .map_err(|e| format!("Failed to write project.json: {}", e))?;
+    std::io::Write::write_all(&mut writer, &project_json)
+        .map_err(|e| format!("Failed to write project.json: {}", e))?;
+    writer.finish().map_err(|e| format!("Failed to finish bundle: {}", e))?;
+
+    Ok(BundleResult {
+        output_path: output_path.to_string(),
+        file_count: packed.len(),
+        total_size_bytes,
+    })
+}
+
+/// Zip the clip list as `project.json` with every referenced file under `media/`
+#[command]
+pub async fn export_project_bundle(clips: Vec<VideoClip>, project_name: String, output_path: String) -> Result<BundleResult, String> {
+    if clips.is_empty() {
+        return Err("No clips to bundle".to_string());
+    }
+    if let Some(missing) = clips.iter().find(|clip| !Path::new(&clip.file_path).exists()) {
+        return Err(format!("File not found: {}", missing.file_path));
+    }
+
+    let bundle_path = output_path.clone();
+    let result = tokio::task::spawn_blocking(move || write_project_bundle(clips, &project_name, &bundle_path))
+        .await
+        .map_err(|e| format!("Bundle export failed: {}", e))?;
+
+    // Don't leave a half-written archive behind
+    if result.is_err() {
+        let _ = std::fs::remove_file(&output_path);
+    }
+    result
+}
+
+fn extract_project_bundle(bundle_path: &str, extract_dir: &Path) -> Result<Vec<VideoClip>, String> {
+    let archive = std::fs::File::open(bundle_path)
+        .map_err(|e| format!("Failed to open bundle: {}", e))?;
+    let mut archive = zip::ZipArchive::new(archive)
+        .map_err(|e| format!("Not a valid project bundle: {}", e))?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read bundle entry: {}", e))?;
+        // enclosed_name rejects entries that would escape the extract directory
+        let Some(relative_path) = entry.enclosed_name() else {
+            return Err(format!("Bundle contains an unsafe path: {}", entry.name()));
+        };
+        let target = extract_dir.join(relative_path);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mut file = std::fs::File::create(&target)
+            .map_err(|e| format!("Failed to extract {}: {}", entry.name(), e))?;
+        std::io::copy(&mut entry, &mut file)
+            .map_err(|e| format!("Failed to extract {}: {}", entry.name(), e))?;
+    }
+
+    let project_json = std::fs::read(extract_dir.join("project.json"))
+        .map_err(|_| "Bundle does not contain a project.json".to_string())?;
+    let mut clips: Vec<VideoClip> = serde_json::from_slice(&project_json)
+        .map_err(|e| format!("Failed to parse project.json: {}", e))?;
+
+    for clip in &mut clips {
+        // project.json is as untrusted as the entries, so its paths must stay inside the bundle too
+        let relative_path = Path::new(&clip.file_path);
+        let enclosed = relative_path
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_) | std::path::Component::CurDir));
+        if !enclosed {
+            return Err(format!("Bundle references an unsafe media path: {}", clip.file_path));
+        }
+        clip.file_path = extract_dir.join(relative_path).to_string_lossy().to_string();
+    }
+    Ok(clips)
+}
+
+/// Unpack a bundle from `export_project_bundle`, returning its clips pointed at the extracted media
+#[command]
+pub async fn import_project_bundle(bundle_path: String, extract_dir: String) -> Result<Vec<VideoClip>, String> {
+    let extract_dir = std::path::PathBuf::from(extract_dir);
+    std::fs::create_dir_all(&extract_dir)
+        .map_err(|e| format!("Failed to create extract directory: {}", e))?;
+
+    tokio::task::spawn_blocking(move || extract_project_bundle(&bundle_path, &extract_dir))
+        .await
+        .map_err(|e| format!("Bundle import failed: {}", e))?
+}
+
+/// Recursively collect (path, size, age in seconds) for every file under `dir`
+fn collect_temp_files(dir: &Path, files: &mut Vec<(std::path::PathBuf, u64, u64)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            collect_temp_files(&path, files);
+        } else {
+            let age_seconds = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0);
+            files.push((path, metadata.len(), age_seconds));
+        }
+    }
+}
+
+/// Every file under the app's temp dir, which holds all intermediate processing output
+fn managed_temp_files(app: &AppHandle) -> Vec<(std::path::PathBuf, u64, u64)> {
+    let mut files = Vec::new();
+    collect_temp_files(&app_temp_dir(app), &mut files);
+    files
+}
+
+#[command]
+pub async fn get_temp_directory_stats(app: AppHandle) -> Result<TempDirStats, String> {
+    let files = managed_temp_files(&app);
+
+    Ok(TempDirStats {
+        path: app_temp_dir(&app).to_string_lossy().to_string(),
+        used_bytes: files.iter().map(|(_, size, _)| size).sum(),
+        file_count: files.len(),
+        oldest_file_age_seconds: files.iter().map(|(_, _, age)| *age).max().unwrap_or(0),
+    })
+}
+
+#[command]
+pub async fn cleanup_temp_files(app: AppHandle, max_age_hours: u64) -> Result<CleanupResult, String> {
+    let max_age_seconds = max_age_hours.saturating_mul(3600);
+    let mut files_removed = 0;
+    let mut bytes_freed = 0;
+
+    for (path, size, age_seconds) in managed_temp_files(&app) {
+        if age_seconds < max_age_seconds {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                files_removed += 1;
+                bytes_freed += size;
+            }
+            Err(e) => log::warn!("Failed to remove temp file {}: {}", path.display(), e),
+        }
+    }
+
+    println!("Temp cleanup removed {} files ({} bytes)", files_removed, bytes_freed);
+
+    Ok(CleanupResult {
+        files_removed,
+        bytes_freed,
+    })
+}
+
+/// List fonts through fontconfig, returning None when fc-list is unavailable
+fn list_fonts_with_fc_list() -> Option<Vec<FontInfo>> {
+    let output = Command::new("fc-list")
+        .args(["--format", "%{family[0]}|%{style[0]}|%{file}\\n"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let fonts: Vec<FontInfo> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '|');
+            let family = parts.next()?.trim();
+            let style = parts.next()?.trim();
+            let path = parts.next()?.trim();
+            if family.is_empty() || path.is_empty() {
+                return None;
+            }
+            Some(FontInfo {
+                family: family.to_string(),
+                style: if style.is_empty() { "Regular".to_string() } else { style.to_string() },
+                path: path.to_string(),
+            })
+        })
+        .collect();
+
+    if fonts.is_empty() {
+        None
+    } else {
+        Some(fonts)
+    }
+}
+
+/// Collect font files from a directory, deriving family and style from names like "Family-Bold.ttf"
+fn list_fonts_in_directory(dir: &Path, fonts: &mut Vec<FontInfo>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_fonts_in_directory(&path, fonts);
+            continue;
+        }
+
+        let is_font = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext.to_lowercase().as_str(), "ttf" | "otf" | "ttc"))
+            .unwrap_or(false);
+        if !is_font {
+            continue;
+        }
+
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let (family, style) = stem.split_once('-').unwrap_or((stem, "Regular"));
+        fonts.push(FontInfo {
+            family: family.to_string(),
+            style: style.to_string(),
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+}
+
+fn enumerate_system_fonts() -> Vec<FontInfo> {
+    if cfg!(target_os = "windows") {
+        let mut fonts = Vec::new();
+        list_fonts_in_directory(Path::new("C:\\Windows\\Fonts"), &mut fonts);
+        return fonts;
+    }
+
+    if let Some(fonts) = list_fonts_with_fc_list() {
+        return fonts;
+    }
+
+    // macOS without fontconfig installed
+    let mut fonts = Vec::new();
+    list_fonts_in_directory(Path::new("/System/Library/Fonts"), &mut fonts);
+    list_fonts_in_directory(Path::new("/Library/Fonts"), &mut fonts);
+    if let Some(home) = dirs::home_dir() {
+        list_fonts_in_directory(&home.join("Library/Fonts"), &mut fonts);
+    }
+    fonts
+}
+
+#[command]
+pub async fn get_available_fonts() -> Result<Vec<FontInfo>, String> {
+    Ok(FONT_CACHE.get_or_init(enumerate_system_fonts).clone())
+}
+
+#[command]
+pub async fn set_temp_directory(app: AppHandle, path: String) -> Result<(), String> {
+    let dir = Path::new(&path);
+    if !dir.is_dir() {
+        return Err(format!("Directory does not exist: {}", path));
+    }
+
+    let probe_file = dir.join(format!(".clipforge_write_test_{}", std::process::id()));
+    std::fs::write(&probe_file, b"").map_err(|e| format!("Directory is not writable: {}", e))?;
+    let _ = std::fs::remove_file(&probe_file);
+
+    match available_disk_space(dir) {
+        Some(available) if available < MIN_TEMP_DIR_FREE_BYTES => {
+            return Err(format!(
+                "Temp directory needs at least 10 GB free, but only {:.1} GB is available",
+                available as f64 / (1024.0 * 1024.0 * 1024.0)
+            ));
+        }
+        Some(_) => {}
+        None => log::warn!("Could not determine free disk space for {}", path),
+    }
+
+    let state = app.state::<std::sync::Mutex<ClipForgeConfig>>();
+    let mut config = state
+        .lock()
+        .map_err(|e| format!("Failed to update settings: {}", e))?;
+    config.temp_directory = Some(dir.to_path_buf());
+    config.save()
+}
@@ -0,0 +1,122 @@
+use tauri::{command, AppHandle, Emitter, State};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Oldest entries are dropped once the buffer holds this many
+const MAX_LOG_ENTRIES: usize = 1000;
+
+thread_local! {
+    // Set while an entry is being emitted, so logging done by the emit itself is not re-captured
+    static EMITTING_LOG_ENTRY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Payload of the `clipforge://log-entry` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub level: String,
+    pub module: String,
+    pub message: String,
+}
+
+/// In-memory ring buffer behind the `log` facade, held as managed state
+#[derive(Clone, Default)]
+pub struct ClipForgeLogger {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    app: Arc<OnceLock<AppHandle>>,
+}
+
+impl ClipForgeLogger {
+    /// Register as the global `log` logger; only the first call in a process takes effect
+    pub fn install(&self) {
+        if log::set_boxed_logger(Box::new(self.clone())).is_ok() {
+            log::set_max_level(log::LevelFilter::Debug);
+        }
+    }
+
+    /// Start emitting `clipforge://log-entry` events once the app handle exists
+    pub fn attach(&self, app: AppHandle) {
+        let _ = self.app.set(app);
+    }
+}
+
+impl log::Log for ClipForgeLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        // Only this crate's records; dependencies such as reqwest and hyper log heavily at debug
+        let own_target = metadata.target().split("::").next() == Some(env!("CARGO_CRATE_NAME"));
+        own_target && metadata.level() <= log::Level::Debug
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) || EMITTING_LOG_ENTRY.with(|flag| flag.get()) {
+            return;
+        }
+
+        let entry = LogEntry {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            level: record.level().as_str().to_lowercase(),
+            module: record.module_path().unwrap_or(record.target()).to_string(),
+            message: record.args().to_string(),
+        };
+        eprintln!("[{}] {}: {}", entry.level, entry.module, entry.message);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= MAX_LOG_ENTRIES {
+                entries.pop_front();
+            }
+            entries.push_back(entry.clone());
+        }
+
+        if let Some(app) = self.app.get() {
+            EMITTING_LOG_ENTRY.with(|flag| flag.set(true));
+            let _ = app.emit("clipforge://log-entry", entry);
+            EMITTING_LOG_ENTRY.with(|flag| flag.set(false));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[command]
+pub async fn get_clipforge_logs(
+    state: State<'_, ClipForgeLogger>,
+    level_filter: Option<String>,
+) -> Result<Vec<LogEntry>, String> {
+    // A level filter keeps that level and everything more severe
+    let max_level = match level_filter.as_deref() {
+        None => log::Level::Debug,
+        Some(level) => match level.to_lowercase().as_str() {
+            "error" => log::Level::Error,
+            "warn" => log::Level::Warn,
+            "info" => log::Level::Info,
+            "debug" => log::Level::Debug,
+            _ => return Err(format!("Unknown log level: {}. Use error, warn, info or debug", level)),
+        },
+    };
+
+    let entries = state
+        .entries
+        .lock()
+        .map_err(|e| format!("Failed to read logs: {}", e))?;
+    Ok(entries
+        .iter()
+        .filter(|entry| entry.level.parse::<log::Level>().is_ok_and(|level| level <= max_level))
+        .cloned()
+        .collect())
+}
+
+#[command]
+pub async fn clear_logs(state: State<'_, ClipForgeLogger>) -> Result<(), String> {
+    state
+        .entries
+        .lock()
+        .map_err(|e| format!("Failed to clear logs: {}", e))?
+        .clear();
+    Ok(())
+}
@@ -7,8 +7,15 @@ pub mod video_upscaler;
 pub mod character_extractor;
 pub mod ai_styler;
 pub mod binary_utils;
+pub mod subtitles;
+pub mod operations;
+pub mod logging;
+// Helpers shared by the filter-building tests
+#[cfg(test)]
+mod test_support;
 
 use serde::{Deserialize, Serialize};
+use tauri::command;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VideoMetadata {
@@ -18,6 +25,15 @@ pub struct VideoMetadata {
     pub fps: f64,
     pub file_size: u64,
     pub format: String,
+    // Codec details default so metadata built by the frontend still deserializes
+    #[serde(default)]
+    pub codec_name: String,
+    #[serde(default)]
+    pub codec_long_name: String,
+    #[serde(default)]
+    pub codec_profile: Option<String>,
+    #[serde(default)]
+    pub bit_rate_kbps: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -29,6 +45,327 @@ pub struct VideoClip {
     pub end_time: f64,
     pub trim_in: f64,
     pub trim_out: f64,
+    #[serde(default = "default_clip_volume")]
+    pub volume: f64,
+    /// Filter ids from the AI styler's FILTERS list
+    #[serde(default)]
+    pub filters: Vec<String>,
+}
+
+fn default_clip_volume() -> f64 {
+    1.0
+}
+
+/// Reusable trim, volume and filter settings; trims are stored relative to the clip's ends
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipTemplate {
+    pub id: String,
+    pub name: String,
+    pub trim_in_offset: f64,
+    pub trim_out_offset_from_end: f64,
+    pub volume: f64,
+    pub filters: Vec<String>,
+    pub created_at: u64,
+}
+
+// Serializes read-modify-write cycles on the templates file
+static TEMPLATE_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// A gap (positive duration) or overlap (negative duration) between two adjacent clips
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimelineGap {
+    pub after_clip_id: String,
+    pub before_clip_id: String,
+    pub gap_start: f64,
+    pub gap_end: f64,
+    pub duration: f64,
+}
+
+#[command]
+pub async fn get_timeline_gaps(clips: Vec<VideoClip>) -> Result<Vec<TimelineGap>, String> {
+    if let Some(clip) = clips.iter().find(|clip| clip.start_time > clip.end_time) {
+        return Err(format!(
+            "Clip {} starts after it ends ({}s > {}s)",
+            clip.id, clip.start_time, clip.end_time
+        ));
+    }
+
+    let mut sorted_clips = clips;
+    sorted_clips.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+
+    let gaps = sorted_clips
+        .windows(2)
+        // Ignore floating point noise between clips that are meant to be back to back
+        .filter(|pair| (pair[1].start_time - pair[0].end_time).abs() > 1e-6)
+        .map(|pair| TimelineGap {
+            after_clip_id: pair[0].id.clone(),
+            before_clip_id: pair[1].id.clone(),
+            gap_start: pair[0].end_time,
+            gap_end: pair[1].start_time,
+            duration: pair[1].start_time - pair[0].end_time,
+        })
+        .collect();
+
+    Ok(gaps)
+}
+
+/// Clips that sit close together on the timeline, treated as one chapter
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipGroup {
+    pub id: String,
+    pub clips: Vec<VideoClip>,
+    pub total_duration: f64,
+    pub label: Option<String>,
+}
+
+fn clip_duration(clip: &VideoClip) -> f64 {
+    clip.end_time - clip.start_time
+}
+
+/// Group clips whose gap to the previous clip is at most `gap_threshold_seconds`; overlaps always group
+pub fn group_clips_by_proximity(clips: Vec<VideoClip>, gap_threshold_seconds: f64) -> Vec<ClipGroup> {
+    let mut sorted_clips = clips;
+    sorted_clips.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let mut grouped: Vec<Vec<VideoClip>> = Vec::new();
+    let mut group_end = f64::NEG_INFINITY;
+    for clip in sorted_clips {
+        match grouped.last_mut() {
+            Some(group) if clip.start_time - group_end <= gap_threshold_seconds => {
+                group_end = group_end.max(clip.end_time);
+                group.push(clip);
+            }
+            _ => {
+                group_end = clip.end_time;
+                grouped.push(vec![clip]);
+            }
+        }
+    }
+
+    grouped
+        .into_iter()
+        .enumerate()
+        .map(|(i, clips)| ClipGroup {
+            id: format!("chapter_{}", i + 1),
+            total_duration: clips.iter().map(clip_duration).sum(),
+            label: Some(format!("Chapter {}", i + 1)),
+            clips,
+        })
+        .collect()
+}
+
+#[command]
+pub async fn auto_chapter_clips(clips: Vec<VideoClip>, gap_threshold_seconds: f64) -> Result<Vec<ClipGroup>, String> {
+    if gap_threshold_seconds < 0.0 {
+        return Err("Gap threshold cannot be negative".to_string());
+    }
+    Ok(group_clips_by_proximity(clips, gap_threshold_seconds))
+}
+
+#[command]
+pub async fn sort_clips_by(clips: Vec<VideoClip>, field: String, ascending: bool) -> Result<Vec<VideoClip>, String> {
+    let file_name = |clip: &VideoClip| {
+        std::path::Path::new(&clip.file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+
+    let mut sorted_clips = clips;
+    match field.as_str() {
+        "start_time" => sorted_clips.sort_by(|a, b| a.start_time.total_cmp(&b.start_time)),
+        "duration" => sorted_clips.sort_by(|a, b| clip_duration(a).total_cmp(&clip_duration(b))),
+        "file_name" => sorted_clips.sort_by_key(file_name),
+        "file_size" => sorted_clips.sort_by_key(|clip| clip.metadata.file_size),
+        _ => {
+            return Err(format!(
+                "Unknown sort field: {}. Use start_time, duration, file_name or file_size",
+                field
+            ))
+        }
+    }
+
+    if !ascending {
+        sorted_clips.reverse();
+    }
+
+    Ok(sorted_clips)
+}
+
+fn templates_path() -> Result<std::path::PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Failed to get data directory")?
+        .join("clipforge");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    Ok(dir.join("templates.json"))
+}
+
+fn load_templates() -> Result<Vec<ClipTemplate>, String> {
+    let path = templates_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read templates: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse templates: {}", e))
+}
+
+fn save_templates(templates: &[ClipTemplate]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize templates: {}", e))?;
+    std::fs::write(templates_path()?, content).map_err(|e| format!("Failed to write templates: {}", e))
+}
+
+#[command]
+pub async fn save_clip_as_template(clip: VideoClip, name: String) -> Result<ClipTemplate, String> {
+    if name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+
+    let source_duration = if clip.metadata.duration > 0.0 { clip.metadata.duration } else { clip.trim_out };
+    let template = ClipTemplate {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.trim().to_string(),
+        trim_in_offset: clip.trim_in.max(0.0),
+        trim_out_offset_from_end: (source_duration - clip.trim_out).max(0.0),
+        volume: clip.volume,
+        filters: clip.filters,
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+
+    let _guard = TEMPLATE_FILE_LOCK.lock().map_err(|e| format!("Failed to lock templates: {}", e))?;
+    let mut templates = load_templates()?;
+    templates.push(template.clone());
+    save_templates(&templates)?;
+
+    Ok(template)
+}
+
+#[command]
+pub async fn create_clip_from_template(
+    app: tauri::AppHandle,
+    file_path: String,
+    template_id: String,
+) -> Result<VideoClip, String> {
+    let template = {
+        let _guard = TEMPLATE_FILE_LOCK.lock().map_err(|e| format!("Failed to lock templates: {}", e))?;
+        load_templates()?
+            .into_iter()
+            .find(|template| template.id == template_id)
+            .ok_or_else(|| format!("Template not found: {}", template_id))?
+    };
+
+    let mut clip = filesystem::import_video(app, file_path, None).await?;
+    let duration = clip.metadata.duration;
+    if template.trim_in_offset + template.trim_out_offset_from_end >= duration {
+        return Err(format!(
+            "Template trims {:.2}s from a {:.2}s clip, leaving nothing",
+            template.trim_in_offset + template.trim_out_offset_from_end,
+            duration
+        ));
+    }
+
+    clip.trim_in = template.trim_in_offset;
+    clip.trim_out = duration - template.trim_out_offset_from_end;
+    clip.end_time = clip.start_time + (clip.trim_out - clip.trim_in);
+    clip.volume = template.volume;
+    clip.filters = template.filters;
+
+    Ok(clip)
+}
+
+#[command]
+pub async fn list_templates() -> Result<Vec<ClipTemplate>, String> {
+    let _guard = TEMPLATE_FILE_LOCK.lock().map_err(|e| format!("Failed to lock templates: {}", e))?;
+    let mut templates = load_templates()?;
+    templates.sort_by_key(|template| template.created_at);
+    Ok(templates)
+}
+
+#[command]
+pub async fn delete_template(id: String) -> Result<(), String> {
+    let _guard = TEMPLATE_FILE_LOCK.lock().map_err(|e| format!("Failed to lock templates: {}", e))?;
+    let mut templates = load_templates()?;
+    let count = templates.len();
+    templates.retain(|template| template.id != id);
+    if templates.len() == count {
+        return Err(format!("Template not found: {}", id));
+    }
+    save_templates(&templates)
 }
 
 // This function is no longer needed in Tauri 2.0
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(id: &str, start_time: f64, end_time: f64) -> VideoClip {
+        VideoClip {
+            id: id.to_string(),
+            file_path: format!("{}.mp4", id),
+            metadata: VideoMetadata {
+                duration: end_time - start_time,
+                width: 1920,
+                height: 1080,
+                fps: 30.0,
+                file_size: 0,
+                format: "mp4".to_string(),
+                codec_name: String::new(),
+                codec_long_name: String::new(),
+                codec_profile: None,
+                bit_rate_kbps: 0,
+            },
+            start_time,
+            end_time,
+            trim_in: 0.0,
+            trim_out: end_time - start_time,
+            volume: 1.0,
+            filters: Vec::new(),
+        }
+    }
+
+    fn group_ids(groups: &[ClipGroup]) -> Vec<Vec<&str>> {
+        groups
+            .iter()
+            .map(|group| group.clips.iter().map(|clip| clip.id.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn groups_clips_separated_by_at_most_the_threshold() {
+        let clips = vec![
+            clip("c", 12.0, 15.0),
+            clip("a", 0.0, 4.0),
+            clip("b", 5.0, 10.0),
+            clip("d", 15.5, 20.0),
+        ];
+        let groups = group_clips_by_proximity(clips, 1.0);
+
+        assert_eq!(group_ids(&groups), vec![vec!["a", "b"], vec!["c", "d"]]);
+        assert_eq!(groups[0].id, "chapter_1");
+        assert_eq!(groups[0].total_duration, 9.0);
+        assert_eq!(groups[1].label.as_deref(), Some("Chapter 2"));
+    }
+
+    #[test]
+    fn overlapping_clips_group_against_the_furthest_end() {
+        // "b" ends inside "a", so the gap to "c" is measured from the end of "a"
+        let clips = vec![clip("a", 0.0, 10.0), clip("b", 2.0, 3.0), clip("c", 10.5, 12.0)];
+        let groups = group_clips_by_proximity(clips, 1.0);
+
+        assert_eq!(group_ids(&groups), vec![vec!["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn nan_start_times_sort_without_panicking() {
+        let clips = vec![clip("a", 4.0, 5.0), clip("nan", f64::NAN, 1.0), clip("b", 1.0, 2.0)];
+        let groups = group_clips_by_proximity(clips, 0.0);
+
+        assert_eq!(groups.iter().map(|group| group.clips.len()).sum::<usize>(), 3);
+    }
+}
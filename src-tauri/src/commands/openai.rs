@@ -1,6 +1,38 @@
-use tauri::command;
+use tauri::{command, AppHandle, Manager, State};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::commands::binary_utils::{app_temp_dir, get_ffmpeg_path};
+use crate::commands::subtitles::{format_srt, SubtitleEntry};
+
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+const COPYRIGHT_SCAN_PROMPT: &str = "List any visible trademarked logos, brand names, copyrighted characters, or recognizable IP in this image. Return JSON: [{item, confidence, timestamp_seconds}] or []";
+
+// Minimum gap between GPT-4o requests during a scan
+const VISION_REQUEST_GAP: Duration = Duration::from_millis(500);
+
+const TEXT_EXTRACTION_PROMPT: &str = "Extract all visible text from this frame. Return JSON: {text: string, regions: [{x, y, width, height, text}]}";
+
+const SPEAKER_COMPARISON_PROMPT: &str = "You will hear two short audio clips. Are they spoken by the same person? Compare voice pitch, timbre and accent only, not the words. Reply with exactly SAME or DIFFERENT.";
+
+const WHISPER_MAX_ATTEMPTS: u32 = 3;
+
+// Longest Retry-After worth waiting out; anything beyond it is returned to the caller
+const WHISPER_MAX_RETRY_AFTER_SECONDS: u64 = 60;
+
+// Whisper rejects uploads over 25 MB; chunks are cut to fit under it with some headroom
+const WHISPER_MAX_UPLOAD_BYTES: u64 = 25 * 1024 * 1024;
+
+// 16 kHz mono 16-bit PCM, as extracted for transcribe_audio
+const TRANSCRIPTION_WAV_BYTES_PER_SECOND: u64 = 16_000 * 2;
+
+// Audio shared between consecutive chunks so words at a cut are heard whole by one of them
+const TRANSCRIPTION_CHUNK_OVERLAP_SECONDS: f64 = 5.0;
+
+// Known speakers compared against before a new one is created
+const MAX_TRACKED_SPEAKERS: usize = 6;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct OpenAIImageRequest {
@@ -24,14 +56,279 @@ struct OpenAIImageData {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct OpenAIError {
+struct OpenAIErrorBody {
     error: OpenAIErrorDetail,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct OpenAIErrorDetail {
+    #[serde(default)]
     message: String,
-    r#type: String,
+    #[serde(default)]
+    r#type: Option<String>,
+    #[serde(default)]
+    code: Option<String>,
+}
+
+/// Typed OpenAI failure; reaches the frontend as `{ kind, message, retry_after_seconds }`
+#[derive(Debug, Clone)]
+pub enum OpenAiError {
+    RateLimited { retry_after_seconds: Option<u64> },
+    InvalidApiKey,
+    InsufficientQuota,
+    ContentPolicyViolation { message: String },
+    InvalidRequest { message: String },
+    NetworkError(String),
+    UnexpectedResponse(String),
+    /// A local step around the request failed, such as FFmpeg or a file read or write
+    Local(String),
+}
+
+impl OpenAiError {
+    fn kind(&self) -> &'static str {
+        match self {
+            OpenAiError::RateLimited { .. } => "rate_limited",
+            OpenAiError::InvalidApiKey => "invalid_api_key",
+            OpenAiError::InsufficientQuota => "insufficient_quota",
+            OpenAiError::ContentPolicyViolation { .. } => "content_policy_violation",
+            OpenAiError::InvalidRequest { .. } => "invalid_request",
+            OpenAiError::NetworkError(_) => "network_error",
+            OpenAiError::UnexpectedResponse(_) => "unexpected_response",
+            OpenAiError::Local(_) => "local_error",
+        }
+    }
+
+    /// Worth retrying the same request later
+    fn is_transient(&self) -> bool {
+        matches!(self, OpenAiError::RateLimited { .. } | OpenAiError::NetworkError(_))
+    }
+
+    /// Classify a non-success response from its status, error body and Retry-After header
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after_seconds = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        let body = response.text().await.unwrap_or_default();
+
+        let detail = serde_json::from_str::<OpenAIErrorBody>(&body).ok().map(|body| body.error);
+        let code = detail.as_ref().and_then(|d| d.code.clone()).unwrap_or_default();
+        let error_type = detail.as_ref().and_then(|d| d.r#type.clone()).unwrap_or_default();
+        let message = detail
+            .map(|d| d.message)
+            .filter(|message| !message.is_empty())
+            .unwrap_or_else(|| if body.is_empty() { status.to_string() } else { body });
+
+        // Quota exhaustion also comes back as 429, so check the code before the status
+        if code == "insufficient_quota" || error_type == "insufficient_quota" {
+            OpenAiError::InsufficientQuota
+        } else if code == "invalid_api_key" || status == reqwest::StatusCode::UNAUTHORIZED {
+            OpenAiError::InvalidApiKey
+        } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            OpenAiError::RateLimited { retry_after_seconds }
+        } else if code == "content_policy_violation" || error_type == "content_policy_violation" {
+            OpenAiError::ContentPolicyViolation { message }
+        } else if status.is_client_error() {
+            OpenAiError::InvalidRequest { message }
+        } else {
+            OpenAiError::UnexpectedResponse(message)
+        }
+    }
+}
+
+impl std::fmt::Display for OpenAiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenAiError::RateLimited { retry_after_seconds: Some(seconds) } => {
+                write!(f, "OpenAI rate limit reached, retry in {} seconds", seconds)
+            }
+            OpenAiError::RateLimited { retry_after_seconds: None } => write!(f, "OpenAI rate limit reached, try again shortly"),
+            OpenAiError::InvalidApiKey => write!(f, "OpenAI rejected the API key"),
+            OpenAiError::InsufficientQuota => write!(f, "OpenAI account has no remaining quota"),
+            OpenAiError::ContentPolicyViolation { message } => write!(f, "Request blocked by OpenAI content policy: {}", message),
+            OpenAiError::InvalidRequest { message } => write!(f, "Invalid OpenAI request: {}", message),
+            OpenAiError::NetworkError(message) => write!(f, "Failed to reach OpenAI: {}", message),
+            OpenAiError::UnexpectedResponse(message) => write!(f, "OpenAI API error: {}", message),
+            OpenAiError::Local(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Serialize for OpenAiError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let retry_after_seconds = match self {
+            OpenAiError::RateLimited { retry_after_seconds } => *retry_after_seconds,
+            _ => None,
+        };
+        let mut state = serializer.serialize_struct("OpenAiError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("retry_after_seconds", &retry_after_seconds)?;
+        state.end()
+    }
+}
+
+// Commands that still return String errors can `?` a typed OpenAI error
+impl From<OpenAiError> for String {
+    fn from(error: OpenAiError) -> Self {
+        error.to_string()
+    }
+}
+
+// Lets commands returning OpenAiError `?` the String errors of local helpers
+impl From<String> for OpenAiError {
+    fn from(message: String) -> Self {
+        OpenAiError::Local(message)
+    }
+}
+
+/// Endpoint settings for OpenAI or an OpenAI-compatible server, held as managed state
+#[derive(Debug, Clone)]
+pub struct OpenAiConfig {
+    pub base_url: String,
+    pub api_key: String,
+    pub api_version: Option<String>,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_OPENAI_BASE_URL.to_string(),
+            api_key: env::var("OPENAI_API_KEY").unwrap_or_default(),
+            api_version: None,
+        }
+    }
+}
+
+impl OpenAiConfig {
+    /// Full URL for an API path such as "chat/completions", with Azure's api-version appended
+    pub fn endpoint(&self, path: &str) -> String {
+        let url = format!("{}/{}", self.base_url.trim_end_matches('/'), path.trim_start_matches('/'));
+        match &self.api_version {
+            Some(version) => format!("{}?api-version={}", url, version),
+            None => url,
+        }
+    }
+
+    /// Prefer the key passed to a command, falling back to the configured one
+    pub fn resolve_key(&self, api_key: &str) -> String {
+        if api_key.is_empty() {
+            self.api_key.clone()
+        } else {
+            api_key.to_string()
+        }
+    }
+}
+
+/// OpenAI settings safe to hand to the frontend
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAiConfigPublic {
+    pub base_url: String,
+    pub api_version: Option<String>,
+    pub has_api_key: bool,
+}
+
+fn lock_config(state: &Mutex<OpenAiConfig>) -> Result<OpenAiConfig, String> {
+    state
+        .lock()
+        .map(|config| config.clone())
+        .map_err(|e| format!("Failed to read OpenAI config: {}", e))
+}
+
+pub(crate) fn app_openai_config(app: &AppHandle) -> Result<OpenAiConfig, String> {
+    lock_config(app.state::<Mutex<OpenAiConfig>>().inner())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyrightFlag {
+    pub timestamp: f64,
+    pub item: String,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyrightScanResult {
+    pub flags: Vec<CopyrightFlag>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TextRegion {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoTextEntry {
+    pub timestamp: f64,
+    pub full_text: String,
+    pub regions: Vec<TextRegion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OnscreenTextEntry {
+    pub timestamp: f64,
+    pub text: String,
+    /// 0.0 - 1.0 from tesseract; GPT-4o does not report one
+    pub confidence: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubtitleGenerationResult {
+    pub srt_path: String,
+    pub speaker_count: Option<usize>,
+    pub total_words: usize,
+    pub language_detected: String,
+}
+
+/// Point OpenAI requests at a different base URL, e.g. Azure OpenAI or a local server
+#[command]
+pub async fn configure_openai(
+    state: State<'_, Mutex<OpenAiConfig>>,
+    base_url: String,
+    api_key: String,
+    api_version: Option<String>,
+) -> Result<(), String> {
+    let base_url = base_url.trim().trim_end_matches('/').to_string();
+    if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+        return Err(format!("Base URL must start with http:// or https://: {}", base_url));
+    }
+
+    let api_version = api_version
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty());
+
+    let mut config = state
+        .lock()
+        .map_err(|e| format!("Failed to update OpenAI config: {}", e))?;
+    *config = OpenAiConfig { base_url, api_key, api_version };
+
+    Ok(())
+}
+
+/// Get the current OpenAI endpoint settings without the API key
+#[command]
+pub async fn get_openai_config(state: State<'_, Mutex<OpenAiConfig>>) -> Result<OpenAiConfigPublic, String> {
+    let config = lock_config(state.inner())?;
+    Ok(OpenAiConfigPublic {
+        base_url: config.base_url,
+        api_version: config.api_version,
+        has_api_key: !config.api_key.is_empty(),
+    })
 }
 
 /// Get OpenAI API key from environment variable
@@ -45,7 +342,13 @@ pub async fn get_openai_api_key() -> Result<String, String> {
 
 /// Generate an image using DALL-E 3
 #[command]
-pub async fn generate_dalle_image(prompt: String, api_key: String) -> Result<Vec<u8>, String> {
+pub async fn generate_dalle_image(
+    state: State<'_, Mutex<OpenAiConfig>>,
+    prompt: String,
+    api_key: String,
+) -> Result<Vec<u8>, OpenAiError> {
+    let config = lock_config(state.inner()).map_err(OpenAiError::UnexpectedResponse)?;
+    let api_key = config.resolve_key(&api_key);
     let client = reqwest::Client::new();
     
     let request_body = OpenAIImageRequest {
@@ -58,60 +361,220 @@ pub async fn generate_dalle_image(prompt: String, api_key: String) -> Result<Vec
     };
 
     let response = client
-        .post("https://api.openai.com/v1/images/generations")
+        .post(config.endpoint("images/generations"))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
+        .map_err(|e| OpenAiError::NetworkError(e.to_string()))?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("OpenAI API error: {}", error_text));
+        return Err(OpenAiError::from_response(response).await);
     }
 
     let image_response: OpenAIImageResponse = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| OpenAiError::UnexpectedResponse(format!("Failed to parse response: {}", e)))?;
 
     if let Some(image_data) = image_response.data.first() {
         if let Some(b64_data) = &image_data.b64_json {
             let image_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, b64_data)
-                .map_err(|e| format!("Failed to decode base64 image: {}", e))?;
+                .map_err(|e| OpenAiError::UnexpectedResponse(format!("Failed to decode base64 image: {}", e)))?;
             Ok(image_bytes)
         } else {
-            Err("No base64 image data in response".to_string())
+            Err(OpenAiError::UnexpectedResponse("No base64 image data in response".to_string()))
         }
     } else {
-        Err("No image data in response".to_string())
+        Err(OpenAiError::UnexpectedResponse("No image data in response".to_string()))
     }
 }
 
 /// Apply style transfer to an image using DALL-E 3 variations
 #[command]
 pub async fn style_transfer_image(
+    state: State<'_, Mutex<OpenAiConfig>>,
     _image_path: String,
     style_prompt: String,
     api_key: String,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, OpenAiError> {
     // For now, we'll use the style prompt to generate a new image
     // In a more sophisticated implementation, we would upload the image
     // and use DALL-E 3's image editing capabilities
     
     let enhanced_prompt = format!("Apply this style to the image: {}", style_prompt);
-    generate_dalle_image(enhanced_prompt, api_key).await
+    generate_dalle_image(state, enhanced_prompt, api_key).await
+}
+
+/// Encode an image as PNG bytes for multipart upload
+fn encode_png(image: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut bytes, image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(bytes.into_inner())
+}
+
+/// The edits API inpaints transparent pixels, so a mask without transparency is treated as
+/// black-means-replace and its luminance becomes the alpha channel
+fn mask_to_alpha(mask: &image::DynamicImage) -> image::RgbaImage {
+    let mut rgba = mask.to_rgba8();
+    if rgba.pixels().all(|pixel| pixel[3] == 255) {
+        for pixel in rgba.pixels_mut() {
+            let [r, g, b, _] = pixel.0;
+            let luma = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) as u8;
+            pixel.0 = [255, 255, 255, if luma < 128 { 0 } else { 255 }];
+        }
+    }
+    rgba
+}
+
+/// Fill the masked regions of a frame with OpenAI image edits and save it at the frame's size
+#[command]
+pub async fn inpaint_frame(
+    app: AppHandle,
+    api_key: String,
+    frame_path: String,
+    mask_path: String,
+    prompt: String,
+    output_path: String,
+) -> Result<String, String> {
+    let frame = image::open(&frame_path)
+        .map_err(|e| format!("Failed to open frame: {}", e))?;
+    let mask = image::open(&mask_path)
+        .map_err(|e| format!("Failed to open mask: {}", e))?;
+
+    let (width, height) = (frame.width(), frame.height());
+    if (mask.width(), mask.height()) != (width, height) {
+        return Err(format!(
+            "Mask is {}x{} but the frame is {}x{}",
+            mask.width(),
+            mask.height(),
+            width,
+            height
+        ));
+    }
+
+    let frame_png = encode_png(&image::DynamicImage::ImageRgba8(frame.to_rgba8()))?;
+    let mask_png = encode_png(&image::DynamicImage::ImageRgba8(mask_to_alpha(&mask)))?;
+
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let png_part = |bytes: Vec<u8>, name: &str| {
+        reqwest::multipart::Part::bytes(bytes)
+            .file_name(name.to_string())
+            .mime_str("image/png")
+            .map_err(|e| format!("Failed to create image part: {}", e))
+    };
+    // gpt-image-1 accepts non-square frames, unlike dall-e-2
+    let form = reqwest::multipart::Form::new()
+        .text("model", "gpt-image-1")
+        .text("prompt", prompt)
+        .text("size", "auto")
+        .part("image", png_part(frame_png, "frame.png")?)
+        .part("mask", png_part(mask_png, "mask.png")?);
+
+    let response = reqwest::Client::new()
+        .post(config.endpoint("images/edits"))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| OpenAiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(OpenAiError::from_response(response).await.into());
+    }
+
+    let image_response: OpenAIImageResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let b64_data = image_response
+        .data
+        .first()
+        .and_then(|image_data| image_data.b64_json.as_ref())
+        .ok_or("No base64 image data in response")?;
+    let image_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, b64_data)
+        .map_err(|e| format!("Failed to decode base64 image: {}", e))?;
+
+    // The API answers at its own size; scale back so the frame drops into the video unchanged
+    let inpainted = image::load_from_memory(&image_bytes)
+        .map_err(|e| format!("Failed to decode inpainted image: {}", e))?;
+    inpainted
+        .resize_exact(width, height, image::imageops::FilterType::Lanczos3)
+        .save(&output_path)
+        .map_err(|e| format!("Failed to save inpainted frame: {}", e))?;
+
+    Ok(output_path)
+}
+
+/// Build a `width`x`height` PNG mask that is opaque white except for a transparent black box to inpaint
+#[allow(clippy::too_many_arguments)]
+#[command]
+pub async fn generate_rectangular_mask(
+    app: AppHandle,
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+    mask_w: u32,
+    mask_h: u32,
+    output_path: String,
+) -> Result<String, String> {
+    if width == 0 || height == 0 || mask_w == 0 || mask_h == 0 {
+        return Err("Mask dimensions must be greater than zero".to_string());
+    }
+    if x + mask_w > width || y + mask_h > height {
+        return Err(format!(
+            "Mask box {}x{} at ({}, {}) does not fit in {}x{}",
+            mask_w, mask_h, x, y, width, height
+        ));
+    }
+
+    // replace=1 writes the box's zero alpha instead of blending it over the white
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = tokio::process::Command::new(ffmpeg_path)
+        .arg("-f")
+        .arg("lavfi")
+        .arg("-i")
+        .arg(format!("color=c=white:s={}x{}", width, height))
+        .arg("-vf")
+        .arg(format!(
+            "format=rgba,drawbox=x={}:y={}:w={}:h={}:color=black@0.0:t=fill:replace=1",
+            x, y, mask_w, mask_h
+        ))
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-pix_fmt")
+        .arg("rgba")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(output_path)
 }
 
 /// Validate OpenAI API key by making a test request
 #[command]
-pub async fn validate_openai_key(api_key: String) -> Result<bool, String> {
+pub async fn validate_openai_key(
+    state: State<'_, Mutex<OpenAiConfig>>,
+    api_key: String,
+) -> Result<bool, String> {
+    let config = lock_config(state.inner())?;
+    let api_key = config.resolve_key(&api_key);
     let client = reqwest::Client::new();
     
     // Make a simple request to test the key
     let response = client
-        .get("https://api.openai.com/v1/models")
+        .get(config.endpoint("models"))
         .header("Authorization", format!("Bearer {}", api_key))
         .send()
         .await
@@ -119,3 +582,1018 @@ pub async fn validate_openai_key(api_key: String) -> Result<bool, String> {
 
     Ok(response.status().is_success())
 }
+
+/// Parse the JSON array GPT-4o returns for a frame, tolerating markdown code fences
+fn parse_copyright_flags(content: &str, timestamp: f64) -> Vec<CopyrightFlag> {
+    let (Some(start), Some(end)) = (content.find('['), content.rfind(']')) else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+
+    let items: Vec<serde_json::Value> = serde_json::from_str(&content[start..=end]).unwrap_or_default();
+    items
+        .iter()
+        .filter_map(|entry| {
+            let item = entry["item"].as_str()?.trim().to_string();
+            if item.is_empty() {
+                return None;
+            }
+            // Confidence sometimes comes back as a string or a percentage
+            let confidence = entry["confidence"]
+                .as_f64()
+                .or_else(|| entry["confidence"].as_str().and_then(|c| c.trim_end_matches('%').parse().ok()))
+                .map(|c| if c > 1.0 { c / 100.0 } else { c })
+                .unwrap_or(0.0);
+            Some(CopyrightFlag { timestamp, item, confidence })
+        })
+        .collect()
+}
+
+/// Sample frames from a video and ask GPT-4o Vision to flag logos, brands or characters
+#[command]
+pub async fn scan_for_copyright_content(
+    app: AppHandle,
+    input_path: String,
+    api_key: String,
+    sample_interval_seconds: f64,
+) -> Result<CopyrightScanResult, String> {
+    if sample_interval_seconds <= 0.0 {
+        return Err("Sample interval must be greater than zero".to_string());
+    }
+
+    let frames_dir = app_temp_dir(&app)
+        .join("clipforge_copyright_scan")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&frames_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let result = scan_frames_for_copyright(&app, &config, &frames_dir, &input_path, &api_key, sample_interval_seconds).await;
+
+    if let Err(e) = std::fs::remove_dir_all(&frames_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result
+}
+
+async fn scan_frames_for_copyright(
+    app: &AppHandle,
+    config: &OpenAiConfig,
+    frames_dir: &std::path::Path,
+    input_path: &str,
+    api_key: &str,
+    sample_interval_seconds: f64,
+) -> Result<CopyrightScanResult, String> {
+    // Extract one frame per interval
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = tokio::process::Command::new(ffmpeg_path)
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vf")
+        .arg(format!("fps=1/{}", sample_interval_seconds))
+        .arg("-q:v")
+        .arg("3")
+        .arg(frames_dir.join("frame_%05d.jpg"))
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let mut frame_paths: Vec<_> = std::fs::read_dir(frames_dir)
+        .map_err(|e| format!("Failed to read extracted frames: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    frame_paths.sort();
+
+    println!("Scanning {} frames for copyrighted content", frame_paths.len());
+
+    let client = reqwest::Client::new();
+    let mut flags = Vec::new();
+    let mut last_request: Option<Instant> = None;
+
+    for (i, frame_path) in frame_paths.iter().enumerate() {
+        let timestamp = i as f64 * sample_interval_seconds;
+
+        if let Some(last) = last_request {
+            let elapsed = last.elapsed();
+            if elapsed < VISION_REQUEST_GAP {
+                tokio::time::sleep(VISION_REQUEST_GAP - elapsed).await;
+            }
+        }
+
+        let frame_bytes = std::fs::read(frame_path)
+            .map_err(|e| format!("Failed to read frame: {}", e))?;
+        let base64_frame = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &frame_bytes);
+
+        last_request = Some(Instant::now());
+        let response = client
+            .post(config.endpoint("chat/completions"))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({
+                "model": "gpt-4o",
+                "messages": [
+                    {
+                        "role": "user",
+                        "content": [
+                            { "type": "text", "text": COPYRIGHT_SCAN_PROMPT },
+                            {
+                                "type": "image_url",
+                                "image_url": {
+                                    "url": format!("data:image/jpeg;base64,{}", base64_frame),
+                                    "detail": "low"
+                                }
+                            }
+                        ]
+                    }
+                ],
+                "max_tokens": 500,
+                "temperature": 0.1
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call OpenAI API: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("OpenAI API error: {}", error_text));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+        let content = response_json["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or("");
+
+        let frame_flags = parse_copyright_flags(content, timestamp);
+        if !frame_flags.is_empty() {
+            println!("Frame at {}s flagged {} items", timestamp, frame_flags.len());
+        }
+        flags.extend(frame_flags);
+    }
+
+    let message = format!(
+        "Found {} potential copyright flags in {} sampled frames. \
+This is an automated estimate and not legal advice; review flagged content yourself before publishing.",
+        flags.len(),
+        frame_paths.len()
+    );
+
+    Ok(CopyrightScanResult { flags, message })
+}
+
+/// Transcribe audio with Whisper, retrying with exponential backoff since long files can time out
+async fn transcribe_with_retry(
+    client: &reqwest::Client,
+    config: &OpenAiConfig,
+    api_key: &str,
+    audio_bytes: &[u8],
+    file_name: &str,
+    language: Option<&str>,
+) -> Result<serde_json::Value, OpenAiError> {
+    let mut last_error = OpenAiError::UnexpectedResponse("No transcription attempts were made".to_string());
+
+    for attempt in 0..WHISPER_MAX_ATTEMPTS {
+        if attempt > 0 {
+            // Honour Retry-After when the rate limiter gives one, unless it is too long to block on
+            let backoff = match &last_error {
+                OpenAiError::RateLimited { retry_after_seconds: Some(seconds) } => {
+                    if *seconds > WHISPER_MAX_RETRY_AFTER_SECONDS {
+                        return Err(last_error);
+                    }
+                    Duration::from_secs(*seconds)
+                }
+                _ => Duration::from_secs(1 << (attempt - 1)),
+            };
+            log::warn!("Whisper attempt {} failed, retrying in {:?}: {}", attempt, backoff, last_error);
+            tokio::time::sleep(backoff).await;
+        }
+
+        let mime = if file_name.ends_with(".wav") { "audio/wav" } else { "audio/mpeg" };
+        let audio_part = reqwest::multipart::Part::bytes(audio_bytes.to_vec())
+            .file_name(file_name.to_string())
+            .mime_str(mime)
+            .map_err(|e| OpenAiError::InvalidRequest { message: format!("Failed to create audio part: {}", e) })?;
+        let mut form = reqwest::multipart::Form::new()
+            .text("model", "whisper-1")
+            .text("response_format", "verbose_json")
+            .part("file", audio_part);
+        if let Some(language) = language {
+            form = form.text("language", language.to_string());
+        }
+
+        let response = match client
+            .post(config.endpoint("audio/transcriptions"))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(form)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                last_error = OpenAiError::NetworkError(format!("Failed to call Whisper API: {}", e));
+                continue;
+            }
+        };
+
+        if !response.status().is_success() {
+            last_error = OpenAiError::from_response(response).await;
+            // Errors such as a bad key will not succeed on retry
+            if !last_error.is_transient() {
+                return Err(last_error);
+            }
+            continue;
+        }
+
+        return response
+            .json()
+            .await
+            .map_err(|e| OpenAiError::UnexpectedResponse(format!("Failed to parse Whisper response: {}", e)));
+    }
+
+    Err(last_error)
+}
+
+/// Cut a segment out of the extracted audio and return it base64-encoded
+async fn encode_audio_chunk(
+    ffmpeg_path: &std::path::Path,
+    audio_path: &std::path::Path,
+    chunk_path: &std::path::Path,
+    start: f64,
+    end: f64,
+) -> Result<String, String> {
+    let output = tokio::process::Command::new(ffmpeg_path)
+        .arg("-y")
+        .arg("-ss")
+        .arg(start.to_string())
+        .arg("-t")
+        .arg((end - start).max(0.1).to_string())
+        .arg("-i")
+        .arg(audio_path)
+        .arg("-c:a")
+        .arg("libmp3lame")
+        .arg(chunk_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let bytes = std::fs::read(chunk_path).map_err(|e| format!("Failed to read audio chunk: {}", e))?;
+    Ok(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes))
+}
+
+/// Ask GPT-4o whether two audio clips share a speaker
+async fn is_same_speaker(
+    client: &reqwest::Client,
+    config: &OpenAiConfig,
+    api_key: &str,
+    first_clip: &str,
+    second_clip: &str,
+) -> Result<bool, OpenAiError> {
+    let response = client
+        .post(config.endpoint("chat/completions"))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({
+            "model": "gpt-4o-audio-preview",
+            "modalities": ["text"],
+            "messages": [
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": SPEAKER_COMPARISON_PROMPT },
+                        { "type": "input_audio", "input_audio": { "data": first_clip, "format": "mp3" } },
+                        { "type": "input_audio", "input_audio": { "data": second_clip, "format": "mp3" } }
+                    ]
+                }
+            ],
+            "max_tokens": 5,
+            "temperature": 0
+        }))
+        .send()
+        .await
+        .map_err(|e| OpenAiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(OpenAiError::from_response(response).await);
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| OpenAiError::UnexpectedResponse(format!("Failed to parse OpenAI response: {}", e)))?;
+    let answer = response_json["choices"][0]["message"]["content"]
+        .as_str()
+        .unwrap_or("")
+        .to_uppercase();
+
+    Ok(!answer.contains("DIFFERENT"))
+}
+
+/// Transcribe a video with Whisper into an SRT file, optionally labelling speakers with GPT-4o
+#[command]
+pub async fn generate_subtitles_from_video(
+    app: AppHandle,
+    input_path: String,
+    api_key: String,
+    output_srt_path: String,
+    include_speaker_labels: bool,
+) -> Result<SubtitleGenerationResult, OpenAiError> {
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_subtitles")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let result = generate_subtitles_in(&app, &config, &work_dir, &input_path, &api_key, &output_srt_path, include_speaker_labels).await;
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result
+}
+
+async fn generate_subtitles_in(
+    app: &AppHandle,
+    config: &OpenAiConfig,
+    work_dir: &std::path::Path,
+    input_path: &str,
+    api_key: &str,
+    output_srt_path: &str,
+    include_speaker_labels: bool,
+) -> Result<SubtitleGenerationResult, OpenAiError> {
+    // Mono 16 kHz MP3 keeps long recordings under Whisper's upload limit
+    let audio_path = work_dir.join("audio.mp3");
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = tokio::process::Command::new(&ffmpeg_path)
+        .arg("-y")
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vn")
+        .arg("-ac")
+        .arg("1")
+        .arg("-ar")
+        .arg("16000")
+        .arg("-c:a")
+        .arg("libmp3lame")
+        .arg("-b:a")
+        .arg("64k")
+        .arg(&audio_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to extract audio: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let audio_bytes = std::fs::read(&audio_path).map_err(|e| format!("Failed to read extracted audio: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let transcription = transcribe_with_retry(&client, config, api_key, &audio_bytes, "audio.mp3", None).await?;
+
+    let language_detected = transcription["language"].as_str().unwrap_or("unknown").to_string();
+    let segments: Vec<(f64, f64, String)> = parse_whisper_segments(&transcription, 0.0)
+        .into_iter()
+        .map(|segment| (segment.start, segment.end, segment.text))
+        .collect();
+
+    let total_words = segments.iter().map(|(_, _, text)| text.split_whitespace().count()).sum();
+
+    // Speaker index per segment, compared first against the previous segment then known speakers
+    let mut speaker_ids: Vec<usize> = Vec::with_capacity(segments.len());
+    let mut speaker_count = None;
+    if include_speaker_labels && !segments.is_empty() {
+        let mut speaker_references: Vec<String> = Vec::new();
+        let mut previous_clip: Option<String> = None;
+
+        for (i, (start, end, _)) in segments.iter().enumerate() {
+            let chunk_path = work_dir.join(format!("segment_{:04}.mp3", i));
+            let clip = encode_audio_chunk(&ffmpeg_path, &audio_path, &chunk_path, *start, *end).await?;
+
+            let speaker = match (&previous_clip, speaker_ids.last()) {
+                (Some(previous), Some(&previous_speaker)) => {
+                    if is_same_speaker(&client, config, api_key, previous, &clip).await? {
+                        previous_speaker
+                    } else {
+                        let mut matched = None;
+                        for (id, reference) in speaker_references.iter().enumerate() {
+                            if id != previous_speaker && is_same_speaker(&client, config, api_key, reference, &clip).await? {
+                                matched = Some(id);
+                                break;
+                            }
+                        }
+                        match matched {
+                            Some(id) => id,
+                            None if speaker_references.len() < MAX_TRACKED_SPEAKERS => {
+                                speaker_references.push(clip.clone());
+                                speaker_references.len() - 1
+                            }
+                            None => previous_speaker,
+                        }
+                    }
+                }
+                _ => {
+                    speaker_references.push(clip.clone());
+                    0
+                }
+            };
+
+            speaker_ids.push(speaker);
+            previous_clip = Some(clip);
+        }
+
+        speaker_count = Some(speaker_references.len());
+    }
+
+    let entries: Vec<SubtitleEntry> = segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, end, text))| SubtitleEntry {
+            index: i + 1,
+            start,
+            end,
+            text: match speaker_ids.get(i) {
+                Some(speaker) => format!("[Speaker {}] {}", speaker + 1, text),
+                None => text,
+            },
+        })
+        .collect();
+
+    std::fs::write(output_srt_path, format_srt(&entries))
+        .map_err(|e| format!("Failed to write subtitles: {}", e))?;
+
+    Ok(SubtitleGenerationResult {
+        srt_path: output_srt_path.to_string(),
+        speaker_count,
+        total_words,
+        language_detected,
+    })
+}
+
+/// Non-empty segments of a verbose_json transcription, shifted by `offset` seconds
+fn parse_whisper_segments(transcription: &serde_json::Value, offset: f64) -> Vec<TranscriptSegment> {
+    transcription["segments"]
+        .as_array()
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|segment| {
+                    let text = segment["text"].as_str()?.trim().to_string();
+                    if text.is_empty() {
+                        return None;
+                    }
+                    Some(TranscriptSegment {
+                        start: segment["start"].as_f64()? + offset,
+                        end: segment["end"].as_f64()? + offset,
+                        text,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Append a chunk's segments, splitting the overlap at `cut`: earlier segments come from the previous chunk, later ones from this one
+fn merge_chunk_segments(merged: &mut Vec<TranscriptSegment>, chunk: Vec<TranscriptSegment>, cut: f64) {
+    merged.retain(|segment| segment.start < cut);
+    for segment in chunk.into_iter().filter(|segment| segment.start >= cut) {
+        // A sentence straddling the cut can be heard by both chunks
+        let repeated = merged.last().is_some_and(|previous| {
+            previous.end > segment.start && previous.text.eq_ignore_ascii_case(&segment.text)
+        });
+        if !repeated {
+            merged.push(segment);
+        }
+    }
+}
+
+/// Start times of the overlapping chunks covering `duration`; the last chunk is the first one reaching the end
+fn chunk_starts(duration: f64, chunk_seconds: f64, overlap: f64) -> Vec<f64> {
+    let step = chunk_seconds - overlap;
+    let mut starts = vec![0.0];
+    let mut chunk_start = 0.0;
+    while chunk_start + chunk_seconds < duration {
+        chunk_start += step;
+        starts.push(chunk_start);
+    }
+    starts
+}
+
+/// Transcribe a file's audio with Whisper into timestamped segments, splitting uploads over the 25 MB limit
+#[command]
+pub async fn transcribe_audio(
+    app: AppHandle,
+    file_path: String,
+    api_key: String,
+    language: Option<String>,
+) -> Result<Vec<TranscriptSegment>, OpenAiError> {
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_transcribe")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let language = language.filter(|language| !language.trim().is_empty());
+    let result = transcribe_audio_in(&app, &config, &work_dir, &file_path, &api_key, language.as_deref()).await;
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result
+}
+
+async fn transcribe_audio_in(
+    app: &AppHandle,
+    config: &OpenAiConfig,
+    work_dir: &std::path::Path,
+    file_path: &str,
+    api_key: &str,
+    language: Option<&str>,
+) -> Result<Vec<TranscriptSegment>, OpenAiError> {
+    let audio_path = work_dir.join("audio.wav");
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = tokio::process::Command::new(&ffmpeg_path)
+        .arg("-y")
+        .arg("-i")
+        .arg(file_path)
+        .arg("-vn")
+        .arg("-ar")
+        .arg("16000")
+        .arg("-ac")
+        .arg("1")
+        .arg(&audio_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to extract audio: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let audio_size = std::fs::metadata(&audio_path)
+        .map_err(|e| format!("Failed to read extracted audio: {}", e))?
+        .len();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    if audio_size <= WHISPER_MAX_UPLOAD_BYTES {
+        let audio_bytes = std::fs::read(&audio_path).map_err(|e| format!("Failed to read extracted audio: {}", e))?;
+        let transcription = transcribe_with_retry(&client, config, api_key, &audio_bytes, "audio.wav", language).await?;
+        return Ok(parse_whisper_segments(&transcription, 0.0));
+    }
+
+    // Keep 5% headroom under the limit for the WAV header and rounding
+    let duration = audio_size as f64 / TRANSCRIPTION_WAV_BYTES_PER_SECOND as f64;
+    let chunk_seconds = (WHISPER_MAX_UPLOAD_BYTES as f64 * 0.95 / TRANSCRIPTION_WAV_BYTES_PER_SECOND as f64).floor();
+
+    let mut merged: Vec<TranscriptSegment> = Vec::new();
+    for (index, chunk_start) in chunk_starts(duration, chunk_seconds, TRANSCRIPTION_CHUNK_OVERLAP_SECONDS)
+        .into_iter()
+        .enumerate()
+    {
+        let chunk_path = work_dir.join(format!("chunk_{:03}.wav", index));
+        let output = tokio::process::Command::new(&ffmpeg_path)
+            .arg("-y")
+            .arg("-ss")
+            .arg(chunk_start.to_string())
+            .arg("-t")
+            .arg(chunk_seconds.to_string())
+            .arg("-i")
+            .arg(&audio_path)
+            .arg("-c")
+            .arg("copy")
+            .arg(&chunk_path)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)).into());
+        }
+
+        log::info!("Transcribing chunk {} starting at {:.1}s", index + 1, chunk_start);
+        let chunk_bytes = std::fs::read(&chunk_path).map_err(|e| format!("Failed to read audio chunk: {}", e))?;
+        let transcription = transcribe_with_retry(&client, config, api_key, &chunk_bytes, "audio.wav", language).await?;
+        let segments = parse_whisper_segments(&transcription, chunk_start);
+
+        // Split the overlap down the middle; the first chunk has no overlap before it
+        let cut = if index == 0 { 0.0 } else { chunk_start + TRANSCRIPTION_CHUNK_OVERLAP_SECONDS / 2.0 };
+        merge_chunk_segments(&mut merged, segments, cut);
+    }
+
+    Ok(merged)
+}
+
+/// Parse the JSON object GPT-4o returns for a text extraction, tolerating markdown code fences
+fn parse_text_extraction(content: &str) -> (String, Vec<TextRegion>) {
+    let (Some(start), Some(end)) = (content.find('{'), content.rfind('}')) else {
+        return (String::new(), Vec::new());
+    };
+    if end < start {
+        return (String::new(), Vec::new());
+    }
+
+    let parsed: serde_json::Value = serde_json::from_str(&content[start..=end]).unwrap_or_default();
+    let full_text = parsed["text"].as_str().unwrap_or("").trim().to_string();
+    let regions = parsed["regions"]
+        .as_array()
+        .map(|regions| {
+            regions
+                .iter()
+                .filter_map(|region| {
+                    Some(TextRegion {
+                        x: region["x"].as_f64()?,
+                        y: region["y"].as_f64()?,
+                        width: region["width"].as_f64()?,
+                        height: region["height"].as_f64()?,
+                        text: region["text"].as_str().unwrap_or("").trim().to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    (full_text, regions)
+}
+
+/// Sample frames from a video and read their visible text with GPT-4o Vision
+#[command]
+pub async fn extract_text_from_video(
+    app: AppHandle,
+    input_path: String,
+    api_key: String,
+    sample_rate_seconds: f64,
+) -> Result<Vec<VideoTextEntry>, String> {
+    if sample_rate_seconds <= 0.0 {
+        return Err("Sample rate must be greater than zero".to_string());
+    }
+
+    let frames_dir = app_temp_dir(&app)
+        .join("clipforge_text_extraction")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&frames_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let config = app_openai_config(&app)?;
+    let api_key = config.resolve_key(&api_key);
+    let result = extract_text_from_frames(&app, &config, &frames_dir, &input_path, &api_key, sample_rate_seconds).await;
+
+    if let Err(e) = std::fs::remove_dir_all(&frames_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result
+}
+
+async fn extract_text_from_frames(
+    app: &AppHandle,
+    config: &OpenAiConfig,
+    frames_dir: &std::path::Path,
+    input_path: &str,
+    api_key: &str,
+    sample_rate_seconds: f64,
+) -> Result<Vec<VideoTextEntry>, String> {
+    // Extract one frame per interval; PNG keeps small text legible
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let output = tokio::process::Command::new(ffmpeg_path)
+        .arg("-i")
+        .arg(input_path)
+        .arg("-vf")
+        .arg(format!("fps=1/{}", sample_rate_seconds))
+        .arg(frames_dir.join("frame_%05d.png"))
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let mut frame_paths: Vec<_> = std::fs::read_dir(frames_dir)
+        .map_err(|e| format!("Failed to read extracted frames: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    frame_paths.sort();
+
+    println!("Extracting text from {} frames", frame_paths.len());
+
+    let client = reqwest::Client::new();
+    let mut entries: Vec<VideoTextEntry> = Vec::new();
+    let mut last_request: Option<Instant> = None;
+
+    for (i, frame_path) in frame_paths.iter().enumerate() {
+        let timestamp = i as f64 * sample_rate_seconds;
+
+        if let Some(last) = last_request {
+            let elapsed = last.elapsed();
+            if elapsed < VISION_REQUEST_GAP {
+                tokio::time::sleep(VISION_REQUEST_GAP - elapsed).await;
+            }
+        }
+
+        let frame_bytes = std::fs::read(frame_path)
+            .map_err(|e| format!("Failed to read frame: {}", e))?;
+        let base64_frame = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &frame_bytes);
+
+        last_request = Some(Instant::now());
+        let response = client
+            .post(config.endpoint("chat/completions"))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({
+                "model": "gpt-4o",
+                "messages": [
+                    {
+                        "role": "user",
+                        "content": [
+                            { "type": "text", "text": TEXT_EXTRACTION_PROMPT },
+                            {
+                                "type": "image_url",
+                                "image_url": {
+                                    "url": format!("data:image/png;base64,{}", base64_frame),
+                                    "detail": "high"
+                                }
+                            }
+                        ]
+                    }
+                ],
+                "max_tokens": 1500,
+                "temperature": 0
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call OpenAI API: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("OpenAI API error: {}", error_text));
+        }
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+        let content = response_json["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or("");
+
+        let (full_text, regions) = parse_text_extraction(content);
+
+        // Slides stay on screen across samples, so keep only the first frame of each text state
+        if entries.last().is_some_and(|previous| previous.full_text == full_text) {
+            continue;
+        }
+
+        entries.push(VideoTextEntry { timestamp, full_text, regions });
+    }
+
+    Ok(entries)
+}
+
+/// Build a searchable log of on-screen text, with GPT-4o Vision when a key is given or FFmpeg's tesseract `ocr` filter otherwise
+#[command]
+pub async fn extract_onscreen_text(
+    app: AppHandle,
+    input_path: String,
+    api_key: Option<String>,
+    sample_interval_seconds: f64,
+) -> Result<Vec<OnscreenTextEntry>, String> {
+    if sample_interval_seconds <= 0.0 {
+        return Err("Sample interval must be greater than zero".to_string());
+    }
+
+    let entries = match api_key.filter(|key| !key.trim().is_empty()) {
+        Some(api_key) => extract_text_from_video(app, input_path, api_key, sample_interval_seconds)
+            .await?
+            .into_iter()
+            .map(|entry| OnscreenTextEntry {
+                timestamp: entry.timestamp,
+                text: entry.full_text,
+                confidence: None,
+            })
+            .collect(),
+        None => extract_text_with_ocr_filter(&app, &input_path, sample_interval_seconds).await?,
+    };
+
+    // Drop blank frames and text that has not changed since the previous entry
+    let mut deduplicated: Vec<OnscreenTextEntry> = Vec::new();
+    for entry in entries {
+        let text = entry.text.trim();
+        if text.is_empty() || deduplicated.last().is_some_and(|previous| previous.text == text) {
+            continue;
+        }
+        deduplicated.push(OnscreenTextEntry { text: text.to_string(), ..entry });
+    }
+
+    Ok(deduplicated)
+}
+
+/// Run FFmpeg's `ocr` filter on sampled frames, for builds compiled with tesseract
+async fn extract_text_with_ocr_filter(
+    app: &AppHandle,
+    input_path: &str,
+    sample_interval_seconds: f64,
+) -> Result<Vec<OnscreenTextEntry>, String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let filters = tokio::process::Command::new(&ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-filters")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+    let has_ocr = String::from_utf8_lossy(&filters.stdout)
+        .lines()
+        .any(|line| line.split_whitespace().nth(1) == Some("ocr"));
+    if !has_ocr {
+        return Err("This FFmpeg build has no ocr filter; provide an OpenAI API key to read on-screen text".to_string());
+    }
+
+    let output = tokio::process::Command::new(&ffmpeg_path)
+        .arg("-i")
+        .arg(input_path)
+        .arg("-an")
+        .arg("-vf")
+        .arg(format!("fps=1/{},ocr,metadata=print", sample_interval_seconds))
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(parse_ocr_metadata(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// Parse `metadata=print` output: a "pts_time:" line per frame, then lavfi.ocr.text (possibly multi-line) and lavfi.ocr.confidence
+fn parse_ocr_metadata(log: &str) -> Vec<OnscreenTextEntry> {
+    let mut entries: Vec<OnscreenTextEntry> = Vec::new();
+    let mut reading_text = false;
+
+    for line in log.lines() {
+        // Strip the "[Parsed_metadata_2 @ 0x...] " log prefix
+        let content = match line.strip_prefix('[').and_then(|rest| rest.split_once("] ")) {
+            Some((_, rest)) => rest,
+            None => line,
+        };
+
+        if let Some(time) = content.split_once("pts_time:").and_then(|(_, rest)| rest.split_whitespace().next()) {
+            reading_text = false;
+            if let Ok(timestamp) = time.parse() {
+                entries.push(OnscreenTextEntry { timestamp, text: String::new(), confidence: None });
+            }
+        } else if let Some(text) = content.strip_prefix("lavfi.ocr.text=") {
+            reading_text = true;
+            if let Some(entry) = entries.last_mut() {
+                entry.text = text.to_string();
+            }
+        } else if let Some(confidences) = content.strip_prefix("lavfi.ocr.confidence=") {
+            reading_text = false;
+            // One value per recognised word, 0 - 100
+            let words: Vec<f64> = confidences.split_whitespace().filter_map(|value| value.parse().ok()).collect();
+            if let Some(entry) = entries.last_mut() {
+                entry.confidence = (!words.is_empty()).then(|| words.iter().sum::<f64>() / words.len() as f64 / 100.0);
+            }
+        } else if reading_text {
+            if let Some(entry) = entries.last_mut() {
+                entry.text.push('\n');
+                entry.text.push_str(content);
+            }
+        }
+    }
+
+    entries
+}
+
+/// Case-insensitive substring search over extracted on-screen text
+#[command]
+pub fn search_onscreen_text(entries: Vec<OnscreenTextEntry>, query: String) -> Vec<OnscreenTextEntry> {
+    let query = query.trim().to_lowercase();
+    entries
+        .into_iter()
+        .filter(|entry| entry.text.to_lowercase().contains(&query))
+        .collect()
+}
+
+// Voices accepted by the speech endpoint
+const TTS_VOICES: &[&str] = &["alloy", "echo", "fable", "onyx", "nova", "shimmer"];
+
+/// Synthesize speech for `text` with the OpenAI TTS endpoint and save it as MP3
+pub async fn text_to_speech_file(
+    app: &AppHandle,
+    api_key: &str,
+    text: &str,
+    voice: &str,
+    output_path: &std::path::Path,
+) -> Result<(), OpenAiError> {
+    if !TTS_VOICES.contains(&voice) {
+        return Err(OpenAiError::InvalidRequest {
+            message: format!("Unknown voice: {}. Available: {}", voice, TTS_VOICES.join(", ")),
+        });
+    }
+
+    let config = app_openai_config(app).map_err(OpenAiError::UnexpectedResponse)?;
+    let api_key = config.resolve_key(api_key);
+    let response = reqwest::Client::new()
+        .post(config.endpoint("audio/speech"))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({
+            "model": "tts-1",
+            "input": text,
+            "voice": voice,
+            "response_format": "mp3"
+        }))
+        .send()
+        .await
+        .map_err(|e| OpenAiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(OpenAiError::from_response(response).await);
+    }
+
+    let audio_bytes = response
+        .bytes()
+        .await
+        .map_err(|e| OpenAiError::NetworkError(format!("Failed to read speech audio: {}", e)))?;
+    std::fs::write(output_path, &audio_bytes)
+        .map_err(|e| OpenAiError::Local(format!("Failed to save speech audio: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment { start, end, text: text.to_string() }
+    }
+
+    #[test]
+    fn chunks_cover_the_audio_without_an_overlap_only_tail() {
+        let (chunk_seconds, overlap) = (100.0, 10.0);
+        for duration in [150.0, 190.0, 190.5, 280.0, 1000.0] {
+            let starts = chunk_starts(duration, chunk_seconds, overlap);
+            assert_eq!(starts[0], 0.0);
+            assert!(starts.last().unwrap() + chunk_seconds >= duration, "{} not covered", duration);
+            for pair in starts.windows(2) {
+                assert_eq!(pair[1] - pair[0], chunk_seconds - overlap);
+            }
+            // Every chunk after the first reaches past the previous chunk's end
+            for (previous, start) in starts.iter().zip(starts.iter().skip(1)) {
+                assert!(previous + chunk_seconds < duration, "chunk at {} only covers overlap of {}", start, duration);
+            }
+        }
+        assert_eq!(chunk_starts(190.0, chunk_seconds, overlap), vec![0.0, 90.0]);
+        assert_eq!(chunk_starts(190.5, chunk_seconds, overlap), vec![0.0, 90.0, 180.0]);
+    }
+
+    #[test]
+    fn merge_splits_the_overlap_at_the_cut() {
+        let mut merged = vec![segment(80.0, 88.0, "before the overlap"), segment(92.0, 97.0, "late in chunk one")];
+        let chunk = vec![segment(91.0, 94.0, "early in chunk two"), segment(96.0, 99.0, "after the cut")];
+        merge_chunk_segments(&mut merged, chunk, 95.0);
+
+        let texts: Vec<&str> = merged.iter().map(|segment| segment.text.as_str()).collect();
+        assert_eq!(texts, vec!["before the overlap", "late in chunk one", "after the cut"]);
+    }
+
+    #[test]
+    fn merge_drops_a_sentence_heard_by_both_chunks() {
+        let mut merged = vec![segment(90.0, 96.0, "Straddles the cut.")];
+        let chunk = vec![segment(95.2, 96.0, "straddles the cut."), segment(96.0, 99.0, "Next sentence.")];
+        merge_chunk_segments(&mut merged, chunk, 95.0);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].start, 90.0);
+        assert_eq!(merged[1].text, "Next sentence.");
+    }
+}
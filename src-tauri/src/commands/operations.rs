@@ -0,0 +1,308 @@
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{ExitStatus, Output, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Payload of the `clipforge://ffmpeg-process-started` event, so a run can be cancelled while in flight
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FfmpegProcessStarted {
+    /// The operation id, as listed by `list_active_operations`
+    pub process_id: String,
+    pub operation_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActiveOperation {
+    /// Accepted by both `kill_operation` and `cancel_ffmpeg_process`
+    pub id: String,
+    pub operation_type: String,
+    pub input_path: String,
+    pub output_path: String,
+    /// Seconds since the Unix epoch
+    pub started_at: u64,
+    pub pid: Option<u32>,
+    /// Whether the child handle is held, so stopping it fails the command with a cancellation error
+    pub cancellable: bool,
+}
+
+/// FFmpeg processes currently running on behalf of a command, held as managed state
+#[derive(Debug, Default)]
+pub struct OperationRegistry {
+    operations: HashMap<String, ActiveOperation>,
+    // Child handles of cancellable operations, keyed by the same id; removed on exit or cancellation
+    children: HashMap<String, tokio::process::Child>,
+}
+
+/// Record a spawned process and return the id to deregister it with
+pub fn register_operation(
+    app: &AppHandle,
+    operation_type: &str,
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    pid: Option<u32>,
+) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    let operation = ActiveOperation {
+        id: id.clone(),
+        operation_type: operation_type.to_string(),
+        input_path: input_path.as_ref().to_string_lossy().to_string(),
+        output_path: output_path.as_ref().to_string_lossy().to_string(),
+        started_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        pid,
+        cancellable: false,
+    };
+
+    if let Some(state) = app.try_state::<Mutex<OperationRegistry>>() {
+        if let Ok(mut registry) = state.lock() {
+            registry.operations.insert(id.clone(), operation);
+        }
+    }
+
+    id
+}
+
+pub fn deregister_operation(app: &AppHandle, id: &str) {
+    if let Some(state) = app.try_state::<Mutex<OperationRegistry>>() {
+        if let Ok(mut registry) = state.lock() {
+            registry.operations.remove(id);
+            registry.children.remove(id);
+        }
+    }
+}
+
+/// Drop-in for `Command::output()` that lists the process in the registry while it runs
+pub trait TrackedOutput {
+    fn tracked_output(
+        &mut self,
+        app: &AppHandle,
+        operation_type: &str,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> std::io::Result<Output>;
+}
+
+impl TrackedOutput for std::process::Command {
+    fn tracked_output(
+        &mut self,
+        app: &AppHandle,
+        operation_type: &str,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> std::io::Result<Output> {
+        // Match output(): no stdin, capture stdout and stderr
+        let child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let id = register_operation(app, operation_type, input_path, output_path, Some(child.id()));
+        let output = child.wait_with_output();
+        deregister_operation(app, &id);
+        output
+    }
+}
+
+/// Async counterpart of `TrackedOutput` for `tokio::process::Command`
+pub trait TrackedOutputAsync {
+    fn tracked_output(
+        &mut self,
+        app: &AppHandle,
+        operation_type: &str,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> impl std::future::Future<Output = std::io::Result<Output>>;
+}
+
+impl TrackedOutputAsync for tokio::process::Command {
+    async fn tracked_output(
+        &mut self,
+        app: &AppHandle,
+        operation_type: &str,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+    ) -> std::io::Result<Output> {
+        let child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let id = register_operation(app, operation_type, input_path, output_path, child.id());
+        let output = child.wait_with_output().await;
+        deregister_operation(app, &id);
+        output
+    }
+}
+
+/// Register a spawned child as a cancellable operation and announce its id to the frontend
+pub fn store_cancellable_child(
+    app: &AppHandle,
+    operation_type: &str,
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    child: tokio::process::Child,
+) -> Result<String, String> {
+    let process_id = register_operation(app, operation_type, input_path, output_path, child.id());
+    let state = app
+        .try_state::<Mutex<OperationRegistry>>()
+        .ok_or("Operation registry is not available")?;
+    let mut registry = state
+        .lock()
+        .map_err(|e| format!("Failed to store FFmpeg process: {}", e))?;
+    if let Some(operation) = registry.operations.get_mut(&process_id) {
+        operation.cancellable = true;
+    }
+    registry.children.insert(process_id.clone(), child);
+    drop(registry);
+
+    let started = FfmpegProcessStarted {
+        process_id: process_id.clone(),
+        operation_type: operation_type.to_string(),
+    };
+    if let Err(e) = app.emit("clipforge://ffmpeg-process-started", started) {
+        log::warn!("Failed to emit process start: {}", e);
+    }
+
+    Ok(process_id)
+}
+
+/// Wait for a stored child to exit and deregister it; errors if it was cancelled first
+pub async fn wait_cancellable_child(app: &AppHandle, process_id: &str) -> Result<ExitStatus, String> {
+    let state = app
+        .try_state::<Mutex<OperationRegistry>>()
+        .ok_or("Operation registry is not available")?;
+    loop {
+        {
+            let mut registry = state
+                .lock()
+                .map_err(|e| format!("Failed to read FFmpeg processes: {}", e))?;
+            let Some(child) = registry.children.get_mut(process_id) else {
+                return Err(format!("FFmpeg process {} was cancelled", process_id));
+            };
+            let finished = match child.try_wait() {
+                Ok(Some(status)) => Some(Ok(status)),
+                Ok(None) => None,
+                Err(e) => Some(Err(format!("Failed to wait for FFmpeg: {}", e))),
+            };
+            if let Some(result) = finished {
+                registry.children.remove(process_id);
+                registry.operations.remove(process_id);
+                return result;
+            }
+        }
+        tokio::time::sleep(PROCESS_POLL_INTERVAL).await;
+    }
+}
+
+/// Like `tracked_output`, but the child can also be stopped with `kill_operation` or `cancel_ffmpeg_process`.
+/// The id is announced with `clipforge://ffmpeg-process-started` and listed by `list_active_operations` while it runs.
+pub async fn cancellable_output(
+    app: &AppHandle,
+    operation_type: &str,
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    command: &mut tokio::process::Command,
+) -> Result<Output, String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    // Drain both pipes concurrently so a full pipe can't stall FFmpeg
+    let mut stdout = child.stdout.take().ok_or("Failed to capture FFmpeg stdout")?;
+    let mut stderr = child.stderr.take().ok_or("Failed to capture FFmpeg stderr")?;
+    let stdout_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        let _ = stdout.read_to_end(&mut buffer).await;
+        buffer
+    });
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        let _ = stderr.read_to_end(&mut buffer).await;
+        buffer
+    });
+
+    let process_id = store_cancellable_child(app, operation_type, input_path, output_path, child)?;
+    let status = wait_cancellable_child(app, &process_id).await?;
+
+    Ok(Output {
+        status,
+        stdout: stdout_task.await.unwrap_or_default(),
+        stderr: stderr_task.await.unwrap_or_default(),
+    })
+}
+
+/// Stop a registered operation: cancellable children through their handle, anything else by pid
+async fn stop_operation(registry: &Mutex<OperationRegistry>, operation_id: &str) -> Result<(), String> {
+    let (operation, child) = {
+        let mut registry = registry
+            .lock()
+            .map_err(|e| format!("Failed to update operation registry: {}", e))?;
+        (registry.operations.remove(operation_id), registry.children.remove(operation_id))
+    };
+
+    if let Some(mut child) = child {
+        return child
+            .kill()
+            .await
+            .map_err(|e| format!("Failed to kill FFmpeg process {}: {}", operation_id, e));
+    }
+
+    // A missing entry means the process already finished
+    let operation = operation.ok_or_else(|| format!("No active operation with id {}", operation_id))?;
+    let pid = operation
+        .pid
+        .ok_or_else(|| format!("Operation {} has no process id", operation_id))?
+        .to_string();
+
+    #[cfg(unix)]
+    let output = std::process::Command::new("kill").args(["-TERM", &pid]).output();
+    #[cfg(windows)]
+    let output = std::process::Command::new("taskkill").args(["/PID", &pid, "/T", "/F"]).output();
+
+    let output = output.map_err(|e| format!("Failed to stop process {}: {}", pid, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to stop process {}: {}",
+            pid,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+#[command]
+pub async fn cancel_ffmpeg_process(
+    state: State<'_, Mutex<OperationRegistry>>,
+    process_id: String,
+) -> Result<(), String> {
+    stop_operation(state.inner(), &process_id).await
+}
+
+#[command]
+pub async fn list_active_operations(state: State<'_, Mutex<OperationRegistry>>) -> Result<Vec<ActiveOperation>, String> {
+    let registry = state
+        .lock()
+        .map_err(|e| format!("Failed to read operation registry: {}", e))?;
+
+    let mut operations: Vec<ActiveOperation> = registry.operations.values().cloned().collect();
+    operations.sort_by_key(|operation| operation.started_at);
+    Ok(operations)
+}
+
+/// Stop a running FFmpeg process; the command that started it sees a cancellation error or a failed exit status
+#[command]
+pub async fn kill_operation(state: State<'_, Mutex<OperationRegistry>>, operation_id: String) -> Result<(), String> {
+    stop_operation(state.inner(), &operation_id).await
+}
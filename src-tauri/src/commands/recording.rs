@@ -4,7 +4,7 @@ use std::process::Command;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
-use crate::commands::binary_utils::get_ffmpeg_path;
+use crate::commands::binary_utils::{app_temp_dir, get_binary_path, get_ffmpeg_path, get_ffprobe_path};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MonitorInfo {
@@ -15,6 +15,8 @@ pub struct MonitorInfo {
     pub width: u32,
     pub height: u32,
     pub is_primary: bool,
+    /// Physical pixels per logical pixel, e.g. 2.0 on Retina displays
+    pub scale_factor: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,6 +39,40 @@ pub struct RecordingSession {
     pub process_id: Option<u32>,
     pub recording_type: String, // "screen", "webcam", "both"
     pub is_active: bool,
+    /// FFmpeg is suspended; nothing is captured until the session is resumed
+    pub paused: bool,
+    /// Captured screen rectangle, or None for a full-screen or webcam recording
+    pub region: Option<RecordingRegion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RecordingRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FpsRecommendation {
+    pub monitor_refresh_hz: f64,
+    pub recommended_recording_fps: f64,
+    pub reason: String,
+}
+
+// Assumed when the refresh rate cannot be detected
+const DEFAULT_REFRESH_HZ: f64 = 60.0;
+
+// Recording faster than this grows the file without a visible gain; slower looks choppy
+const MAX_RECOMMENDED_FPS: u32 = 75;
+const MIN_RECOMMENDED_FPS: u32 = 30;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RescueResult {
+    pub success: bool,
+    pub strategy_used: String,
+    pub recovered_duration_seconds: f64,
+    pub frames_recovered: u64,
 }
 
 // Global state to track recording sessions and capture sources
@@ -45,36 +81,244 @@ lazy_static::lazy_static! {
     static ref CAPTURE_SOURCES: Mutex<HashMap<String, CaptureSource>> = Mutex::new(HashMap::new());
 }
 
+/// Active displays from Core Graphics; positions are scaled from points to pixels to match the pixel sizes
+#[cfg(target_os = "macos")]
+fn enumerate_monitors() -> Vec<MonitorInfo> {
+    use core_graphics::display::CGDisplay;
+
+    let Ok(display_ids) = CGDisplay::active_displays() else {
+        return Vec::new();
+    };
+
+    display_ids
+        .into_iter()
+        .map(|display_id| {
+            let display = CGDisplay::new(display_id);
+            let bounds = display.bounds();
+            let width = display.pixels_wide() as u32;
+            let scale_factor = if bounds.size.width > 0.0 { width as f64 / bounds.size.width } else { 1.0 };
+            MonitorInfo {
+                id: display_id.to_string(),
+                name: if display.is_builtin() {
+                    "Built-in Display".to_string()
+                } else {
+                    format!("Display {}", display_id)
+                },
+                x: (bounds.origin.x * scale_factor).round() as i32,
+                y: (bounds.origin.y * scale_factor).round() as i32,
+                width,
+                height: display.pixels_high() as u32,
+                is_primary: display.is_main(),
+                scale_factor,
+            }
+        })
+        .collect()
+}
+
+/// Monitors from EnumDisplayMonitors, with the effective DPI as the scale factor
+#[cfg(target_os = "windows")]
+fn enumerate_monitors() -> Vec<MonitorInfo> {
+    use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+    use windows::Win32::Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW};
+    use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+    use windows::Win32::UI::WindowsAndMessaging::MONITORINFOF_PRIMARY;
+
+    unsafe extern "system" fn collect_monitor(monitor: HMONITOR, _hdc: HDC, _clip: *mut RECT, data: LPARAM) -> BOOL {
+        // SAFETY: data is the &mut Vec passed to EnumDisplayMonitors below, alive for the whole enumeration
+        let monitors = &mut *(data.0 as *mut Vec<MonitorInfo>);
+
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+            let rect = info.monitorInfo.rcMonitor;
+            let name_len = info.szDevice.iter().position(|c| *c == 0).unwrap_or(info.szDevice.len());
+            let device_name = String::from_utf16_lossy(&info.szDevice[..name_len]);
+
+            let (mut dpi_x, mut dpi_y) = (96u32, 96u32);
+            let _ = GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+
+            monitors.push(MonitorInfo {
+                id: device_name.clone(),
+                name: device_name,
+                x: rect.left,
+                y: rect.top,
+                width: (rect.right - rect.left).max(0) as u32,
+                height: (rect.bottom - rect.top).max(0) as u32,
+                is_primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+                scale_factor: dpi_x as f64 / 96.0,
+            });
+        }
+        BOOL(1)
+    }
+
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+    // SAFETY: the callback only runs during this call, while monitors is borrowed
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect_monitor),
+            LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+        );
+    }
+    monitors
+}
+
+/// Parse connected outputs with an active mode from `xrandr --query`,
+/// e.g. "HDMI-1 connected primary 1920x1080+0+0 (normal left inverted right) 527mm x 296mm"
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn parse_xrandr_monitors(output: &str, scale_factor: f64) -> Vec<MonitorInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next()?;
+            if tokens.next()? != "connected" {
+                return None;
+            }
+            let mut is_primary = false;
+            // Outputs that are connected but switched off have no geometry
+            let geometry = tokens.find(|token| {
+                is_primary |= *token == "primary";
+                token.contains('x') && token.contains('+')
+            })?;
+
+            let (size, position) = geometry.split_once('+')?;
+            let (width, height) = size.split_once('x')?;
+            let (x, y) = position.split_once('+')?;
+            Some(MonitorInfo {
+                id: name.to_string(),
+                name: name.to_string(),
+                x: x.parse().ok()?,
+                y: y.parse().ok()?,
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+                is_primary,
+                scale_factor,
+            })
+        })
+        .collect()
+}
+
+/// Connected outputs from xrandr; X11 has no per-output scale, so GDK_SCALE is used when set
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn enumerate_monitors() -> Vec<MonitorInfo> {
+    let Ok(output) = Command::new("xrandr").arg("--query").output() else {
+        return Vec::new();
+    };
+    let scale_factor = std::env::var("GDK_SCALE")
+        .ok()
+        .and_then(|scale| scale.parse::<f64>().ok())
+        .filter(|scale| *scale > 0.0)
+        .unwrap_or(1.0);
+
+    let mut monitors = parse_xrandr_monitors(&String::from_utf8_lossy(&output.stdout), scale_factor);
+    // Without a "primary" flag, treat the first output as primary
+    if !monitors.iter().any(|monitor| monitor.is_primary) {
+        if let Some(first) = monitors.first_mut() {
+            first.is_primary = true;
+        }
+    }
+    monitors
+}
+
 #[command]
 pub async fn get_available_monitors() -> Result<Vec<MonitorInfo>, String> {
-    // For macOS, we'll use a simple approach to get monitor information
-    // In a real implementation, you'd use Core Graphics APIs
-    let mut monitors = Vec::new();
-    
-    // Mock data for now - in production, you'd query the display manager
-    monitors.push(MonitorInfo {
-        id: "monitor_1".to_string(),
-        name: "Built-in Retina Display".to_string(),
-        x: 0,
-        y: 0,
-        width: 2560,
-        height: 1600,
-        is_primary: true,
-    });
-    
-    monitors.push(MonitorInfo {
-        id: "monitor_2".to_string(),
-        name: "External Display".to_string(),
-        x: 2560,
-        y: 0,
-        width: 1920,
-        height: 1080,
-        is_primary: false,
-    });
+    let monitors = tokio::task::spawn_blocking(enumerate_monitors)
+        .await
+        .map_err(|e| format!("Monitor enumeration failed: {}", e))?;
 
+    if monitors.is_empty() {
+        return Err("No displays detected".to_string());
+    }
     Ok(monitors)
 }
 
+/// The part of a rectangle on the monitor it overlaps most, or None when it is entirely off-screen
+fn clamp_to_monitors(x: i32, y: i32, width: u32, height: u32, monitors: &[MonitorInfo]) -> Option<(i32, i32, u32, u32)> {
+    let left = x as i64;
+    let top = y as i64;
+    let right = left + width as i64;
+    let bottom = top + height as i64;
+
+    monitors
+        .iter()
+        .filter_map(|monitor| {
+            let overlap_left = left.max(monitor.x as i64);
+            let overlap_top = top.max(monitor.y as i64);
+            let overlap_right = right.min(monitor.x as i64 + monitor.width as i64);
+            let overlap_bottom = bottom.min(monitor.y as i64 + monitor.height as i64);
+            (overlap_right > overlap_left && overlap_bottom > overlap_top)
+                .then_some((overlap_left, overlap_top, overlap_right, overlap_bottom))
+        })
+        .max_by_key(|(l, t, r, b)| (r - l) * (b - t))
+        .map(|(l, t, r, b)| (l as i32, t as i32, (r - l) as u32, (b - t) as u32))
+}
+
+/// Reject sources that lie entirely off-screen; sources that only partially overlap are clamped to the monitor they overlap most
+fn validate_capture_source_bounds(source: &mut CaptureSource, monitors: &[MonitorInfo]) -> Result<(), String> {
+    let Some((x, y, width, height)) = clamp_to_monitors(source.x, source.y, source.width, source.height, monitors) else {
+        return Err(format!(
+            "Capture region {}x{} at ({}, {}) is outside all monitor bounds",
+            source.width, source.height, source.x, source.y
+        ));
+    };
+
+    source.x = x;
+    source.y = y;
+    source.width = width;
+    source.height = height;
+    Ok(())
+}
+
+/// Place a requested recording region on the monitors, rounded down to the even size yuv420p needs.
+/// With `in_points` the region is in logical points (macOS) and is scaled to pixels by the monitor it is on.
+fn fit_recording_region(
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    monitors: &[MonitorInfo],
+    in_points: bool,
+) -> Result<RecordingRegion, String> {
+    let outside = || format!("Recording region {}x{} at ({}, {}) is outside all monitor bounds", width, height, x, y);
+
+    let (x, y, width, height) = if in_points {
+        // Monitor bounds are in pixels, so compare against them divided back into points
+        let monitor_points: Vec<MonitorInfo> = monitors
+            .iter()
+            .map(|monitor| MonitorInfo {
+                x: (monitor.x as f64 / monitor.scale_factor).round() as i32,
+                y: (monitor.y as f64 / monitor.scale_factor).round() as i32,
+                width: (monitor.width as f64 / monitor.scale_factor).round() as u32,
+                height: (monitor.height as f64 / monitor.scale_factor).round() as u32,
+                ..monitor.clone()
+            })
+            .collect();
+        let (x, y, width, height) = clamp_to_monitors(x, y, width, height, &monitor_points).ok_or_else(outside)?;
+        let scale = monitor_points
+            .iter()
+            .find(|monitor| {
+                x >= monitor.x && y >= monitor.y && x < monitor.x + monitor.width as i32 && y < monitor.y + monitor.height as i32
+            })
+            .map_or(1.0, |monitor| monitor.scale_factor);
+        (
+            (x as f64 * scale).round() as i32,
+            (y as f64 * scale).round() as i32,
+            (width as f64 * scale).round() as u32,
+            (height as f64 * scale).round() as u32,
+        )
+    } else {
+        clamp_to_monitors(x, y, width, height, monitors).ok_or_else(outside)?
+    };
+
+    let region = RecordingRegion { x, y, width: width & !1, height: height & !1 };
+    if region.width == 0 || region.height == 0 {
+        return Err("Region must be at least 2x2 pixels".to_string());
+    }
+    Ok(region)
+}
+
 #[command]
 pub async fn add_capture_source(
     source_type: String,
@@ -87,7 +331,7 @@ pub async fn add_capture_source(
 ) -> Result<String, String> {
     let source_id = Uuid::new_v4().to_string();
     
-    let source = CaptureSource {
+    let mut source = CaptureSource {
         id: source_id.clone(),
         name,
         source_type,
@@ -99,6 +343,9 @@ pub async fn add_capture_source(
         is_active: true,
     };
 
+    let monitors = get_available_monitors().await?;
+    validate_capture_source_bounds(&mut source, &monitors)?;
+
     {
         let mut sources = CAPTURE_SOURCES.lock().unwrap();
         sources.insert(source_id.clone(), source);
@@ -115,13 +362,13 @@ pub async fn update_capture_source_position(
     width: u32,
     height: u32,
 ) -> Result<String, String> {
+    let monitors = get_available_monitors().await?;
     let mut sources = CAPTURE_SOURCES.lock().unwrap();
     
     if let Some(source) = sources.get_mut(&source_id) {
-        source.x = x;
-        source.y = y;
-        source.width = width;
-        source.height = height;
+        let mut updated = CaptureSource { x, y, width, height, ..source.clone() };
+        validate_capture_source_bounds(&mut updated, &monitors)?;
+        *source = updated;
         Ok("Position updated".to_string())
     } else {
         Err("Source not found".to_string())
@@ -193,6 +440,113 @@ pub async fn start_screen_recording(app: AppHandle, _window_ids: Vec<String>) ->
         process_id: Some(process_id),
         recording_type: "screen".to_string(),
         is_active: true,
+        paused: false,
+        region: None,
+    };
+
+    {
+        let mut sessions = RECORDING_SESSIONS.lock().unwrap();
+        sessions.insert(session_id.clone(), session);
+    }
+
+    Ok(session_id)
+}
+
+/// Grabber input arguments and any crop filter for a screen rectangle on this platform
+#[cfg(target_os = "macos")]
+fn region_capture_args(region: &RecordingRegion, fps: u32) -> (Vec<String>, Option<String>) {
+    // avfoundation always captures the whole screen, so crop to the region afterwards
+    let args = vec![
+        "-f".to_string(), "avfoundation".to_string(),
+        "-framerate".to_string(), fps.to_string(),
+        "-capture_cursor".to_string(), "1".to_string(),
+        "-capture_mouse_clicks".to_string(), "1".to_string(),
+        "-i".to_string(), "1:none".to_string(), // Screen capture on macOS, no audio device
+    ];
+    let crop = format!("crop={}:{}:{}:{}", region.width, region.height, region.x, region.y);
+    (args, Some(crop))
+}
+
+/// Grabber input arguments and any crop filter for a screen rectangle on this platform
+#[cfg(target_os = "windows")]
+fn region_capture_args(region: &RecordingRegion, fps: u32) -> (Vec<String>, Option<String>) {
+    let args = vec![
+        "-f".to_string(), "gdigrab".to_string(),
+        "-framerate".to_string(), fps.to_string(),
+        "-offset_x".to_string(), region.x.to_string(),
+        "-offset_y".to_string(), region.y.to_string(),
+        "-video_size".to_string(), format!("{}x{}", region.width, region.height),
+        "-i".to_string(), "desktop".to_string(),
+    ];
+    (args, None)
+}
+
+/// Grabber input arguments and any crop filter for a screen rectangle on this platform
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn region_capture_args(region: &RecordingRegion, fps: u32) -> (Vec<String>, Option<String>) {
+    let display = std::env::var("DISPLAY").unwrap_or_else(|_| ":0.0".to_string());
+    let args = vec![
+        "-f".to_string(), "x11grab".to_string(),
+        "-framerate".to_string(), fps.to_string(),
+        "-video_size".to_string(), format!("{}x{}", region.width, region.height),
+        "-i".to_string(), format!("{}+{},{}", display, region.x, region.y),
+    ];
+    (args, None)
+}
+
+/// Record a rectangle of the screen (video only); stopped like a full-screen recording with `stop_recording("screen")`
+#[command]
+pub async fn start_region_recording(
+    app: AppHandle,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    output_path: String,
+    fps: u32,
+) -> Result<String, String> {
+    if !(1..=120).contains(&fps) {
+        return Err("FPS must be between 1 and 120".to_string());
+    }
+
+    let monitors = get_available_monitors().await?;
+    // avfoundation's screen device is the main display, so a macOS region has to lie on it
+    #[cfg(target_os = "macos")]
+    let monitors: Vec<MonitorInfo> = monitors.into_iter().filter(|monitor| monitor.is_primary).collect();
+    let region = fit_recording_region(x, y, width, height, &monitors, cfg!(target_os = "macos"))?;
+
+    if let Some(parent) = std::path::Path::new(&output_path).parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let (mut args, crop_filter) = region_capture_args(&region, fps);
+    if let Some(filter) = crop_filter {
+        args.extend(["-vf".to_string(), filter]);
+    }
+    args.extend([
+        "-c:v".to_string(), "libx264".to_string(),
+        "-preset".to_string(), "medium".to_string(),
+        "-crf".to_string(), "23".to_string(),
+        "-pix_fmt".to_string(), "yuv420p".to_string(),
+        "-y".to_string(),
+        output_path.clone(),
+    ]);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let child = Command::new(ffmpeg_path)
+        .args(&args)
+        .spawn()
+        .map_err(|e| format!("Failed to start region recording: {}", e))?;
+
+    let session_id = Uuid::new_v4().to_string();
+    let session = RecordingSession {
+        id: session_id.clone(),
+        output_path,
+        process_id: Some(child.id()),
+        recording_type: "screen".to_string(),
+        is_active: true,
+        paused: false,
+        region: Some(region),
     };
 
     {
@@ -251,6 +605,8 @@ pub async fn start_webcam_recording(app: AppHandle, _device_id: String) -> Resul
         process_id: Some(process_id),
         recording_type: "webcam".to_string(),
         is_active: true,
+        paused: false,
+        region: None,
     };
 
     {
@@ -269,6 +625,11 @@ pub async fn stop_recording(recording_type: String) -> Result<String, String> {
     for (session_id, session) in sessions.iter_mut() {
         if session.recording_type == recording_type && session.is_active {
             if let Some(process_id) = session.process_id {
+                // A stopped process cannot act on SIGTERM, so thaw it first or it is never reaped
+                if session.paused {
+                    set_process_suspended(process_id, false)?;
+                    session.paused = false;
+                }
                 // Send SIGTERM to the process
                 let _ = Command::new("kill")
                     .arg("-TERM")
@@ -284,18 +645,92 @@ pub async fn stop_recording(recording_type: String) -> Result<String, String> {
     Err("No active recording found".to_string())
 }
 
+/// Freeze or thaw a recording's FFmpeg process
+#[cfg(unix)]
+fn set_process_suspended(process_id: u32, suspended: bool) -> Result<(), String> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    let signal = if suspended { Signal::SIGSTOP } else { Signal::SIGCONT };
+    kill(Pid::from_raw(process_id as i32), signal)
+        .map_err(|e| format!("Failed to send {} to recording process: {}", signal.as_str(), e))
+}
+
+/// Freeze or thaw a recording's FFmpeg process
+#[cfg(windows)]
+fn set_process_suspended(process_id: u32, suspended: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME};
+
+    // Windows has no stop signal, so suspend every thread the process owns
+    let mut threads_changed = 0;
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)
+            .map_err(|e| format!("Failed to list recording process threads: {}", e))?;
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+
+        let mut has_entry = Thread32First(snapshot, &mut entry).is_ok();
+        while has_entry {
+            if entry.th32OwnerProcessID == process_id {
+                if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    if suspended {
+                        SuspendThread(thread);
+                    } else {
+                        ResumeThread(thread);
+                    }
+                    let _ = CloseHandle(thread);
+                    threads_changed += 1;
+                }
+            }
+            has_entry = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+    }
+
+    if threads_changed == 0 {
+        return Err(format!("Recording process {} has no threads to suspend or resume", process_id));
+    }
+    Ok(())
+}
+
+/// Move every active recording into or out of the paused state; sessions already there are left alone
+fn set_recordings_paused(paused: bool) -> Result<usize, String> {
+    let mut sessions = RECORDING_SESSIONS.lock().unwrap();
+    if !sessions.values().any(|session| session.is_active) {
+        return Err("No active recording found".to_string());
+    }
+
+    let mut changed = 0;
+    for session in sessions.values_mut().filter(|session| session.is_active && session.paused != paused) {
+        if let Some(process_id) = session.process_id {
+            set_process_suspended(process_id, paused)?;
+        }
+        session.paused = paused;
+        changed += 1;
+    }
+    Ok(changed)
+}
+
 #[command]
 pub async fn pause_recording() -> Result<String, String> {
-    // For FFmpeg, we can't easily pause/resume, so we'll just return success
-    // In a real implementation, you'd need to handle this differently
-    Ok("Recording paused".to_string())
+    match set_recordings_paused(true)? {
+        0 => Ok("Recording already paused".to_string()),
+        _ => Ok("Recording paused".to_string()),
+    }
 }
 
 #[command]
 pub async fn resume_recording() -> Result<String, String> {
-    // For FFmpeg, we can't easily pause/resume, so we'll just return success
-    // In a real implementation, you'd need to handle this differently
-    Ok("Recording resumed".to_string())
+    match set_recordings_paused(false)? {
+        0 => Ok("Recording already running".to_string()),
+        _ => Ok("Recording resumed".to_string()),
+    }
 }
 
 #[command]
@@ -303,3 +738,450 @@ pub async fn get_recording_status() -> Result<Vec<RecordingSession>, String> {
     let sessions = RECORDING_SESSIONS.lock().unwrap();
     Ok(sessions.values().cloned().collect::<Vec<_>>())
 }
+
+/// Primary display refresh rate from system_profiler ("@ 60.00Hz" or "Refresh Rate: 120 Hz")
+#[cfg(target_os = "macos")]
+async fn detect_refresh_rate() -> Option<f64> {
+    let output = tokio::process::Command::new("system_profiler")
+        .arg("SPDisplaysDataType")
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    stdout.lines().find_map(|line| {
+        let line = line.trim();
+        let value = if let Some(rest) = line.strip_prefix("Refresh Rate:") {
+            rest
+        } else {
+            line.rsplit_once('@')?.1
+        };
+        value.trim().trim_end_matches("Hz").trim().parse().ok()
+    })
+}
+
+/// Current display frequency of the primary monitor from EnumDisplaySettingsW
+#[cfg(target_os = "windows")]
+async fn detect_refresh_rate() -> Option<f64> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Graphics::Gdi::{EnumDisplaySettingsW, DEVMODEW, ENUM_CURRENT_SETTINGS};
+
+    let mut devmode = DEVMODEW {
+        dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+        ..Default::default()
+    };
+    // SAFETY: devmode is a properly sized DEVMODEW and a null device name means the primary display
+    let found = unsafe { EnumDisplaySettingsW(PCWSTR::null(), ENUM_CURRENT_SETTINGS, &mut devmode) };
+    // Frequencies of 0 or 1 mean "hardware default"
+    (found.as_bool() && devmode.dmDisplayFrequency > 1).then_some(devmode.dmDisplayFrequency as f64)
+}
+
+/// Active mode of the first connected output from xrandr, marked with '*'
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+async fn detect_refresh_rate() -> Option<f64> {
+    let output = tokio::process::Command::new("xrandr")
+        .arg("--current")
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    stdout.lines().find_map(|line| {
+        line.split_whitespace()
+            .find(|rate| rate.contains('*'))
+            .and_then(|rate| rate.trim_end_matches(['*', '+']).parse().ok())
+    })
+}
+
+/// The refresh rate itself up to 75 Hz, otherwise the fastest whole-number divisor of it at or below 75 fps,
+/// e.g. 120 Hz -> 60 fps and 144 Hz -> 72 fps, so every recorded frame is the same number of displayed frames apart
+fn recommend_recording_fps(refresh_hz: f64) -> (f64, String) {
+    // Judge divisors against the nominal rate so 59.94 Hz style rates keep their exact fraction
+    let nominal_hz = refresh_hz.round().max(1.0) as u32;
+    if nominal_hz <= MAX_RECOMMENDED_FPS {
+        return (
+            refresh_hz,
+            format!("Recording at the {:.0} Hz refresh rate captures every displayed frame", refresh_hz),
+        );
+    }
+
+    let divisor = (2..=nominal_hz)
+        .find(|divisor| nominal_hz.is_multiple_of(*divisor) && nominal_hz / divisor <= MAX_RECOMMENDED_FPS)
+        .unwrap_or(nominal_hz);
+    if nominal_hz / divisor < MIN_RECOMMENDED_FPS {
+        return (
+            DEFAULT_REFRESH_HZ,
+            format!(
+                "{:.0} Hz has no whole divisor between {} and {} fps, so {} fps is used with slightly uneven frame pacing",
+                refresh_hz, MIN_RECOMMENDED_FPS, MAX_RECOMMENDED_FPS, DEFAULT_REFRESH_HZ
+            ),
+        );
+    }
+
+    (
+        refresh_hz / divisor as f64,
+        format!(
+            "Keeping 1 in {} frames of {:.0} Hz keeps frame pacing even without the file size of recording every frame",
+            divisor, refresh_hz
+        ),
+    )
+}
+
+#[command]
+pub async fn get_fps_recommendation(_app: AppHandle) -> Result<FpsRecommendation, String> {
+    let (monitor_refresh_hz, detected) = match detect_refresh_rate().await {
+        Some(hz) if hz > 0.0 => (hz, true),
+        _ => (DEFAULT_REFRESH_HZ, false),
+    };
+
+    let (recommended_recording_fps, mut reason) = recommend_recording_fps(monitor_refresh_hz);
+    if !detected {
+        reason = format!("Could not detect the refresh rate, assuming {} Hz. {}", DEFAULT_REFRESH_HZ, reason);
+    }
+
+    Ok(FpsRecommendation {
+        monitor_refresh_hz,
+        recommended_recording_fps,
+        reason,
+    })
+}
+
+/// Duration and video packet count of a rescued file, or None if it still isn't readable
+fn probe_recovered_media(app: &AppHandle, file_path: &str) -> Option<(f64, u64)> {
+    let ffprobe_path = get_ffprobe_path(app).ok()?;
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "error",
+            "-count_packets",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=nb_read_packets:format=duration",
+            "-of", "json",
+            file_path,
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let duration = json["format"]["duration"].as_str()?.parse().ok()?;
+    let frames = json["streams"][0]["nb_read_packets"].as_str()?.parse().ok()?;
+    Some((duration, frames))
+}
+
+/// Rebuild the moov atom with untrunc, using a short clip encoded like our recordings as the template
+fn rescue_with_reference(app: &AppHandle, input_path: &str, output_path: &str) -> Result<(), String> {
+    let work_dir = app_temp_dir(app)
+        .join("clipforge_rescue")
+        .join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let result = (|| {
+        // Same codec settings start_screen_recording uses, so the sample tables line up
+        let reference_path = work_dir.join("reference.mp4").to_string_lossy().to_string();
+        let ffmpeg_path = get_ffmpeg_path(app)?;
+        let output = Command::new(ffmpeg_path)
+            .args([
+                "-y",
+                "-f", "lavfi", "-i", "nullsrc=size=1920x1080:rate=30",
+                "-f", "lavfi", "-i", "anullsrc=channel_layout=stereo:sample_rate=48000",
+                "-t", "0.1",
+                "-c:v", "libx264", "-preset", "medium", "-crf", "23", "-pix_fmt", "yuv420p",
+                "-c:a", "aac", "-b:a", "128k",
+                &reference_path,
+            ])
+            .output()
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+
+        // untrunc writes <input>_fixed.mp4, so run it on a copy inside the work dir
+        let broken_path = work_dir.join("broken.mp4");
+        std::fs::copy(input_path, &broken_path)
+            .map_err(|e| format!("Failed to copy recording: {}", e))?;
+        let untrunc_path = get_binary_path(app, "untrunc")?;
+        let output = Command::new(untrunc_path)
+            .arg(&reference_path)
+            .arg(&broken_path)
+            .output()
+            .map_err(|e| format!("untrunc is not available: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("untrunc failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+
+        std::fs::copy(work_dir.join("broken.mp4_fixed.mp4"), output_path)
+            .map_err(|e| format!("Failed to save rescued recording: {}", e))?;
+        Ok(())
+    })();
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+    result
+}
+
+#[command]
+pub async fn rescue_partial_recording(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+) -> Result<RescueResult, String> {
+    if !std::path::Path::new(&input_path).exists() {
+        return Err(format!("Recording not found: {}", input_path));
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let remux_strategies: [(&str, Vec<&str>); 2] = [
+        // Fragmented MP4 remuxes cleanly even without a final moov
+        ("stream_copy", vec!["-y", "-i", &input_path, "-c", "copy", &output_path]),
+        ("regenerate_timestamps", vec!["-y", "-fflags", "+genpts", "-i", &input_path, "-c", "copy", &output_path]),
+    ];
+
+    for (strategy, args) in remux_strategies {
+        log::info!("Trying rescue strategy {} for {}", strategy, input_path);
+        let output = Command::new(&ffmpeg_path)
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        if !output.status.success() {
+            continue;
+        }
+        if let Some((duration, frames)) = probe_recovered_media(&app, &output_path) {
+            if frames > 0 {
+                return Ok(RescueResult {
+                    success: true,
+                    strategy_used: strategy.to_string(),
+                    recovered_duration_seconds: duration,
+                    frames_recovered: frames,
+                });
+            }
+        }
+    }
+
+    log::info!("Trying rescue strategy reference_reconstruction for {}", input_path);
+    match rescue_with_reference(&app, &input_path, &output_path) {
+        Ok(()) => {
+            if let Some((duration, frames)) = probe_recovered_media(&app, &output_path) {
+                return Ok(RescueResult {
+                    success: frames > 0,
+                    strategy_used: "reference_reconstruction".to_string(),
+                    recovered_duration_seconds: duration,
+                    frames_recovered: frames,
+                });
+            }
+        }
+        Err(e) => log::warn!("Reference reconstruction failed: {}", e),
+    }
+
+    let _ = std::fs::remove_file(&output_path);
+
+    Ok(RescueResult {
+        success: false,
+        strategy_used: "none".to_string(),
+        recovered_duration_seconds: 0.0,
+        frames_recovered: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: i32, y: i32, width: u32, height: u32) -> MonitorInfo {
+        MonitorInfo {
+            id: "monitor".to_string(),
+            name: "Monitor".to_string(),
+            x,
+            y,
+            width,
+            height,
+            is_primary: false,
+            scale_factor: 1.0,
+        }
+    }
+
+    fn source(x: i32, y: i32, width: u32, height: u32) -> CaptureSource {
+        CaptureSource {
+            id: "source".to_string(),
+            name: "Source".to_string(),
+            source_type: "monitor".to_string(),
+            device_id: "0".to_string(),
+            x,
+            y,
+            width,
+            height,
+            is_active: true,
+        }
+    }
+
+    fn bounds(source: &CaptureSource) -> (i32, i32, u32, u32) {
+        (source.x, source.y, source.width, source.height)
+    }
+
+    #[test]
+    fn source_inside_monitor_is_unchanged() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(100, 100, 640, 480);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (100, 100, 640, 480));
+    }
+
+    #[test]
+    fn source_matching_monitor_exactly_is_unchanged() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(0, 0, 1920, 1080);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (0, 0, 1920, 1080));
+    }
+
+    #[test]
+    fn source_off_screen_is_rejected() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(5000, 5000, 640, 480);
+        assert!(validate_capture_source_bounds(&mut s, &monitors).is_err());
+    }
+
+    #[test]
+    fn source_touching_only_the_edge_is_rejected() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(1920, 0, 640, 480);
+        assert!(validate_capture_source_bounds(&mut s, &monitors).is_err());
+    }
+
+    #[test]
+    fn zero_sized_source_is_rejected() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(100, 100, 0, 480);
+        assert!(validate_capture_source_bounds(&mut s, &monitors).is_err());
+    }
+
+    #[test]
+    fn no_monitors_rejects_everything() {
+        let mut s = source(0, 0, 640, 480);
+        assert!(validate_capture_source_bounds(&mut s, &[]).is_err());
+    }
+
+    #[test]
+    fn partial_overlap_is_clamped() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(1600, 900, 640, 480);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (1600, 900, 320, 180));
+    }
+
+    #[test]
+    fn negative_origin_is_clamped() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(-100, -50, 640, 480);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (0, 0, 540, 430));
+    }
+
+    #[test]
+    fn spanning_monitors_clamps_to_largest_overlap() {
+        let monitors = [monitor(0, 0, 2560, 1600), monitor(2560, 0, 1920, 1080)];
+        let mut s = source(2460, 0, 640, 480);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (2560, 0, 540, 480));
+    }
+
+    #[test]
+    fn monitor_with_negative_origin_is_supported() {
+        let monitors = [monitor(0, 0, 1920, 1080), monitor(-1280, 0, 1280, 1024)];
+        let mut s = source(-1000, 100, 640, 480);
+        validate_capture_source_bounds(&mut s, &monitors).unwrap();
+        assert_eq!(bounds(&s), (-1000, 100, 640, 480));
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[test]
+    fn parses_xrandr_outputs() {
+        let output = "Screen 0: minimum 320 x 200, current 4480 x 1440, maximum 16384 x 16384
+eDP-1 connected 2560x1440+1920+0 (normal left inverted right x axis y axis) 309mm x 174mm
+   2560x1440     60.00*+
+HDMI-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+   1920x1080     60.00*+  50.00
+DP-1 disconnected (normal left inverted right x axis y axis)
+DP-2 connected (normal left inverted right x axis y axis)
+";
+        let monitors = parse_xrandr_monitors(output, 2.0);
+        assert_eq!(monitors.len(), 2);
+
+        assert_eq!(monitors[0].id, "eDP-1");
+        assert_eq!((monitors[0].x, monitors[0].y, monitors[0].width, monitors[0].height), (1920, 0, 2560, 1440));
+        assert!(!monitors[0].is_primary);
+
+        assert_eq!(monitors[1].id, "HDMI-1");
+        assert!(monitors[1].is_primary);
+        assert_eq!(monitors[1].scale_factor, 2.0);
+    }
+
+    #[test]
+    fn large_values_do_not_overflow() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let mut s = source(i32::MAX - 10, 0, u32::MAX, 480);
+        assert!(validate_capture_source_bounds(&mut s, &monitors).is_err());
+    }
+
+    #[test]
+    fn fps_recommendation_divides_the_refresh_rate() {
+        let table = [
+            (30.0, 30.0),
+            (59.94, 59.94),
+            (60.0, 60.0),
+            (75.0, 75.0),
+            (90.0, 45.0),
+            (100.0, 50.0),
+            (119.88, 59.94),
+            (120.0, 60.0),
+            (144.0, 72.0),
+            (165.0, 55.0),
+            (240.0, 60.0),
+            (360.0, 72.0),
+        ];
+        for (refresh_hz, expected_fps) in table {
+            let (fps, _) = recommend_recording_fps(refresh_hz);
+            assert!((fps - expected_fps).abs() < 1e-9, "{} Hz: expected {} fps, got {}", refresh_hz, expected_fps, fps);
+        }
+    }
+
+    #[test]
+    fn refresh_rates_without_a_usable_divisor_fall_back_to_60_fps() {
+        // 151 is prime and 143 only divides into 11 or 13 fps
+        for refresh_hz in [151.0, 143.0] {
+            let (fps, reason) = recommend_recording_fps(refresh_hz);
+            assert_eq!(fps, 60.0);
+            assert!(reason.contains("uneven"), "{}", reason);
+        }
+    }
+
+    #[test]
+    fn recording_region_is_clamped_onto_the_monitor() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        let region = fit_recording_region(-100, -50, 641, 481, &monitors, false).unwrap();
+        assert_eq!(region, RecordingRegion { x: 0, y: 0, width: 540, height: 430 });
+    }
+
+    #[test]
+    fn recording_region_off_screen_is_rejected() {
+        let monitors = [monitor(0, 0, 1920, 1080)];
+        assert!(fit_recording_region(-800, 0, 640, 480, &monitors, false).is_err());
+        assert!(fit_recording_region(1919, 0, 1, 480, &monitors, false).is_err());
+    }
+
+    #[test]
+    fn recording_region_in_points_is_scaled_to_pixels() {
+        // A 2x display reported in pixels, 1440x900 points
+        let mut retina = monitor(0, 0, 2880, 1800);
+        retina.scale_factor = 2.0;
+        let region = fit_recording_region(100, 50, 640, 480, &[retina.clone()], true).unwrap();
+        assert_eq!(region, RecordingRegion { x: 200, y: 100, width: 1280, height: 960 });
+
+        // Clamped in points before scaling, so the region ends at the display edge
+        let region = fit_recording_region(1200, 800, 640, 480, &[retina], true).unwrap();
+        assert_eq!(region, RecordingRegion { x: 2400, y: 1600, width: 480, height: 200 });
+    }
+}
@@ -0,0 +1,242 @@
+use tauri::command;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubtitleEntry {
+    pub index: usize,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub total_entries: usize,
+    pub conflicts_resolved: usize,
+    pub output_path: String,
+}
+
+/// Parse an SRT timestamp like "00:01:02,345" into seconds
+fn parse_srt_timestamp(timestamp: &str) -> Option<f64> {
+    let timestamp = timestamp.trim().replace('.', ",");
+    let (clock, millis) = timestamp.split_once(',')?;
+    let parts: Vec<&str> = clock.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let hours: f64 = parts[0].trim().parse().ok()?;
+    let minutes: f64 = parts[1].trim().parse().ok()?;
+    let seconds: f64 = parts[2].trim().parse().ok()?;
+    let millis: f64 = millis.trim().parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds + millis / 1000.0)
+}
+
+/// Format seconds as an SRT timestamp like "00:01:02,345"
+fn format_srt_timestamp(seconds: f64) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        total_millis / 3_600_000,
+        (total_millis / 60_000) % 60,
+        (total_millis / 1000) % 60,
+        total_millis % 1000
+    )
+}
+
+/// Parse the contents of an SRT file into subtitle entries
+pub fn parse_srt(content: &str) -> Result<Vec<SubtitleEntry>, String> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut entries = Vec::new();
+
+    for block in content.split("\n\n").map(|b| b.trim()).filter(|b| !b.is_empty()) {
+        let mut lines = block.lines();
+        let mut timing_line = lines.next().unwrap_or("");
+
+        // The numeric index line is optional in practice
+        let index = if timing_line.contains("-->") {
+            entries.len() + 1
+        } else {
+            let index = timing_line.trim().parse::<usize>().unwrap_or(entries.len() + 1);
+            timing_line = lines.next().unwrap_or("");
+            index
+        };
+
+        let (start, end) = timing_line
+            .split_once("-->")
+            .ok_or_else(|| format!("Invalid SRT timing line: {}", timing_line))?;
+        // Ignore any positioning data after the end timestamp
+        let end = end.split_whitespace().next().unwrap_or("");
+        let start = parse_srt_timestamp(start)
+            .ok_or_else(|| format!("Invalid SRT timestamp: {}", start.trim()))?;
+        let end = parse_srt_timestamp(end)
+            .ok_or_else(|| format!("Invalid SRT timestamp: {}", end))?;
+
+        entries.push(SubtitleEntry {
+            index,
+            start,
+            end,
+            text: lines.collect::<Vec<_>>().join("\n"),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Serialize subtitle entries as SRT, renumbering them from 1
+pub fn format_srt(entries: &[SubtitleEntry]) -> String {
+    let mut output = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_srt_timestamp(entry.start),
+            format_srt_timestamp(entry.end),
+            entry.text
+        ));
+    }
+    output
+}
+
+fn overlaps(a: &SubtitleEntry, b: &SubtitleEntry) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+#[command]
+pub async fn merge_subtitle_files(
+    srt_path_a: String,
+    srt_path_b: String,
+    output_path: String,
+    conflict_strategy: String,
+) -> Result<MergeResult, String> {
+    let content_a = fs::read_to_string(&srt_path_a)
+        .map_err(|e| format!("Failed to read {}: {}", srt_path_a, e))?;
+    let content_b = fs::read_to_string(&srt_path_b)
+        .map_err(|e| format!("Failed to read {}: {}", srt_path_b, e))?;
+
+    let entries_a = parse_srt(&content_a)?;
+    let entries_b = parse_srt(&content_b)?;
+
+    let strategy = if conflict_strategy.is_empty() { "layer" } else { conflict_strategy.as_str() };
+    let mut conflicts_resolved = 0;
+
+    let mut merged: Vec<SubtitleEntry> = match strategy {
+        // Overlapping entries are both kept on screen, so layering leaves every conflict in place
+        "layer" => entries_a.into_iter().chain(entries_b).collect(),
+        "prefer_a" | "prefer_b" => {
+            let (winners, losers) = if strategy == "prefer_a" {
+                (entries_a, entries_b)
+            } else {
+                (entries_b, entries_a)
+            };
+            let kept_losers: Vec<SubtitleEntry> = losers
+                .into_iter()
+                .filter(|loser| {
+                    let conflicting = winners.iter().any(|winner| overlaps(winner, loser));
+                    if conflicting {
+                        conflicts_resolved += 1;
+                    }
+                    !conflicting
+                })
+                .collect();
+            winners.into_iter().chain(kept_losers).collect()
+        }
+        "interleave" => entries_a.into_iter().chain(entries_b).collect(),
+        _ => {
+            return Err(format!(
+                "Unknown conflict strategy: {}. Use layer, interleave, prefer_a or prefer_b",
+                conflict_strategy
+            ))
+        }
+    };
+
+    merged.sort_by(|a, b| {
+        a.start
+            .partial_cmp(&b.start)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.end.partial_cmp(&b.end).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    if strategy == "interleave" {
+        // Cut each entry off where the next one begins so only one is on screen at a time.
+        // The earlier entry's text is not repeated afterwards: if it would have outlasted the
+        // later entry, the later entry is held until that end instead.
+        let mut interleaved: Vec<SubtitleEntry> = Vec::with_capacity(merged.len());
+        for mut entry in merged {
+            if let Some(previous) = interleaved.last_mut() {
+                if entry.start < previous.end {
+                    conflicts_resolved += 1;
+                    if entry.start > previous.start {
+                        let previous_end = previous.end;
+                        previous.end = entry.start;
+                        if previous_end > entry.end {
+                            entry.end = previous_end;
+                        }
+                    } else if entry.end > previous.end {
+                        // Same start time: show the later entry once the earlier one ends
+                        entry.start = previous.end;
+                    } else {
+                        // Same start and ends no later than the earlier entry, so it would never be shown
+                        continue;
+                    }
+                }
+            }
+            interleaved.push(entry);
+        }
+        merged = interleaved;
+    }
+
+    for (i, entry) in merged.iter_mut().enumerate() {
+        entry.index = i + 1;
+    }
+
+    fs::write(&output_path, format_srt(&merged))
+        .map_err(|e| format!("Failed to write merged subtitles: {}", e))?;
+
+    Ok(MergeResult {
+        total_entries: merged.len(),
+        conflicts_resolved,
+        output_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_crlf_files() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nWorld\r\n";
+        let entries = parse_srt(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].start, entries[0].end), (1.0, 2.5));
+        assert_eq!(entries[0].text, "Hello");
+        assert_eq!(entries[1].index, 2);
+        assert_eq!(entries[1].text, "World");
+    }
+
+    #[test]
+    fn keeps_multi_line_cues_together() {
+        let content = "1\n00:01:02,345 --> 00:01:04,000 X1:10 X2:20\nFirst line\nSecond line\n\n00:01:05,000 --> 00:01:06,000\nNo index\n";
+        let entries = parse_srt(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].start, 62.345);
+        assert_eq!(entries[0].end, 64.0);
+        assert_eq!(entries[0].text, "First line\nSecond line");
+        assert_eq!(entries[1].index, 2);
+        assert_eq!(entries[1].text, "No index");
+    }
+
+    #[test]
+    fn rejects_malformed_timestamps() {
+        for content in [
+            "1\n00:01,000 --> 00:00:02,000\nMissing hours\n",
+            "1\n00:00:01,000 --> 00:00:xx,000\nNot a number\n",
+            "1\n00:00:01,000 00:00:02,000\nNo arrow\n",
+        ] {
+            assert!(parse_srt(content).is_err(), "accepted {:?}", content);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+/// One level of FFmpeg's av_get_token unescaping: quotes group literally, a backslash makes the next character literal,
+/// and unquoted whitespace at either end is dropped
+fn unescape_level(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut kept_len = 0;
+    let mut chars = value.trim_start().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                unescaped.extend(chars.next());
+                kept_len = unescaped.len();
+            }
+            '\'' => {
+                for quoted in chars.by_ref() {
+                    if quoted == '\'' {
+                        break;
+                    }
+                    unescaped.push(quoted);
+                }
+                kept_len = unescaped.len();
+            }
+            _ => {
+                unescaped.push(c);
+                if !c.is_whitespace() {
+                    kept_len = unescaped.len();
+                }
+            }
+        }
+    }
+    unescaped.truncate(kept_len);
+    unescaped
+}
+
+/// A filter option value as the filter sees it, after the graph and option parsing levels
+pub(crate) fn unescape_filter_option(value: &str) -> String {
+    unescape_level(&unescape_level(value))
+}
+
+/// Filter names of a chain, splitting on commas outside quoted option values
+pub(crate) fn filter_names(chain: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in chain.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            ',' if !quoted => {
+                names.push(&chain[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    names.push(&chain[start..]);
+    names.into_iter().map(|filter| filter.split('=').next().unwrap()).collect()
+}
+
+/// Assert a filter chain has balanced quotes and uses only the `allowed` filters
+pub(crate) fn assert_filter_chain(chain: &str, allowed: &[&str]) {
+    assert_eq!(chain.matches('\'').count() % 2, 0, "unbalanced quotes in {}", chain);
+    let names = filter_names(chain);
+    assert!(names.iter().all(|name| allowed.contains(name)), "{:?} uses a filter outside {:?}", names, allowed);
+}
+
+/// Render the lavfi `source` through `filter` with the ffmpeg on PATH, for tests marked `#[ignore = "requires ffmpeg"]`
+pub(crate) fn render_filter(source: &str, filter: &str, output_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("clipforge_filter_tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    let output_path = dir.join(output_name);
+
+    let output = std::process::Command::new("ffmpeg")
+        .args(["-y", "-f", "lavfi", "-i", source, "-vf", filter, "-pix_fmt", "yuv420p"])
+        .arg(&output_path)
+        .output()
+        .expect("ffmpeg must be installed to run this test");
+    assert!(output.status.success(), "{}: {}", filter, String::from_utf8_lossy(&output.stderr));
+
+    output_path
+}
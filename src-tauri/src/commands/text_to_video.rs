@@ -1,7 +1,30 @@
-use tauri::{command, AppHandle};
+use tauri::{command, AppHandle, Manager};
 use std::fs;
 use tokio::process::Command;
-use crate::commands::binary_utils::get_ffmpeg_path;
+use serde::{Deserialize, Serialize};
+use crate::commands::ai_styler::escape_filter_path;
+use crate::commands::binary_utils::{app_temp_dir, get_ffmpeg_path, get_ffprobe_path};
+use crate::commands::ffmpeg::{add_audio_track, get_video_metadata};
+use crate::commands::filesystem::get_available_fonts;
+use crate::commands::openai::{text_to_speech_file, OpenAiError};
+
+// Transition names accepted by FFmpeg's xfade filter
+pub(crate) const XFADE_TRANSITIONS: &[&str] = &[
+    "fade", "fadeblack", "fadewhite", "dissolve", "wipeleft", "wiperight", "wipeup", "wipedown",
+    "slideleft", "slideright", "slideup", "slidedown", "circleopen", "circleclose", "radial",
+    "smoothleft", "smoothright", "pixelize", "zoomin",
+];
+
+const STITCH_FPS: u32 = 30;
+
+// Images without narration stay on screen this long, over silence
+const UNNARRATED_IMAGE_SECONDS: f64 = 3.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NarrationSegment {
+    pub image_index: usize,
+    pub text: String,
+}
 
 /// Generate a video from text using DALL-E images and FFmpeg
 #[command]
@@ -18,7 +41,7 @@ pub async fn generate_text_to_video(
         .map_err(|_| "OPENAI_API_KEY environment variable not set")?;
 
     // Create temporary directory for images
-    let temp_dir = std::env::temp_dir().join("clipforge_text_to_video");
+    let temp_dir = app_temp_dir(&app).join("clipforge_text_to_video");
     fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
@@ -38,7 +61,7 @@ pub async fn generate_text_to_video(
         };
 
         // Generate image using DALL-E
-        let image_bytes = crate::commands::openai::generate_dalle_image(scene_prompt, api_key.clone())
+        let image_bytes = crate::commands::openai::generate_dalle_image(app.state(), scene_prompt, api_key.clone())
             .await
             .map_err(|e| format!("Failed to generate image for scene {}: {}", i + 1, e))?;
 
@@ -55,7 +78,7 @@ pub async fn generate_text_to_video(
 
     // Clean up temporary files
     if let Err(e) = fs::remove_dir_all(&temp_dir) {
-        eprintln!("Warning: Failed to clean up temp directory: {}", e);
+        log::warn!("Failed to clean up temp directory: {}", e);
     }
 
     Ok(format!("Video generated successfully: {}", output_path))
@@ -74,7 +97,7 @@ async fn create_video_from_images(
     }
 
     // Create input file list for FFmpeg
-    let input_file = std::env::temp_dir().join("ffmpeg_input.txt");
+    let input_file = app_temp_dir(app).join("ffmpeg_input.txt");
     let mut input_content = String::new();
     
     for image_path in image_paths {
@@ -160,13 +183,30 @@ async fn create_video_from_images(
     Ok(())
 }
 
+/// Build the `:fontfile=` drawtext option for an installed font, or nothing for FFmpeg's default
+async fn drawtext_font_option(font_path: Option<String>) -> Result<String, String> {
+    let Some(font_path) = font_path else {
+        return Ok(String::new());
+    };
+
+    let fonts = get_available_fonts().await?;
+    if !fonts.iter().any(|font| font.path == font_path) {
+        return Err(format!("Font is not installed on this system: {}", font_path));
+    }
+
+    Ok(format!(":fontfile='{}'", escape_filter_path(std::path::Path::new(&font_path))))
+}
+
 /// Generate a simple video with text overlay (fallback when no images)
 #[command]
 pub async fn generate_text_overlay_video(
     text: String,
     duration: f64,
     output_path: String,
+    font_path: Option<String>,
 ) -> Result<String, String> {
+    let font_option = drawtext_font_option(font_path).await?;
+
     let mut ffmpeg_cmd = Command::new("ffmpeg");
     
     ffmpeg_cmd
@@ -176,8 +216,9 @@ pub async fn generate_text_overlay_video(
         .arg(format!("color=c=black:size=1920x1080:duration={}", duration))
         .arg("-vf")
         .arg(format!(
-            "drawtext=text='{}':fontcolor=white:fontsize=60:x=(w-text_w)/2:y=(h-text_h)/2",
-            text.replace("'", "\\'")
+            "drawtext=text='{}'{}:fontcolor=white:fontsize=60:x=(w-text_w)/2:y=(h-text_h)/2",
+            escape_drawtext(&text),
+            font_option
         ))
         .arg("-c:v")
         .arg("libx264")
@@ -198,3 +239,734 @@ pub async fn generate_text_overlay_video(
 
     Ok(format!("Text overlay video generated: {}", output_path))
 }
+
+/// Build a video from local images with a Ken Burns zoom on each one
+#[command]
+pub async fn stitch_images_to_video(
+    app: AppHandle,
+    image_paths: Vec<String>,
+    output_path: String,
+    duration_per_image: f64,
+    transition_duration: f64,
+    transition_type: String,
+    audio_path: Option<String>,
+) -> Result<String, String> {
+    if image_paths.is_empty() {
+        return Err("No images to process".to_string());
+    }
+    if duration_per_image <= 0.0 {
+        return Err("Duration per image must be greater than zero".to_string());
+    }
+    if transition_duration < 0.0 {
+        return Err("Transition duration cannot be negative".to_string());
+    }
+    if !XFADE_TRANSITIONS.contains(&transition_type.as_str()) {
+        return Err(format!(
+            "Unknown transition type: {}. Supported: {}",
+            transition_type,
+            XFADE_TRANSITIONS.join(", ")
+        ));
+    }
+
+    for image_path in &image_paths {
+        let path = std::path::Path::new(image_path);
+        if !path.exists() {
+            return Err(format!("Image not found: {}", image_path));
+        }
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+        if !["jpg", "jpeg", "png"].contains(&extension.as_str()) {
+            return Err(format!("Unsupported image format (JPEG or PNG only): {}", image_path));
+        }
+    }
+    if let Some(audio) = &audio_path {
+        if !std::path::Path::new(audio).exists() {
+            return Err(format!("Audio file not found: {}", audio));
+        }
+    }
+
+    let durations = vec![duration_per_image; image_paths.len()];
+    render_image_sequence(
+        &app,
+        &image_paths,
+        &output_path,
+        &durations,
+        transition_duration,
+        &transition_type,
+        audio_path.as_deref(),
+    )
+    .await?;
+
+    Ok(output_path)
+}
+
+/// Ken Burns each image for its own duration and join them with xfade or concat
+async fn render_image_sequence(
+    app: &AppHandle,
+    image_paths: &[String],
+    output_path: &str,
+    durations: &[f64],
+    transition_duration: f64,
+    transition_type: &str,
+    audio_path: Option<&str>,
+) -> Result<(), String> {
+    let use_transitions = transition_duration > 0.0 && image_paths.len() > 1;
+
+    let mut filter_parts = Vec::new();
+    for (i, duration) in durations.iter().enumerate() {
+        // Each clip runs long enough to stay fully visible for its own duration plus the crossfade
+        let clip_duration = if use_transitions { duration + transition_duration } else { *duration };
+        let clip_frames = (clip_duration * STITCH_FPS as f64).round().max(1.0) as u32;
+        let zoom_step = 0.2 / clip_frames as f64;
+        filter_parts.push(format!(
+            "[{}:v]scale=1920:1080:force_original_aspect_ratio=decrease,pad=1920:1080:(ow-iw)/2:(oh-ih)/2,setsar=1,\
+zoompan=z='min(zoom+{:.6},1.2)':x='iw/2-(iw/zoom/2)':y='ih/2-(ih/zoom/2)':d={}:s=1920x1080:fps={},format=yuv420p[v{}]",
+            i, zoom_step, clip_frames, STITCH_FPS, i
+        ));
+    }
+
+    if use_transitions {
+        let mut previous = "v0".to_string();
+        let mut offset = 0.0;
+        for i in 1..image_paths.len() {
+            offset += durations[i - 1];
+            let label = if i == image_paths.len() - 1 { "outv".to_string() } else { format!("x{}", i) };
+            filter_parts.push(format!(
+                "[{}][v{}]xfade=transition={}:duration={}:offset={}[{}]",
+                previous, i, transition_type, transition_duration, offset, label
+            ));
+            previous = label;
+        }
+    } else {
+        let inputs: String = (0..image_paths.len()).map(|i| format!("[v{}]", i)).collect();
+        filter_parts.push(format!("{}concat=n={}:v=1:a=0[outv]", inputs, image_paths.len()));
+    }
+
+    let filter_complex = filter_parts.join(";");
+    println!("Stitch filter complex: {}", filter_complex);
+
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let mut ffmpeg_cmd = Command::new(ffmpeg_path);
+    ffmpeg_cmd.arg("-y");
+    for image_path in image_paths {
+        ffmpeg_cmd.arg("-i").arg(image_path);
+    }
+    if let Some(audio) = audio_path {
+        ffmpeg_cmd.arg("-i").arg(audio);
+    }
+    ffmpeg_cmd
+        .arg("-filter_complex")
+        .arg(&filter_complex)
+        .arg("-map")
+        .arg("[outv]");
+    if audio_path.is_some() {
+        // End the video with the audio track regardless of how many images there are
+        ffmpeg_cmd
+            .arg("-map")
+            .arg(format!("{}:a", image_paths.len()))
+            .arg("-c:a")
+            .arg("aac")
+            .arg("-b:a")
+            .arg("192k")
+            .arg("-shortest");
+    }
+    ffmpeg_cmd
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-r")
+        .arg(STITCH_FPS.to_string())
+        .arg(output_path);
+
+    let output = ffmpeg_cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(())
+}
+
+/// Render a name/title lower third on a transparent 1920x200 strip for compositing
+#[command]
+pub async fn generate_lower_third(
+    app: AppHandle,
+    name: String,
+    title: String,
+    color: String,
+    animation: String,
+    duration: f64,
+    output_path: String,
+) -> Result<String, String> {
+    if duration <= 0.0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+    if !output_path.to_lowercase().ends_with(".webm") {
+        return Err("Lower thirds are rendered as WebM to keep the alpha channel".to_string());
+    }
+
+    let bar_color = color.trim_start_matches('#');
+    if bar_color.is_empty() || !bar_color.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid color: {}", color));
+    }
+    // Hex colors go to FFmpeg as 0xRRGGBB, named colors pass through unchanged
+    let bar_color = if bar_color.len() == 6 && bar_color.chars().all(|c| c.is_ascii_hexdigit()) {
+        format!("0x{}", bar_color)
+    } else {
+        bar_color.to_string()
+    };
+
+    let bar = format!(
+        "color=c=0x00000000:size=1920x200:rate=30:duration={},format=rgba,\
+drawbox=x=40:y=30:w=900:h=140:color={}@0.85:t=fill,\
+drawtext=text='{}':fontcolor=white:fontsize=56:x=70:y=45,\
+drawtext=text='{}':fontcolor=white@0.85:fontsize=32:x=70:y=115",
+        duration,
+        bar_color,
+        escape_drawtext(&name),
+        escape_drawtext(&title)
+    );
+
+    let filter_complex = match animation.as_str() {
+        // crop cannot change its output size per frame, so slide the bar in with overlay instead
+        "slide_in" => format!(
+            "color=c=0x00000000:size=1920x200:rate=30:duration={},format=rgba[bg];{}[bar];\
+[bg][bar]overlay=x='min(0\\,-W+W*t/0.5)':y=0:format=auto,format=rgba[outv]",
+            duration, bar
+        ),
+        "fade" => format!("{},format=rgba,fade=t=in:d=0.5:alpha=1[outv]", bar),
+        "none" => format!("{}[outv]", bar),
+        _ => {
+            return Err(format!(
+                "Unknown animation: {}. Use slide_in, fade or none",
+                animation
+            ))
+        }
+    };
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-filter_complex")
+        .arg(&filter_complex)
+        .arg("-map")
+        .arg("[outv]")
+        .arg("-c:v")
+        .arg("libvpx-vp9")
+        .arg("-pix_fmt")
+        .arg("yuva420p")
+        .arg("-auto-alt-ref")
+        .arg("0")
+        .arg("-b:v")
+        .arg("0")
+        .arg("-crf")
+        .arg("30")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
+}
+
+/// Render centered text onto a solid background and save it as a single image
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn render_text_to_image(
+    app: AppHandle,
+    text: String,
+    output_path: String,
+    width: u32,
+    height: u32,
+    font_size: u32,
+    text_color: String,
+    bg_color: String,
+    font_path: Option<String>,
+) -> Result<String, String> {
+    let extension = std::path::Path::new(&output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    if !["png", "jpg", "bmp"].contains(&extension.as_str()) {
+        return Err("Output path must end in .png, .jpg or .bmp".to_string());
+    }
+    if !(8..=512).contains(&font_size) {
+        return Err("Font size must be between 8 and 512".to_string());
+    }
+    if width == 0 || height == 0 {
+        return Err("Width and height must be greater than zero".to_string());
+    }
+    // Colors are spliced into the filter graph, so only allow FFmpeg color syntax
+    for color in [&text_color, &bg_color] {
+        if color.is_empty() || !color.chars().all(|c| c.is_ascii_alphanumeric() || "#@.".contains(c)) {
+            return Err(format!("Invalid color: {}", color));
+        }
+    }
+
+    let font_option = drawtext_font_option(font_path).await?;
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-f")
+        .arg("lavfi")
+        .arg("-i")
+        .arg(format!("color=c={}:size={}x{}", bg_color, width, height))
+        .arg("-vf")
+        .arg(format!(
+            "drawtext=text='{}'{}:fontsize={}:fontcolor={}:x=(w-text_w)/2:y=(h-text_h)/2",
+            escape_drawtext(&text),
+            font_option,
+            font_size,
+            text_color
+        ))
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
+}
+
+/// Duration of a media file in seconds according to ffprobe
+async fn probe_duration(app: &AppHandle, path: &std::path::Path) -> Result<f64, String> {
+    let ffprobe_path = get_ffprobe_path(app)?;
+    let output = Command::new(ffprobe_path)
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("format=duration")
+        .arg("-of")
+        .arg("csv=p=0")
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| format!("Could not read duration of {}", path.display()))
+}
+
+/// Build a slideshow where each image stays up for as long as its narration takes to speak
+#[command]
+pub async fn generate_narrated_slideshow(
+    app: AppHandle,
+    image_paths: Vec<String>,
+    narration_segments: Vec<NarrationSegment>,
+    output_path: String,
+    api_key: String,
+    voice: String,
+) -> Result<String, OpenAiError> {
+    if image_paths.is_empty() {
+        return Err(OpenAiError::Local("No images to process".to_string()));
+    }
+    for image_path in &image_paths {
+        if !std::path::Path::new(image_path).exists() {
+            return Err(format!("Image not found: {}", image_path).into());
+        }
+    }
+    if let Some(segment) = narration_segments.iter().find(|segment| segment.image_index >= image_paths.len()) {
+        return Err(format!(
+            "Narration segment refers to image {} but only {} images were given",
+            segment.image_index,
+            image_paths.len()
+        ).into());
+    }
+
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_narrated_slideshow")
+        .join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let result = build_narrated_slideshow(&app, &work_dir, &image_paths, &narration_segments, &output_path, &api_key, &voice).await;
+
+    if let Err(e) = fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+
+    result.map(|_| output_path)
+}
+
+async fn build_narrated_slideshow(
+    app: &AppHandle,
+    work_dir: &std::path::Path,
+    image_paths: &[String],
+    narration_segments: &[NarrationSegment],
+    output_path: &str,
+    api_key: &str,
+    voice: &str,
+) -> Result<(), OpenAiError> {
+    // Several segments may narrate the same image; they play back to back
+    let mut durations = vec![0.0; image_paths.len()];
+    let mut narration_audio: Vec<Vec<std::path::PathBuf>> = vec![Vec::new(); image_paths.len()];
+    for (i, segment) in narration_segments.iter().enumerate() {
+        if segment.text.trim().is_empty() {
+            continue;
+        }
+        let audio_path = work_dir.join(format!("narration_{:03}.mp3", i));
+        if let Err(e) = text_to_speech_file(app, api_key, &segment.text, voice, &audio_path).await {
+            log::error!("Failed to narrate segment {}: {}", i + 1, e);
+            return Err(e);
+        }
+        durations[segment.image_index] += probe_duration(app, &audio_path).await?;
+        narration_audio[segment.image_index].push(audio_path);
+    }
+
+    // Concatenate narration in image order, filling unnarrated images with silence
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let mut ffmpeg_cmd = Command::new(&ffmpeg_path);
+    ffmpeg_cmd.arg("-y");
+    let mut input_count = 0;
+    for (duration, clips) in durations.iter_mut().zip(&narration_audio) {
+        if clips.is_empty() {
+            *duration = UNNARRATED_IMAGE_SECONDS;
+            ffmpeg_cmd
+                .arg("-f")
+                .arg("lavfi")
+                .arg("-t")
+                .arg(UNNARRATED_IMAGE_SECONDS.to_string())
+                .arg("-i")
+                .arg("anullsrc=r=44100:cl=stereo");
+            input_count += 1;
+        }
+        for clip in clips {
+            ffmpeg_cmd.arg("-i").arg(clip);
+            input_count += 1;
+        }
+    }
+
+    // TTS output is mono 24 kHz; normalise everything so concat accepts the mix
+    let mut filter_parts: Vec<String> = (0..input_count)
+        .map(|i| format!("[{}:a]aformat=sample_rates=44100:channel_layouts=stereo[a{}]", i, i))
+        .collect();
+    let inputs: String = (0..input_count).map(|i| format!("[a{}]", i)).collect();
+    filter_parts.push(format!("{}concat=n={}:v=0:a=1[outa]", inputs, input_count));
+
+    let narration_path = work_dir.join("narration.m4a");
+    let output = ffmpeg_cmd
+        .arg("-filter_complex")
+        .arg(filter_parts.join(";"))
+        .arg("-map")
+        .arg("[outa]")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-b:a")
+        .arg("192k")
+        .arg(&narration_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg error: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    // Hard cuts keep each image aligned with its narration; crossfades would shift the timing
+    let video_path = work_dir.join("slideshow.mp4").to_string_lossy().to_string();
+    render_image_sequence(app, image_paths, &video_path, &durations, 0.0, "fade", None).await?;
+
+    add_audio_track(
+        app.clone(),
+        video_path,
+        narration_path.to_string_lossy().to_string(),
+        output_path.to_string(),
+        1.0,
+        false,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Escape text for a drawtext `text='...'` value. FFmpeg unescapes it twice, once parsing the filter graph and once
+/// parsing the filter's options, and drawtext then expands `\` sequences itself, so `\` and `%` need one more level.
+/// A quote closes the graph-level quoting, emits `\'` for the option level and reopens.
+fn escape_drawtext(text: &str) -> String {
+    text.replace('\\', "\\\\\\\\")
+        .replace('\'', "'\\\\\\''")
+        .replace(':', "\\:")
+        .replace('%', "\\\\%")
+}
+
+/// Greedy word wrap for drawtext, which has no wrapping of its own
+fn wrap_text(text: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            last.push('…');
+        }
+    }
+    lines
+}
+
+/// Format seconds as M:SS, or H:MM:SS for an hour or more
+fn format_badge_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (hours, minutes, secs) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
+
+/// Compose a shareable card: midpoint thumbnail on the left, title and description on the right
+#[command]
+pub async fn generate_summary_card(
+    app: AppHandle,
+    input_path: String,
+    title: String,
+    description: String,
+    output_path: String,
+    width: u32,
+    height: u32,
+) -> Result<String, String> {
+    let extension = std::path::Path::new(&output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    if !["png", "jpg", "jpeg", "webp", "bmp"].contains(&extension.as_str()) {
+        return Err("Output path must end in .png, .jpg, .webp or .bmp".to_string());
+    }
+    if width < 320 || height < 180 {
+        return Err("Summary cards must be at least 320x180".to_string());
+    }
+    if title.trim().is_empty() {
+        return Err("Title cannot be empty".to_string());
+    }
+
+    let metadata = get_video_metadata(app.clone(), input_path.clone(), None).await?;
+
+    // Thumbnail fills the left half with a margin; text starts just right of centre
+    let margin = (width / 40).max(8);
+    let thumb_width = (width / 2 - margin * 2) & !1;
+    let thumb_height = (height - margin * 2) & !1;
+    let text_x = width / 2 + margin;
+    let text_width = width / 2 - margin * 2;
+
+    let title_size = (height / 12).max(16);
+    let description_size = (height / 26).max(10);
+    let badge_size = (height / 30).max(10);
+    // Average glyph width is roughly 0.55 em
+    let chars_per_line = |font_size: u32| ((text_width as f64 / (font_size as f64 * 0.55)) as usize).max(8);
+
+    // Prefer an installed bold face for the title, otherwise thicken FFmpeg's default font
+    let bold_font = get_available_fonts()
+        .await
+        .ok()
+        .and_then(|fonts| fonts.into_iter().find(|font| font.style.eq_ignore_ascii_case("bold")))
+        .map(|font| font.path);
+    let title_font = match bold_font {
+        Some(path) => drawtext_font_option(Some(path)).await?,
+        None => ":borderw=2:bordercolor=white".to_string(),
+    };
+
+    let mut filters = vec![
+        format!(
+            "[0:v]scale={}:{}:force_original_aspect_ratio=decrease,\
+drawtext=text='{}':fontsize={}:fontcolor=white:box=1:boxcolor=black@0.7:boxborderw={}:\
+x=w-text_w-{}:y=h-text_h-{}[thumb]",
+            thumb_width,
+            thumb_height,
+            escape_drawtext(&format_badge_duration(metadata.duration)),
+            badge_size,
+            badge_size / 3,
+            badge_size,
+            badge_size
+        ),
+        format!(
+            "[1:v][thumb]overlay=x={}+({}-w)/2:y=(H-h)/2[card0]",
+            margin, thumb_width
+        ),
+    ];
+
+    let title_lines = wrap_text(&title, chars_per_line(title_size), 3);
+    let description_lines = wrap_text(&description, chars_per_line(description_size), 8);
+    let title_line_height = title_size * 6 / 5;
+    let description_line_height = description_size * 3 / 2;
+    let block_height = title_lines.len() as u32 * title_line_height
+        + margin
+        + description_lines.len() as u32 * description_line_height;
+    let mut y = height.saturating_sub(block_height) / 2;
+
+    let mut drawtexts = Vec::new();
+    for line in &title_lines {
+        drawtexts.push(format!(
+            "drawtext=text='{}'{}:fontsize={}:fontcolor=white:x={}:y={}",
+            escape_drawtext(line),
+            title_font,
+            title_size,
+            text_x,
+            y
+        ));
+        y += title_line_height;
+    }
+    y += margin;
+    for line in &description_lines {
+        drawtexts.push(format!(
+            "drawtext=text='{}':fontsize={}:fontcolor=white@0.8:x={}:y={}",
+            escape_drawtext(line),
+            description_size,
+            text_x,
+            y
+        ));
+        y += description_line_height;
+    }
+    filters.push(format!("[card0]{}[card]", drawtexts.join(",")));
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-ss")
+        .arg((metadata.duration / 2.0).to_string())
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-f")
+        .arg("lavfi")
+        .arg("-i")
+        .arg(format!("color=c=0x1e1e2e:size={}x{}", width, height))
+        .arg("-filter_complex")
+        .arg(filters.join(";"))
+        .arg("-map")
+        .arg("[card]")
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
+}
+
+/// Render a full-HD countdown clip to put in front of a recording
+#[command]
+pub async fn generate_countdown_clip(
+    app: AppHandle,
+    seconds: u32,
+    output_path: String,
+    style: String,
+) -> Result<String, String> {
+    if !(3..=30).contains(&seconds) {
+        return Err("Countdown must be between 3 and 30 seconds".to_string());
+    }
+
+    // ceil so each number is on screen for its whole second rather than flooring to the next one
+    let counter = format!(
+        "drawtext=text='%{{eif\\:ceil({}-t)\\:d}}':fontsize=200:fontcolor=white:x=(w-text_w)/2:y=(h-text_h)/2",
+        seconds
+    );
+    let (background, filter) = match style.as_str() {
+        "simple" => ("0x101010", counter),
+        // Blur and brighten the start of every second so the digit pulses as it changes
+        "flash" => (
+            "0x1e1e2e",
+            format!(
+                "{},gblur=sigma=12:enable='lt(mod(t,1),0.15)',eq=brightness='0.25*max(0,1-4*mod(t,1))':eval=frame",
+                counter
+            ),
+        ),
+        _ => return Err(format!("Unknown countdown style: {}. Use simple or flash", style)),
+    };
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-f")
+        .arg("lavfi")
+        .arg("-i")
+        .arg(format!("color=c={}:size=1920x1080:rate=30:duration={}", background, seconds))
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::unescape_filter_option;
+
+    /// drawtext's own expansion step, where a backslash makes the next character literal
+    fn expand_drawtext(text: &str) -> String {
+        let mut expanded = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => expanded.extend(chars.next()),
+                _ => expanded.push(c),
+            }
+        }
+        expanded
+    }
+
+    #[test]
+    fn drawtext_escaping_survives_both_unescape_levels() {
+        for text in ["It's 100% done: C:\\clips\\intro.mp4", "'quoted'", "a\\'b", "50%:50%", "%{pts}"] {
+            let value = unescape_filter_option(&format!("'{}'", escape_drawtext(text)));
+            assert_eq!(expand_drawtext(&value), text);
+        }
+    }
+}
@@ -1,7 +1,51 @@
 use tauri::{command, AppHandle};
 use std::path::Path;
 use tokio::process::Command;
-use crate::commands::binary_utils::{get_ffmpeg_path, get_ffprobe_path};
+use crate::commands::binary_utils::{app_temp_dir, get_ffmpeg_path, get_ffprobe_path};
+use crate::commands::ffmpeg::{ensure_h264_level_supported, resolution_to_h264_level};
+use crate::commands::operations::{cancellable_output, TrackedOutputAsync};
+
+// Enhancement filters applied after the lanczos scale for each upscale model,
+// shared by the video and image upscalers
+const UPSCALE_MODEL_FILTERS: &[(&str, &str)] = &[
+    // Real-ESRGAN style: sharpening + denoising
+    ("realesrgan", "unsharp=5:5:0.8:3:3:0.4,eq=contrast=1.1:brightness=0.02"),
+    // ESRGAN style: moderate sharpening
+    ("esrgan", "unsharp=3:3:0.5:2:2:0.2"),
+    // Waifu2x style: anime-optimized
+    ("waifu2x", "eq=contrast=1.2:brightness=0.05:saturation=1.1,unsharp=2:2:0.3"),
+    ("lanczos", ""),
+];
+
+// Factors offered in the UI; upscale_video accepts any factor from 1.1 to 8
+const UPSCALE_PRESET_FACTORS: &[f64] = &[1.5, 2.0, 3.0, 4.0, 8.0];
+
+// Image extensions accepted by upscale_image
+const UPSCALE_IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "bmp", "tiff", "webp"];
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UpscaleModelInfo {
+    pub name: String,
+    pub supported_factors: Vec<f64>,
+}
+
+/// Scaled dimension rounded to an even number, as yuv420p requires
+fn scaled_even_dimension(dimension: u32, upscale_factor: f64) -> u32 {
+    ((dimension as f64 * upscale_factor / 2.0).round() * 2.0) as u32
+}
+
+/// Build the scale + enhancement filter chain for an upscale model
+fn upscale_filter_chain(model: &str, upscale_factor: f64) -> String {
+    // Fractional factors can land on odd sizes, so round to even like scaled_even_dimension
+    let scale_filter = format!(
+        "scale=round(iw*{factor}/2)*2:round(ih*{factor}/2)*2:flags=lanczos",
+        factor = upscale_factor
+    );
+    match UPSCALE_MODEL_FILTERS.iter().find(|(name, _)| *name == model) {
+        Some((_, enhancement)) if !enhancement.is_empty() => format!("{},{}", scale_filter, enhancement),
+        _ => scale_filter,
+    }
+}
 
 /// Upscale video using AI models
 #[command]
@@ -9,7 +53,7 @@ pub async fn upscale_video(
     app: AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
     model: String,
     quality: String,
     _add_to_timeline: bool,
@@ -19,16 +63,16 @@ pub async fn upscale_video(
     }
 
     // Validate upscale factor
-    if upscale_factor != 2 && upscale_factor != 4 && upscale_factor != 8 {
-        return Err("Upscale factor must be 2, 4, or 8".to_string());
+    if !(1.1..=8.0).contains(&upscale_factor) {
+        return Err("Upscale factor must be between 1.1 and 8".to_string());
     }
 
     // Get video metadata
     let metadata = get_video_metadata(&app, &input_path).await?;
     let original_width = metadata.width;
     let original_height = metadata.height;
-    let target_width = original_width * upscale_factor;
-    let target_height = original_height * upscale_factor;
+    let target_width = scaled_even_dimension(original_width, upscale_factor);
+    let target_height = scaled_even_dimension(original_height, upscale_factor);
 
     // Check if target resolution is too high (limit to 4K)
     if target_width > 3840 || target_height > 2160 {
@@ -41,11 +85,14 @@ pub async fn upscale_video(
     println!("Upscaling video from {}x{} to {}x{} using {}", 
              original_width, original_height, target_width, target_height, model);
 
+    ensure_h264_level_supported(&app, target_width, target_height, metadata.fps).await?;
+    let h264_level = resolution_to_h264_level(target_width, target_height, metadata.fps);
+
     match model.as_str() {
-        "realesrgan" => upscale_with_realesrgan(&app, input_path, output_path, upscale_factor, quality).await,
-        "esrgan" => upscale_with_esrgan(&app, input_path, output_path, upscale_factor, quality).await,
-        "waifu2x" => upscale_with_waifu2x(&app, input_path, output_path, upscale_factor, quality).await,
-        "lanczos" => upscale_with_lanczos(&app, input_path, output_path, upscale_factor).await,
+        "realesrgan" => upscale_with_realesrgan(&app, input_path, output_path, upscale_factor, quality, h264_level).await,
+        "esrgan" => upscale_with_esrgan(&app, input_path, output_path, upscale_factor, quality, h264_level).await,
+        "waifu2x" => upscale_with_waifu2x(&app, input_path, output_path, upscale_factor, quality, h264_level).await,
+        "lanczos" => upscale_with_lanczos(&app, input_path, output_path, upscale_factor, h264_level).await,
         _ => Err(format!("Unsupported model: {}", model))
     }
 }
@@ -118,12 +165,13 @@ async fn upscale_with_realesrgan(
     app: &AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
     quality: String,
+    h264_level: &str,
 ) -> Result<String, String> {
     // For now, we'll use FFmpeg with enhanced filters as a fallback
     // In a real implementation, you'd integrate with Real-ESRGAN Python scripts
-    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "realesrgan").await
+    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "realesrgan", h264_level).await
 }
 
 /// Upscale using ESRGAN
@@ -131,10 +179,11 @@ async fn upscale_with_esrgan(
     app: &AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
     quality: String,
+    h264_level: &str,
 ) -> Result<String, String> {
-    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "esrgan").await
+    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "esrgan", h264_level).await
 }
 
 /// Upscale using Waifu2x (optimized for anime/illustrations)
@@ -142,10 +191,11 @@ async fn upscale_with_waifu2x(
     app: &AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
     quality: String,
+    h264_level: &str,
 ) -> Result<String, String> {
-    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "waifu2x").await
+    upscale_with_ffmpeg_enhanced(app, input_path, output_path, upscale_factor, quality, "waifu2x", h264_level).await
 }
 
 /// Upscale using Lanczos (traditional, fast)
@@ -153,7 +203,8 @@ async fn upscale_with_lanczos(
     app: &AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
+    h264_level: &str,
 ) -> Result<String, String> {
     let ffmpeg_path = get_ffmpeg_path(app)?;
     let mut ffmpeg_cmd = Command::new(ffmpeg_path);
@@ -161,9 +212,7 @@ async fn upscale_with_lanczos(
         .arg("-i")
         .arg(&input_path)
         .arg("-vf")
-        .arg(format!("scale={}:{}:flags=lanczos", 
-                     "iw*".to_string() + &upscale_factor.to_string(),
-                     "ih*".to_string() + &upscale_factor.to_string()))
+        .arg(upscale_filter_chain("lanczos", upscale_factor))
         .arg("-c:v")
         .arg("libx264")
         .arg("-preset")
@@ -171,16 +220,13 @@ async fn upscale_with_lanczos(
         .arg("-crf")
         .arg("18")
         .arg("-level")
-        .arg("6.2")  // Support up to 4K
+        .arg(h264_level)
         .arg("-profile:v")
         .arg("high")
         .arg("-y")
         .arg(&output_path);
 
-    let output = ffmpeg_cmd
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+    let output = cancellable_output(app, "upscale", &input_path, &output_path, &mut ffmpeg_cmd).await?;
 
     if !output.status.success() {
         let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -195,34 +241,16 @@ async fn upscale_with_ffmpeg_enhanced(
     app: &AppHandle,
     input_path: String,
     output_path: String,
-    upscale_factor: u32,
+    upscale_factor: f64,
     quality: String,
     model: &str,
+    h264_level: &str,
 ) -> Result<String, String> {
     let ffmpeg_path = get_ffmpeg_path(app)?;
     let mut ffmpeg_cmd = Command::new(ffmpeg_path);
     
-    // Base scaling
-    let scale_filter = format!("scale={}:{}:flags=lanczos", 
-                              "iw*".to_string() + &upscale_factor.to_string(),
-                              "ih*".to_string() + &upscale_factor.to_string());
-
-    // Add model-specific enhancements
-    let enhanced_filter = match model {
-        "realesrgan" => {
-            // Real-ESRGAN style: sharpening + denoising
-            format!("{},unsharp=5:5:0.8:3:3:0.4,eq=contrast=1.1:brightness=0.02", scale_filter)
-        },
-        "esrgan" => {
-            // ESRGAN style: moderate sharpening
-            format!("{},unsharp=3:3:0.5:2:2:0.2", scale_filter)
-        },
-        "waifu2x" => {
-            // Waifu2x style: anime-optimized
-            format!("{},eq=contrast=1.2:brightness=0.05:saturation=1.1,unsharp=2:2:0.3", scale_filter)
-        },
-        _ => scale_filter
-    };
+    // Scaling plus model-specific enhancements
+    let enhanced_filter = upscale_filter_chain(model, upscale_factor);
 
     ffmpeg_cmd
         .arg("-i")
@@ -232,7 +260,7 @@ async fn upscale_with_ffmpeg_enhanced(
         .arg("-c:v")
         .arg("libx264")
         .arg("-level")
-        .arg("6.2")  // Support up to 4K
+        .arg(h264_level)
         .arg("-profile:v")
         .arg("high");
 
@@ -272,8 +300,317 @@ async fn upscale_with_ffmpeg_enhanced(
         .arg("-y")
         .arg(&output_path);
 
-    let output = ffmpeg_cmd
-        .output()
+    let output = cancellable_output(app, "upscale", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(format!("Video upscaled with {}: {}", model, output_path))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UpscaleCompressResult {
+    pub output_path: String,
+    pub input_size_mb: f64,
+    pub output_size_mb: f64,
+    pub upscale_factor: u32,
+    pub actual_bitrate_kbps: u32,
+}
+
+// Audio bitrate reserved out of the size budget when compressing
+const COMPRESS_AUDIO_BITRATE_KBPS: u32 = 128;
+
+// Below this the upscale is wasted on compression artifacts
+const MIN_COMPRESS_VIDEO_BITRATE_KBPS: u32 = 500;
+
+fn file_size_mb(path: &Path) -> f64 {
+    std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0) as f64 / (1024.0 * 1024.0)
+}
+
+/// Upscale with the Real-ESRGAN style chain, then two-pass H.264 encode to fit `target_size_mb`
+#[command]
+pub async fn upscale_and_compress(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    upscale_factor: u32,
+    target_size_mb: u32,
+) -> Result<UpscaleCompressResult, String> {
+    if !Path::new(&input_path).exists() {
+        return Err("Input video file does not exist".to_string());
+    }
+    if target_size_mb < 1 {
+        return Err("Target size must be at least 1 MB".to_string());
+    }
+    if !(2..=8).contains(&upscale_factor) {
+        return Err("Upscale factor must be between 2 and 8".to_string());
+    }
+
+    let metadata = get_video_metadata(&app, &input_path).await?;
+    if metadata.duration <= 0.0 {
+        return Err("Could not determine video duration".to_string());
+    }
+    let target_width = scaled_even_dimension(metadata.width, upscale_factor as f64);
+    let target_height = scaled_even_dimension(metadata.height, upscale_factor as f64);
+    if target_width > 3840 || target_height > 2160 {
+        return Err(format!(
+            "Target resolution {}x{} exceeds 4K limit (3840x2160). Try a lower upscale factor.",
+            target_width, target_height
+        ));
+    }
+
+    // Size budget in kilobits over the duration, less the audio track
+    let total_kbps = (target_size_mb as f64 * 8.0 * 1024.0 / metadata.duration) as u32;
+    let video_kbps = total_kbps.saturating_sub(COMPRESS_AUDIO_BITRATE_KBPS);
+    if video_kbps < MIN_COMPRESS_VIDEO_BITRATE_KBPS {
+        return Err(format!(
+            "A {} MB target leaves only {} kbps of video for {:.0} seconds; at least {} kbps is needed",
+            target_size_mb, video_kbps, metadata.duration, MIN_COMPRESS_VIDEO_BITRATE_KBPS
+        ));
+    }
+
+    let work_dir = app_temp_dir(&app)
+        .join("clipforge_video_upscale")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    ensure_h264_level_supported(&app, target_width, target_height, metadata.fps).await?;
+    let h264_level = resolution_to_h264_level(target_width, target_height, metadata.fps);
+    let result = async {
+        let intermediate_path = work_dir.join("upscaled.mp4").to_string_lossy().to_string();
+        upscale_with_ffmpeg_enhanced(&app, input_path.clone(), intermediate_path.clone(), upscale_factor as f64, "high".to_string(), "realesrgan", h264_level).await?;
+        encode_two_pass(&app, &intermediate_path, &output_path, video_kbps, h264_level, &work_dir).await
+    }
+    .await;
+
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to clean up temp directory: {}", e);
+    }
+    result?;
+
+    // Two-pass ABR lands near the target but not on it, so report what the file actually averages
+    let output_bytes = std::fs::metadata(&output_path)
+        .map_err(|e| format!("Failed to read output file: {}", e))?
+        .len();
+    let output_duration = get_video_metadata(&app, &output_path).await?.duration;
+    if output_duration <= 0.0 {
+        return Err("Could not determine output video duration".to_string());
+    }
+
+    Ok(UpscaleCompressResult {
+        output_path: output_path.clone(),
+        input_size_mb: file_size_mb(Path::new(&input_path)),
+        output_size_mb: file_size_mb(Path::new(&output_path)),
+        upscale_factor,
+        actual_bitrate_kbps: (output_bytes as f64 * 8.0 / 1000.0 / output_duration).round() as u32,
+    })
+}
+
+/// Two-pass H.264 ABR encode at `video_kbps`, keeping the pass log in `work_dir`
+async fn encode_two_pass(
+    app: &AppHandle,
+    input_path: &str,
+    output_path: &str,
+    video_kbps: u32,
+    h264_level: &str,
+    work_dir: &Path,
+) -> Result<(), String> {
+    let ffmpeg_path = get_ffmpeg_path(app)?;
+    let passlog = work_dir.join("passlog");
+    let bitrate = format!("{}k", video_kbps);
+
+    // The first pass only gathers statistics, so its output is discarded
+    let mut first_pass = Command::new(&ffmpeg_path);
+    first_pass
+        .arg("-i")
+        .arg(input_path)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("medium")
+        .arg("-b:v")
+        .arg(&bitrate)
+        .arg("-pass")
+        .arg("1")
+        .arg("-passlogfile")
+        .arg(&passlog)
+        .arg("-an")
+        .arg("-f")
+        .arg("null")
+        .arg("-y")
+        .arg("-");
+    let output = cancellable_output(app, "compress", input_path, output_path, &mut first_pass).await?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    let mut second_pass = Command::new(&ffmpeg_path);
+    second_pass
+        .arg("-i")
+        .arg(input_path)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("medium")
+        .arg("-b:v")
+        .arg(&bitrate)
+        .arg("-pass")
+        .arg("2")
+        .arg("-passlogfile")
+        .arg(&passlog)
+        .arg("-level")
+        .arg(h264_level)
+        .arg("-profile:v")
+        .arg("high")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-b:a")
+        .arg(format!("{}k", COMPRESS_AUDIO_BITRATE_KBPS))
+        .arg("-movflags")
+        .arg("+faststart")
+        .arg("-y")
+        .arg(output_path);
+    let output = cancellable_output(app, "compress", input_path, output_path, &mut second_pass).await?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(())
+}
+
+/// Pseudo super-resolution: over-sharpen, 2x lanczos with denoise, then the final scale and a light sharpen
+#[command]
+pub async fn apply_super_resolution(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    scale: u32,
+) -> Result<String, String> {
+    if !Path::new(&input_path).exists() {
+        return Err("Input video file does not exist".to_string());
+    }
+    if scale != 2 && scale != 4 {
+        return Err("Super resolution scale must be 2 or 4".to_string());
+    }
+
+    let metadata = get_video_metadata(&app, &input_path).await?;
+    let target_width = metadata.width * scale;
+    let target_height = metadata.height * scale;
+    if target_width > 3840 || target_height > 2160 {
+        return Err(format!(
+            "Target resolution {}x{} exceeds 4K limit (3840x2160). Try a lower scale.",
+            target_width, target_height
+        ));
+    }
+
+    ensure_h264_level_supported(&app, target_width, target_height, metadata.fps).await?;
+
+    // Each stage feeds the next through labeled pads so the whole pipeline is one decode/encode
+    let filter_complex = format!(
+        "[0:v]unsharp=9:9:2.0:5:5:0.5[sharpened];\
+[sharpened]scale=iw*2:ih*2:flags=lanczos,hqdn3d=1.5:1.5:4:4[doubled];\
+[doubled]scale={}:{}:flags=lanczos,unsharp=3:3:0.8[outv]",
+        target_width, target_height
+    );
+    println!("Super resolution {}x: {}", scale, filter_complex);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = Command::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-filter_complex")
+        .arg(&filter_complex)
+        .arg("-map")
+        .arg("[outv]")
+        .arg("-map")
+        .arg("0:a?")
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("slow")
+        .arg("-crf")
+        .arg("18")
+        .arg("-level")
+        .arg(resolution_to_h264_level(target_width, target_height, metadata.fps))
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "super_resolution", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
+}
+
+/// Upscale a still image with the same model filter chains as video upscaling
+#[command]
+pub async fn upscale_image(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    scale_factor: u32,
+    model: String,
+) -> Result<String, String> {
+    let input = Path::new(&input_path);
+    if !input.exists() {
+        return Err("Input image file does not exist".to_string());
+    }
+
+    let extension = input
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    if !UPSCALE_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Unsupported image format: {}. Supported: {}",
+            extension,
+            UPSCALE_IMAGE_EXTENSIONS.join(", ")
+        ));
+    }
+
+    if scale_factor != 2 && scale_factor != 4 && scale_factor != 8 {
+        return Err("Upscale factor must be 2, 4, or 8".to_string());
+    }
+    if !UPSCALE_MODEL_FILTERS.iter().any(|(name, _)| *name == model) {
+        return Err(format!("Unsupported model: {}", model));
+    }
+
+    let metadata = get_video_metadata(&app, &input_path).await?;
+    let target_width = metadata.width * scale_factor;
+    let target_height = metadata.height * scale_factor;
+    if target_width > 16384 || target_height > 16384 {
+        return Err(format!(
+            "Target resolution {}x{} exceeds the 16384x16384 limit. Try a lower upscale factor.",
+            target_width, target_height
+        ));
+    }
+
+    println!("Upscaling image from {}x{} to {}x{} using {}",
+             metadata.width, metadata.height, target_width, target_height, model);
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let output = Command::new(ffmpeg_path)
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(upscale_filter_chain(&model, scale_factor as f64))
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-y")
+        .arg(&output_path)
+        .tracked_output(&app, "upscale_image", &input_path, &output_path)
         .await
         .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
 
@@ -282,18 +619,108 @@ async fn upscale_with_ffmpeg_enhanced(
         return Err(format!("FFmpeg error: {}", error_msg));
     }
 
-    Ok(format!("Video upscaled with {}: {}", model, output_path))
+    Ok(output_path)
+}
+
+// Pixel samples (width x height x frames) past which the combined denoiser is slow enough to warn about
+const COMBINED_DENOISE_SAMPLE_LIMIT: f64 = 10_000_000.0;
+
+/// Filter chain for a denoise algorithm at `strength` (0.0 - 1.0)
+fn denoise_filter(algorithm: &str, strength: f64) -> Result<String, String> {
+    let strength = strength.clamp(0.0, 1.0);
+    let spatial = 1.0 + strength * 7.0;
+    let hqdn3d = format!(
+        "hqdn3d={:.2}:{:.2}:{:.2}:{:.2}",
+        spatial,
+        spatial * 0.75,
+        spatial * 1.5,
+        spatial * 1.125
+    );
+    // 0.02 / 0.04 thresholds at the default strength of 0.5
+    let threshold_a = (strength * 0.04).max(0.005);
+    let threshold_b = threshold_a * 2.0;
+    let atadenoise = format!(
+        "atadenoise=0a={a:.3}:0b={b:.3}:1a={a:.3}:1b={b:.3}:2a={a:.3}:2b={b:.3}:s=9",
+        a = threshold_a,
+        b = threshold_b
+    );
+
+    match algorithm {
+        "hqdn3d" => Ok(hqdn3d),
+        "nlmeans" => Ok(format!("nlmeans=s={:.2}", 1.0 + strength * 9.0)),
+        "median" => Ok(format!("median=radius={}", 1 + (strength * 3.0).round() as u32)),
+        "atadenoise" => Ok(atadenoise),
+        "combined" => Ok(format!("{},{}", hqdn3d, atadenoise)),
+        _ => Err(format!(
+            "Unsupported denoise algorithm: {}. Available: hqdn3d, nlmeans, median, atadenoise, combined",
+            algorithm
+        )),
+    }
+}
+
+/// Reduce video noise with hqdn3d, nlmeans, median, atadenoise, or hqdn3d + atadenoise combined
+#[command]
+pub async fn denoise_video(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    algorithm: String,
+    strength: f64,
+) -> Result<String, String> {
+    if !Path::new(&input_path).exists() {
+        return Err("Input video file does not exist".to_string());
+    }
+    let filter = denoise_filter(&algorithm, strength)?;
+
+    if algorithm == "combined" {
+        let metadata = get_video_metadata(&app, &input_path).await?;
+        let samples = metadata.width as f64 * metadata.height as f64 * metadata.fps * metadata.duration;
+        if samples >= COMBINED_DENOISE_SAMPLE_LIMIT {
+            log::warn!(
+                "Combined denoise on {} covers {:.0} pixel samples and may take a long time",
+                input_path, samples
+            );
+        }
+    }
+
+    let ffmpeg_path = get_ffmpeg_path(&app)?;
+    let mut ffmpeg_cmd = Command::new(ffmpeg_path);
+    ffmpeg_cmd
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-vf")
+        .arg(&filter)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("medium")
+        .arg("-crf")
+        .arg("18")
+        .arg("-c:a")
+        .arg("copy")
+        .arg("-y")
+        .arg(&output_path);
+
+    let output = cancellable_output(&app, "denoise", &input_path, &output_path, &mut ffmpeg_cmd).await?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("FFmpeg error: {}", error_msg));
+    }
+
+    Ok(output_path)
 }
 
 /// Get available upscaling models
 #[command]
-pub async fn get_available_upscale_models() -> Result<Vec<String>, String> {
-    let models = vec![
-        "realesrgan".to_string(),
-        "esrgan".to_string(),
-        "waifu2x".to_string(),
-        "lanczos".to_string(),
-    ];
+pub async fn get_available_upscale_models() -> Result<Vec<UpscaleModelInfo>, String> {
+    let models = UPSCALE_MODEL_FILTERS
+        .iter()
+        .map(|(name, _)| UpscaleModelInfo {
+            name: name.to_string(),
+            supported_factors: UPSCALE_PRESET_FACTORS.to_vec(),
+        })
+        .collect();
     Ok(models)
 }
 
@@ -319,3 +746,76 @@ struct VideoMetadata {
     file_size: u64,
     format: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{assert_filter_chain, render_filter};
+
+    /// Run a 1 second clip of synthetic noise through a denoise algorithm with the ffmpeg on PATH
+    fn denoise_noise_clip(algorithm: &str) -> std::path::PathBuf {
+        render_filter(
+            "color=c=gray:size=320x240:rate=25:duration=1,noise=alls=40:allf=t",
+            &denoise_filter(algorithm, 0.5).unwrap(),
+            &format!("denoise_{}.mp4", algorithm),
+        )
+    }
+
+    #[test]
+    fn denoise_filters_use_only_denoisers() {
+        for algorithm in ["hqdn3d", "atadenoise", "combined"] {
+            assert_filter_chain(&denoise_filter(algorithm, 0.5).unwrap(), &["hqdn3d", "atadenoise"]);
+        }
+    }
+
+    #[test]
+    fn atadenoise_uses_default_thresholds_at_half_strength() {
+        assert_eq!(
+            denoise_filter("atadenoise", 0.5).unwrap(),
+            "atadenoise=0a=0.020:0b=0.040:1a=0.020:1b=0.040:2a=0.020:2b=0.040:s=9"
+        );
+    }
+
+    #[test]
+    fn combined_chains_hqdn3d_before_atadenoise() {
+        let filter = denoise_filter("combined", 0.5).unwrap();
+        let (first, second) = filter.split_once(',').unwrap();
+        assert!(first.starts_with("hqdn3d="));
+        assert!(second.starts_with("atadenoise="));
+    }
+
+    #[test]
+    fn combined_filter_at_half_strength() {
+        assert_eq!(
+            denoise_filter("combined", 0.5).unwrap(),
+            "hqdn3d=4.50:3.38:6.75:5.06,atadenoise=0a=0.020:0b=0.040:1a=0.020:1b=0.040:2a=0.020:2b=0.040:s=9"
+        );
+    }
+
+    #[test]
+    fn atadenoise_thresholds_have_a_floor() {
+        assert_eq!(
+            denoise_filter("atadenoise", 0.0).unwrap(),
+            "atadenoise=0a=0.005:0b=0.010:1a=0.005:1b=0.010:2a=0.005:2b=0.010:s=9"
+        );
+    }
+
+    #[test]
+    fn unknown_denoise_algorithm_is_rejected() {
+        assert!(denoise_filter("wavelet", 0.5).is_err());
+    }
+
+    #[test]
+    #[ignore = "requires ffmpeg"]
+    fn atadenoise_renders_noise_clip() {
+        let output_path = denoise_noise_clip("atadenoise");
+        assert!(std::fs::metadata(&output_path).unwrap().len() > 0);
+    }
+
+    #[test]
+    #[ignore = "requires ffmpeg"]
+    fn combined_renders_noise_clip() {
+        let output_path = denoise_noise_clip("combined");
+        assert!(std::fs::metadata(&output_path).unwrap().len() > 0);
+    }
+}
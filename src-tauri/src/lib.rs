@@ -1,59 +1,218 @@
 mod commands;
 
 use commands::{
-    ffmpeg::{get_video_metadata, trim_video, export_timeline, convert_mov_to_mp4},
-    filesystem::{import_video, save_video, import_video_from_file, get_video_url, read_file_bytes},
+    get_timeline_gaps, auto_chapter_clips, sort_clips_by, save_clip_as_template, create_clip_from_template,
+    list_templates, delete_template,
+    ffmpeg::{
+        get_video_metadata, get_video_streams, trim_video, export_timeline, cancel_export, convert_mov_to_mp4, deduplicate_frames, smooth_motion,
+        apply_noise_gate, measure_noise_floor, get_aspect_ratio_presets, crop_to_aspect_ratio,
+        generate_audio_visualization, rotate_video, apply_perspective_correction, generate_color_bars, generate_tone,
+        generate_test_pattern, split_video_by_size, split_video_at_points, validate_export_params, export_frame_as_image,
+        repair_video, detect_audio_sync_offset, extract_audio, add_audio_track, get_video_keyframes, get_next_keyframe, smart_trim,
+        visualize_motion_vectors, convert_360_to_flat, batch_generate_thumbnails, generate_social_assets,
+        measure_video_quality_metrics, create_pip, create_multi_angle_sequence,
+        analyze_scene_complexity, measure_camera_shake, render_image_transition, get_encoding_presets, get_available_encoders,
+        get_ffmpeg_capabilities, extract_waveform_data, extract_audio_waveform, measure_av_sync, correct_av_sync, transcode_with_chapters,
+        generate_beat_synced_montage, merge_with_crossfades
+    },
+    filesystem::{
+        import_video, save_video, import_video_from_file, get_video_url, read_file_bytes,
+        import_video_from_url, clear_url_cache, check_file_integrity, copy_file_path_to_clipboard,
+        copy_file_to_clipboard, get_temp_directory_stats, cleanup_temp_files,
+        get_available_fonts, read_file_bytes_chunked, get_file_size, get_file_checksum, estimate_write_speed,
+        estimate_transfer_time, detect_duplicate_clips,
+        set_temp_directory, import_from_clipboard, export_edl, import_edl, export_project_bundle,
+        import_project_bundle, ChecksumCache
+    },
     recording::{
         get_available_monitors, add_capture_source, update_capture_source_position,
-        remove_capture_source, get_capture_sources, start_screen_recording, 
+        remove_capture_source, get_capture_sources, start_screen_recording, start_region_recording,
         start_webcam_recording, stop_recording, pause_recording, resume_recording, 
-        get_recording_status
+        get_recording_status, get_fps_recommendation, rescue_partial_recording
+    },
+    openai::{
+        get_openai_api_key, generate_dalle_image, style_transfer_image, inpaint_frame,
+        generate_rectangular_mask, validate_openai_key,
+        scan_for_copyright_content, generate_subtitles_from_video, transcribe_audio, extract_text_from_video,
+        extract_onscreen_text, search_onscreen_text,
+        configure_openai, get_openai_config, OpenAiConfig
+    },
+    text_to_video::{
+        generate_text_to_video, generate_text_overlay_video, stitch_images_to_video, generate_lower_third,
+        render_text_to_image, generate_narrated_slideshow, generate_summary_card,
+        generate_countdown_clip
+    },
+    video_upscaler::{
+        upscale_video, get_available_upscale_models, get_video_enhancement_options, upscale_image,
+        apply_super_resolution, upscale_and_compress, denoise_video
     },
-    openai::{get_openai_api_key, generate_dalle_image, style_transfer_image, validate_openai_key},
-    text_to_video::{generate_text_to_video, generate_text_overlay_video},
-    video_upscaler::{upscale_video, get_available_upscale_models, get_video_enhancement_options},
     character_extractor::{
         create_temp_directory, create_directory, extract_video_frames, detect_character_in_frame,
-        compare_images, build_character_sprite_sheet, copy_sprite_sheet_to_location, copy_sprite_sheet_to_desktop, remove_directory
+        compare_images, build_character_sprite_sheet, copy_sprite_sheet_to_location, copy_sprite_sheet_to_desktop, remove_directory,
+        extract_sprite_transparent
     },
-    ai_styler::{apply_filters, upscale_media, process_media, copy_file_to_desktop, copy_file_to_location, get_esrgan_models, download_esrgan_model, generate_image_with_dalle},
+    ai_styler::{
+        apply_filters, preview_filters, apply_audio_filters, upscale_media, process_media, copy_file_to_desktop, copy_file_to_location, get_esrgan_models,
+        download_esrgan_model, generate_image_with_dalle, convert_image_format, apply_color_temperature,
+        get_available_film_looks, apply_film_look, correct_vignette,
+        get_color_grade_presets, apply_color_grade, get_auto_correction_algorithms, auto_correct_exposure
+    },
+    subtitles::merge_subtitle_files,
+    operations::{list_active_operations, kill_operation, cancel_ffmpeg_process, OperationRegistry},
+    binary_utils::{
+        get_default_output_path, get_hardware_capabilities, get_supported_input_formats, is_format_supported,
+        ClipForgeConfig
+    },
+    logging::{get_clipforge_logs, clear_logs, ClipForgeLogger},
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let logger = ClipForgeLogger::default();
+    logger.install();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(std::sync::Mutex::new(OpenAiConfig::default()))
+        .manage(std::sync::Mutex::new(OperationRegistry::default()))
+        .manage(std::sync::Mutex::new(ClipForgeConfig::load()))
+        .manage(std::sync::Mutex::new(ChecksumCache::default()))
+        .manage(logger.clone())
+        .setup(move |app| {
+            logger.attach(app.handle().clone());
+
+            // Periodically clear out intermediate files left behind by processing commands
+            let cleanup_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = cleanup_temp_files(cleanup_handle.clone(), 2).await {
+                        log::warn!("Scheduled temp cleanup failed: {}", e);
+                    }
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_video_metadata,
+            get_video_streams,
             trim_video,
             export_timeline,
+            cancel_export,
             convert_mov_to_mp4,
+            deduplicate_frames,
+            smooth_motion,
+            apply_noise_gate,
+            measure_noise_floor,
+            get_aspect_ratio_presets,
+            crop_to_aspect_ratio,
+            generate_audio_visualization,
+            rotate_video,
+            apply_perspective_correction,
+            generate_color_bars,
+            generate_tone,
+            generate_test_pattern,
+            split_video_by_size,
+            split_video_at_points,
+            validate_export_params,
+            export_frame_as_image,
+            repair_video,
+            detect_audio_sync_offset,
+            extract_audio,
+            add_audio_track,
+            get_video_keyframes,
+            get_next_keyframe,
+            smart_trim,
+            visualize_motion_vectors,
+            convert_360_to_flat,
+            batch_generate_thumbnails,
+            generate_social_assets,
+            measure_video_quality_metrics,
+            create_pip,
+            create_multi_angle_sequence,
+            analyze_scene_complexity,
+            measure_camera_shake,
+            render_image_transition,
+            get_encoding_presets,
+            get_available_encoders,
+            get_ffmpeg_capabilities,
+            extract_waveform_data,
+            extract_audio_waveform,
+            measure_av_sync,
+            correct_av_sync,
+            transcode_with_chapters,
+            generate_beat_synced_montage,
+            merge_with_crossfades,
             import_video,
             save_video,
             import_video_from_file,
             get_video_url,
             read_file_bytes,
+            read_file_bytes_chunked,
+            get_file_size,
+            get_file_checksum,
+            estimate_write_speed,
+            estimate_transfer_time,
+            detect_duplicate_clips,
+            set_temp_directory,
+            import_from_clipboard,
+            export_edl,
+            import_edl,
+            export_project_bundle,
+            import_project_bundle,
+            import_video_from_url,
+            clear_url_cache,
+            check_file_integrity,
+            copy_file_path_to_clipboard,
+            copy_file_to_clipboard,
+            get_temp_directory_stats,
+            cleanup_temp_files,
+            get_available_fonts,
             get_available_monitors,
             add_capture_source,
             update_capture_source_position,
             remove_capture_source,
             get_capture_sources,
             start_screen_recording,
+            start_region_recording,
             start_webcam_recording,
             stop_recording,
             pause_recording,
             resume_recording,
             get_recording_status,
+            get_fps_recommendation,
+            rescue_partial_recording,
             get_openai_api_key,
             generate_dalle_image,
             style_transfer_image,
+            inpaint_frame,
+            generate_rectangular_mask,
             validate_openai_key,
+            scan_for_copyright_content,
+            generate_subtitles_from_video,
+            transcribe_audio,
+            extract_text_from_video,
+            extract_onscreen_text,
+            search_onscreen_text,
+            configure_openai,
+            get_openai_config,
             generate_text_to_video,
             generate_text_overlay_video,
+            stitch_images_to_video,
+            generate_lower_third,
+            render_text_to_image,
+            generate_narrated_slideshow,
+            generate_summary_card,
+            generate_countdown_clip,
             upscale_video,
             get_available_upscale_models,
             get_video_enhancement_options,
+            upscale_image,
+            apply_super_resolution,
+            upscale_and_compress,
+            denoise_video,
             create_temp_directory,
             create_directory,
             extract_video_frames,
@@ -63,7 +222,10 @@ pub fn run() {
             copy_sprite_sheet_to_location,
             copy_sprite_sheet_to_desktop,
             remove_directory,
+            extract_sprite_transparent,
             apply_filters,
+            preview_filters,
+            apply_audio_filters,
             upscale_media,
             process_media,
             copy_file_to_desktop,
@@ -71,6 +233,32 @@ pub fn run() {
             get_esrgan_models,
             download_esrgan_model,
             generate_image_with_dalle,
+            convert_image_format,
+            apply_color_temperature,
+            get_available_film_looks,
+            apply_film_look,
+            correct_vignette,
+            get_color_grade_presets,
+            apply_color_grade,
+            get_auto_correction_algorithms,
+            auto_correct_exposure,
+            merge_subtitle_files,
+            list_active_operations,
+            kill_operation,
+            cancel_ffmpeg_process,
+            get_default_output_path,
+            get_hardware_capabilities,
+            get_supported_input_formats,
+            is_format_supported,
+            get_clipforge_logs,
+            clear_logs,
+            get_timeline_gaps,
+            auto_chapter_clips,
+            sort_clips_by,
+            save_clip_as_template,
+            create_clip_from_template,
+            list_templates,
+            delete_template,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
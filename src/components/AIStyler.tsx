@@ -27,6 +27,8 @@ const FILTERS: FilterOption[] = [
   { id: 'pixelate', name: 'Pixelate', description: 'Create pixel art effect', ffmpegFilter: 'scale=iw/8:ih/8:flags=neighbor,scale=iw*8:ih*8:flags=neighbor', category: 'effects' },
   { id: 'emboss', name: 'Emboss', description: '3D embossed effect', ffmpegFilter: 'convolution=0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0:0 -1 0 -1 5 -1 0 -1 0', category: 'effects' },
   { id: 'oil_paint', name: 'Oil Paint', description: 'Oil painting effect', ffmpegFilter: 'gblur=sigma=1.5,eq=saturation=1.5', category: 'effects' },
+  { id: 'noir', name: 'Film Noir', description: 'High-contrast black and white with vignette and grain', ffmpegFilter: "hue=s=0,curves=all='0/0 0.2/0.05 0.5/0.5 0.8/0.95 1/1',vignette=PI/4,noise=alls=8:allf=t", category: 'color' },
+  { id: 'technicolor', name: 'Technicolor', description: 'Saturated three-strip film color', ffmpegFilter: 'colorchannelmixer=rr=1.2:rg=-0.1:rb=-0.1:gr=-0.15:gg=1.15:gb=0.1:br=0.05:bg=-0.15:bb=1.1,eq=saturation=1.25:contrast=1.05', category: 'color' },
 ];
 
 const AIStyler: React.FC = () => {
@@ -15,6 +15,7 @@ interface MonitorInfo {
   width: number;
   height: number;
   is_primary: boolean;
+  scale_factor: number;
 }
 
 interface Sprite {
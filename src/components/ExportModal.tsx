@@ -170,6 +170,8 @@ const ExportModal: React.FC<ExportModalProps> = ({ isOpen, onClose }) => {
             >
               <option value="720p">720p (1280x720)</option>
               <option value="1080p">1080p (1920x1080)</option>
+              <option value="2k">1440p (2560x1440)</option>
+              <option value="4k">4K (3840x2160)</option>
               <option value="original">Original Resolution</option>
             </select>
           </div>
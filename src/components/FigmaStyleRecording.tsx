@@ -10,6 +10,7 @@ interface MonitorInfo {
   width: number;
   height: number;
   is_primary: boolean;
+  scale_factor: number;
 }
 
 interface CaptureSource {
@@ -7,6 +7,10 @@ export interface VideoMetadata {
   fps: number;
   file_size: number;
   format: string;
+  codec_name?: string;
+  codec_long_name?: string;
+  codec_profile?: string | null;
+  bit_rate_kbps?: number;
 }
 
 export interface VideoClip {
@@ -15,6 +19,8 @@ export interface VideoClip {
   metadata: VideoMetadata;
   start_time: number;
   end_time: number;
+  volume?: number;
+  filters?: string[];
 }
 
 export interface TimelineClip extends VideoClip {
@@ -15,6 +15,7 @@ export interface TrimParams {
   outputPath: string;
   startTime: number;
   endTime: number;
+  snapToKeyframe?: boolean;
 }
 
 export interface ExportParams {